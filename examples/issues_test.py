@@ -99,7 +99,7 @@ async def main():
                     'Comments': issue['comments_count'],
                     'Labels': ', '.join(issue['labels']) if issue['labels'] else '',
                     'Assignees': ', '.join(issue['assignees']) if issue['assignees'] else '',
-                    'Milestone': issue['milestone'] if issue['milestone'] else '',
+                    'Milestone': issue['milestone_title'] or '',
                     'Locked': issue['locked'],
                     'URL': issue['html_url'],
                 }
@@ -1,21 +1,24 @@
-from typing import Dict, List, Optional, Union
+from typing import AsyncIterator, Dict, List, Optional, Tuple, Union
 
 from .gradelib import setup_async as _setup_async
+from .gradelib import markdown_to_text
 from .gradelib import RepoManager as _RustRepoManager
 from .gradelib import TaigaClient
 from .gradelib import GitHubOAuthClient
 from .types import (
     CloneStatus, CloneTask,
-    CommitInfo, BlameLineInfo, CollaboratorInfo,
-    IssueInfo, PullRequestInfo, CodeReviewInfo,
-    CommentInfo, BranchInfo,
-    CloneStatusType, CommentType,
+    CommitInfo, CommitSummary, DiffStats, CoauthorEdge, CoauthorshipGraph, BlameLineInfo, Authorship, BlameAuthorship, CollaboratorInfo,
+    MilestoneInfo, IssueInfo, IssueSearchResults, EventInfo, PullRequestInfo, CodeReviewInfo, PullRequestReviews, PullRequestFileInfo,
+    ReviewCommentInfo, PullRequestCommitInfo, PullRequestCommits,
+    CommentInfo, BranchInfo, ForcePushInfo, RepoSummary, RepoReport, RepoAccess, OwnerRepoInfo, RateLimitStatus,
+    CloneStatusType, CommentType, CloneFailure, CloneSummary, FileEncoding,
     convert_clone_tasks,
 )
 from .async_handler import async_handler
 
 __all__ = [
     "setup_async",
+    "markdown_to_text",
     "RepoManager",
     "CloneStatus",
     "CloneTask",
@@ -40,7 +43,7 @@ class RepoManager:
     This class provides a Python-friendly interface to the underlying Rust implementation.
     """
 
-    def __init__(self, urls: List[str], github_username: str, github_token: str) -> None:
+    def __init__(self, urls: List[str], github_username: str, github_token: str, target_dir: Optional[str] = None, clone_timeout: Optional[float] = None, http_timeout: Optional[float] = None, pool_max_idle_per_host: Optional[int] = None, proxy_url: Optional[str] = None, client_headers: Optional[Dict[str, str]] = None, max_concurrent_clones: Optional[int] = None, clone_retries: Optional[int] = None, token_type: Optional[str] = None, clone_dir_scheme: Optional[str] = None) -> None:
         """
         Initialize a new RepoManager with GitHub credentials.
 
@@ -48,9 +51,88 @@ class RepoManager:
             urls: List of repository URLs to manage
             github_username: GitHub username for authentication
             github_token: GitHub personal access token for authentication
+            target_dir: Optional directory for persistent clones (None = temporary directories).
+                Repositories are cloned into target_dir/<owner>__<repo>; an existing clone
+                there is fetched instead of re-cloned, and its branch and working tree are
+                reset to what was fetched. A clone of a different origin URL in that
+                directory fails the task.
+            clone_timeout: Optional limit in seconds for each clone (None = no limit). A clone
+                that runs longer is aborted and its task fails with "timed out after Ns".
+            http_timeout: Optional limit in seconds for connecting to and completing each GitHub
+                API request (None = 30 seconds). Timed-out requests report "request timed out".
+            pool_max_idle_per_host: Optional number of idle API connections to keep open per host
+            proxy_url: Optional HTTP(S) proxy for API requests and clones, e.g.
+                "http://proxy.example.edu:3128" (None = the HTTPS_PROXY environment variable)
+            client_headers: Optional extra headers sent with every GitHub API request, e.g.
+                {"User-Agent": "example-university-grader", "X-Institution": "Example U"}.
+                A User-Agent here replaces the default one; Authorization can't be set.
+            max_concurrent_clones: Optional cap on clones running at once (None = 8). Further
+                clones wait with status "queued" until a slot frees up.
+            clone_retries: Optional number of retries after a transient network failure such
+                as a DNS error or dropped connection (None = 2). Authentication failures are
+                never retried; CloneTask.attempts records the attempts made.
+            token_type: How github_token is sent to the API: "pat" (default) for personal
+                access tokens ("Authorization: token ...") or "oauth" for OAuth and GitHub
+                App tokens ("Authorization: Bearer ...")
+            clone_dir_scheme: Optional layout of persistent clones under target_dir:
+                "owner__repo" (default), "full-slug" for nested target_dir/owner/repo, or a
+                template such as "{owner}-{repo}" or "students/{owner}/{repo}". Owner and
+                repository names are sanitized so a crafted URL can't escape target_dir.
         """
         self._rust_manager = _RustRepoManager(
-            urls, github_username, github_token)
+            urls, github_token, github_username, target_dir=target_dir, clone_timeout=clone_timeout,
+            http_timeout=http_timeout, pool_max_idle_per_host=pool_max_idle_per_host,
+            proxy_url=proxy_url, client_headers=client_headers,
+            max_concurrent_clones=max_concurrent_clones, clone_retries=clone_retries,
+            token_type=token_type, clone_dir_scheme=clone_dir_scheme)
+
+    @classmethod
+    def from_env(cls, urls: List[str], target_dir: Optional[str] = None, clone_timeout: Optional[float] = None, http_timeout: Optional[float] = None, pool_max_idle_per_host: Optional[int] = None, proxy_url: Optional[str] = None, client_headers: Optional[Dict[str, str]] = None, max_concurrent_clones: Optional[int] = None, clone_retries: Optional[int] = None, token_type: Optional[str] = None, clone_dir_scheme: Optional[str] = None) -> "RepoManager":
+        """
+        Create a RepoManager using GitHub credentials from the environment.
+
+        Reads GITHUB_TOKEN (required) and GITHUB_USERNAME (optional), so tokens
+        don't need to be hardcoded in scripts or notebooks.
+
+        Args:
+            urls: List of repository URLs to manage
+            target_dir: Optional directory for persistent clones (None = temporary directories)
+            clone_timeout: Optional limit in seconds for each clone (None = no limit)
+            http_timeout: Optional limit in seconds for each GitHub API request (None = 30 seconds)
+            pool_max_idle_per_host: Optional number of idle API connections to keep open per host
+            token_type: "pat" (default) or "oauth", depending on the kind of GITHUB_TOKEN
+            clone_dir_scheme: Optional layout of persistent clones (None = "owner__repo")
+
+        Returns:
+            A new RepoManager
+
+        Raises:
+            ValueError: If GITHUB_TOKEN is not set, a timeout is not positive, proxy_url or
+                client_headers is invalid, max_concurrent_clones is 0, or token_type or
+                clone_dir_scheme is invalid
+        """
+        manager = cls.__new__(cls)
+        manager._rust_manager = _RustRepoManager.from_env(
+            urls, target_dir, clone_timeout, http_timeout, pool_max_idle_per_host, proxy_url,
+            client_headers, max_concurrent_clones, clone_retries, token_type, clone_dir_scheme)
+        return manager
+
+    def update_token(self, new_token: str) -> None:
+        """
+        Replaces the GitHub token, e.g. after rotating a secret in a long-running service.
+
+        Clones and API calls started after this returns use the new token; calls already
+        in flight finish with the old one. Clone state and cached results are kept, and
+        the token type (PAT or OAuth) stays as configured.
+
+        Args:
+            new_token: The GitHub token to use from now on
+
+        Raises:
+            ValueError: If new_token is empty or not a valid HTTP header value,
+                e.g. it contains a newline; the old token is kept
+        """
+        self._rust_manager.update_token(new_token)
 
     async def clone_all(self) -> None:
         """
@@ -61,6 +143,22 @@ class RepoManager:
         """
         return await self._rust_manager.clone_all()
 
+    async def clone_all_and_wait(self) -> Dict[str, CloneStatus]:
+        """
+        Clones all repositories configured in this manager instance and waits for them.
+
+        Unlike polling fetch_clone_tasks after clone_all, this resolves only once every
+        clone has completed or failed.
+
+        Returns:
+            A dictionary mapping repository URLs to their final CloneStatus, all "completed"
+
+        Raises:
+            ValueError: If any clone failed, naming each failed URL with its error
+        """
+        rust_statuses = await self._rust_manager.clone_all_and_wait()
+        return {url: CloneStatus.from_rust(status) for url, status in rust_statuses.items()}
+
     async def fetch_clone_tasks(self) -> Dict[str, CloneTask]:
         """
         Fetches the current status of all cloning tasks asynchronously.
@@ -73,71 +171,460 @@ class RepoManager:
             raise ValueError("Failed to fetch clone tasks")
         return convert_clone_tasks(rust_tasks)
 
-    async def clone(self, url: str) -> None:
+    def watch_clone_progress(self) -> AsyncIterator[Tuple[str, CloneStatusType, Optional[int]]]:
+        """
+        Streams clone status changes as they happen, instead of polling fetch_clone_tasks.
+
+        Events are buffered from the moment this is called, so start watching before
+        calling clone_all. Iteration ends once every task has completed or failed.
+
+        Returns:
+            An async iterator of (url, status_type, progress) tuples; progress is the
+            percentage for "cloning" events and None otherwise
+        """
+        return self._rust_manager.watch_clone_progress()
+
+    def canonical_url(self, url: str) -> str:
+        """
+        Returns the canonical form of a repository URL.
+
+        SSH and HTTPS URLs for the same repository (e.g. "git@github.com:owner/repo.git"
+        and "https://github.com/owner/repo") share one canonical URL, so they are only
+        cloned once by this manager.
+
+        Args:
+            url: The repository URL to normalize
+
+        Returns:
+            The canonical URL, or the input unchanged if it is not a recognized git URL
+        """
+        return self._rust_manager.canonical_url(url)
+
+    def clone_summary(self) -> CloneSummary:
+        """
+        Tallies the clone tasks by status, e.g. to report how a batch of clones ended.
+
+        All counts come from one snapshot of the tasks, so they add up to the number of
+        tasks even while clones are still running.
+
+        Returns:
+            The number of tasks per status, plus the URL and error message of each failed
+            task under "failures"
+        """
+        result = self._rust_manager.clone_summary()
+        if not isinstance(result, dict):
+            raise TypeError(f"Expected CloneSummary, got {type(result)}")
+        return result
+
+    def validate_urls(self) -> Dict[str, Optional[str]]:
+        """
+        Checks every managed repository URL without making any requests.
+
+        URLs are run through the same parser the GitHub API methods use, so problems
+        such as a missing owner or a non-GitHub host show up before any fetch. HTTPS
+        and SSH URLs are accepted, with or without a ".git" suffix or trailing slash.
+
+        Returns:
+            Dictionary mapping each URL to None if it is valid, or to an error message
+        """
+        return self._rust_manager.validate_urls()
+
+    def register_local_repo(self, identifier: str, path: str) -> None:
+        """
+        Registers a repository that is already on disk, e.g. checked out by a CI step.
+
+        The repository is added as a completed clone task, so methods that take repository
+        URLs (analyze_branches, analyze_commits_bulk, commit_summary, ...) accept the
+        identifier without anything being cloned. The directory is used in place and is
+        never modified or deleted.
+
+        Args:
+            identifier: Name to refer to the repository by, typically its URL
+            path: Path to the repository's working directory (or its .git directory)
+        """
+        self._rust_manager.register_local_repo(identifier, path)
+
+    def clear_blame_cache(self) -> None:
+        """
+        Drops all cached blame results and resets the cache counters.
+
+        bulk_blame caches each file's result per commit and reuses it until the
+        repository's HEAD moves, so this is only needed to free memory or when files
+        in a clone's working tree were edited by hand.
+        """
+        self._rust_manager.clear_blame_cache()
+
+    def blame_cache_info(self) -> Dict[str, int]:
+        """
+        Reports how the blame cache has been used.
+
+        Returns:
+            Dictionary with "hits", "misses" (each one a blame that was actually run)
+            and "entries" (cached file blames)
+        """
+        return self._rust_manager.blame_cache_info()
+
+    def cancel_clone(self, url: str) -> bool:
+        """
+        Cancels an in-flight clone.
+
+        The clone task is marked as failed with the error "cancelled". Cancelling a
+        clone that isn't running (e.g. already completed) is a harmless no-op.
+
+        Args:
+            url: The repository URL whose clone should be cancelled
+
+        Returns:
+            True if a running clone was signalled, False otherwise
+        """
+        return self._rust_manager.cancel_clone(url)
+
+    def cancel_all(self) -> int:
+        """
+        Cancels all in-flight clones.
+
+        Returns:
+            The number of running clones that were signalled
+        """
+        return self._rust_manager.cancel_all()
+
+    def export_json(self, path: str, data_kind: str, repo_url: str) -> None:
+        """
+        Writes the most recent results of one kind for a repository to a JSON file.
+
+        Args:
+            path: Destination file path
+            data_kind: One of "commits", "blame", "branches", "collaborators", "issues"
+                or "pull_requests"
+            repo_url: The repository URL (or local path, for commits and blame) the
+                results were fetched or analyzed with
+
+        Raises:
+            ValueError: If the kind is unknown, no such results have been fetched yet,
+                or the file cannot be written
+        """
+        self._rust_manager.export_json(path, data_kind, repo_url)
+
+    async def clone(self, url: str, branch: Optional[str] = None, extra_args: Optional[List[str]] = None, checkout: Optional[str] = None) -> None:
         """
         Clones a single repository specified by URL asynchronously.
 
         Args:
             url: The repository URL to clone
+            branch: Optional branch to check out instead of the default branch.
+                If it doesn't exist the clone task fails with a message naming the branch.
+            extra_args: Optional `git clone` flags. Supported are "--depth <n>" (shallow clone),
+                "--no-tags" and "--branch <name>". Flags that could change the URL, destination
+                or transport (e.g. "--upload-pack", "--template", "-c") are rejected.
+                "--filter" (e.g. "--filter=blob:none") is rejected too: clones go through
+                libgit2, which cannot make partial clones. Use "--depth 1" to keep large
+                repositories small instead.
+            checkout: Optional branch, tag or commit SHA to check out once the clone has
+                finished, e.g. a submission's SHA for a fixed grading snapshot. Branches are
+                checked out as branches; tags and SHAs leave HEAD detached. If it can't be
+                checked out the clone task fails with a message naming it.
 
         Returns:
             None
+
+        Raises:
+            ValueError: If extra_args contains an unsupported or malformed flag
+        """
+        return await self._rust_manager.clone(url, branch, extra_args, checkout)
+
+    async def clone_and_wait(self, url: str, branch: Optional[str] = None, extra_args: Optional[List[str]] = None, checkout: Optional[str] = None) -> CloneStatus:
         """
-        return await self._rust_manager.clone(url)
+        Clones a single repository and waits for the clone to finish.
+
+        Takes the same arguments as clone, but resolves only once the clone task has
+        completed, so there is no need to poll fetch_clone_tasks.
+
+        Args:
+            url: The repository URL to clone
+            branch: Optional branch to check out instead of the default branch
+            extra_args: Optional `git clone` flags, as for clone
+            checkout: Optional branch, tag or commit SHA to check out once cloned
+
+        Returns:
+            The final CloneStatus of the task, with status_type "completed"
 
-    async def bulk_blame(self, repo_path: str, file_paths: List[str]) -> Dict[str, Union[List[BlameLineInfo], str]]:
+        Raises:
+            ValueError: If extra_args contains an unsupported or malformed flag, the url
+                was not passed to the constructor (nothing is cloned), or the clone failed
+                (the message is the task's error)
+        """
+        rust_status = await self._rust_manager.clone_and_wait(url, branch, extra_args, checkout)
+        return CloneStatus.from_rust(rust_status)
+
+    async def bulk_blame(self, repo_path: str, file_paths: List[str], ignore_whitespace: bool = False, detect_moves: bool = False, detect_copies: bool = False, max_parallel: Optional[int] = None, rev: Optional[str] = None) -> Dict[str, Union[List[BlameLineInfo], str]]:
         """
         Performs 'git blame' on multiple files within a cloned repository asynchronously.
 
         Args:
             repo_path: The local path to the cloned repository to analyze
             file_paths: List of file paths within the repository to blame
+            ignore_whitespace: Ignore whitespace-only changes (like `git blame -w`)
+            detect_moves: Follow lines moved within a file (like `git blame -M`)
+            detect_copies: Follow lines copied from other files (like `git blame -C`)
+            max_parallel: Optional cap on how many files are blamed at once (None = number of CPUs)
+            rev: Optional revision (commit, branch or tag) to blame at instead of the working tree.
+                An unknown revision is reported as a per-file error string.
 
         Returns:
             Dictionary mapping file paths to either blame information or error strings
+            (a repository with no commits yet gives "EmptyRepository: ..." for every file).
+            Binary files, by content or by a `binary`/`-diff` .gitattributes entry, are not
+            blamed; they map to an error string starting with "BinaryFile:"
         """
-        result = await self._rust_manager.bulk_blame(repo_path, file_paths)
+        result = await self._rust_manager.bulk_blame(
+            repo_path, file_paths, ignore_whitespace, detect_moves, detect_copies, max_parallel, rev)
         if not isinstance(result, dict):
             raise TypeError(
                 f"Expected Dict[str, Union[List[BlameLineInfo], str]], got {type(result)}")
         return result
 
-    async def analyze_commits(self, repo_path: str) -> List[CommitInfo]:
+    async def blame_authorship(self, repo_url: str, file_paths: List[str], identity_map: Optional[Dict[str, str]] = None) -> BlameAuthorship:
+        """
+        Counts how many surviving lines of the given files each author wrote.
+
+        Runs bulk_blame on the files and aggregates the lines by author email, per file and
+        over all files. Emails are compared case-insensitively.
+
+        Args:
+            repo_url: URL of a managed repository that has finished cloning
+            file_paths: Paths of the files to blame, relative to the repository root
+            identity_map: Optional mapping of alias emails to the email their lines are
+                counted under, e.g. {"student@gmail.com": "student@university.edu"}
+
+        Returns:
+            Per-file and total authorship (line counts, percentages and total lines);
+            files that could not be blamed are listed under "errors" with their error
+        """
+        result = await self._rust_manager.blame_authorship(repo_url, file_paths, identity_map)
+        if not isinstance(result, dict):
+            raise TypeError(f"Expected BlameAuthorship, got {type(result)}")
+        return result
+
+    async def commit_activity(self, repo_url: str, bucket: str = "day", utc_offset_minutes: Optional[int] = None) -> List[Tuple[str, int, int, int]]:
+        """
+        Counts commits and changed lines per day, week or month, e.g. for an activity plot.
+
+        Commits are binned by author date. By default each commit's date is local to its
+        author's own timezone, so late-evening work counts for that evening.
+
+        Args:
+            repo_url: URL of a managed repository that has finished cloning
+            bucket: Bin width: "day", "week" (starting on Monday) or "month"
+            utc_offset_minutes: Optional timezone to bin every commit in, in minutes east of
+                UTC, e.g. -300 for US Eastern Standard Time (None = each author's own)
+
+        Returns:
+            List of (bin_start_date, commits, additions, deletions) tuples, oldest first,
+            where bin_start_date is the ISO 8601 date of the bin's first day, e.g.
+            "2024-01-29". Bins without commits are left out.
+        """
+        result = await self._rust_manager.commit_activity(repo_url, bucket, utc_offset_minutes)
+        if not isinstance(result, list):
+            raise TypeError(f"Expected List[Tuple[str, int, int, int]], got {type(result)}")
+        return result
+
+    async def coauthorship_graph(self, repo_url: str, identity_map: Optional[Dict[str, str]] = None) -> CoauthorshipGraph:
+        """
+        Builds a graph of who wrote commits together, from "Co-authored-by" trailers.
+
+        Every commit connects its author with each co-author it credits. Identities are
+        emails, compared case-insensitively, so the result can be fed straight into a
+        graph library, e.g. networkx.Graph with the weights as edge attributes.
+
+        Args:
+            repo_url: URL of a managed repository that has finished cloning
+            identity_map: Optional mapping of alias emails to the email they are counted
+                under, e.g. {"student@gmail.com": "student@university.edu"}
+
+        Returns:
+            "nodes" maps each connected email to the commits it authored or co-authored;
+            "edges" lists undirected {"source", "target", "weight"} pairs, weight being the
+            number of shared commits, heaviest first. Both are empty when no commit has a
+            co-author.
+        """
+        result = await self._rust_manager.coauthorship_graph(repo_url, identity_map)
+        if not isinstance(result, dict):
+            raise TypeError(f"Expected CoauthorshipGraph, got {type(result)}")
+        return result
+
+    async def analyze_commits(self, repo_path: str, fields: Optional[List[str]] = None, author_email: Optional[List[str]] = None, ref: Optional[str] = None, parse_trailers: bool = False, max_count: Optional[int] = None, path: Optional[str] = None, file_stats: bool = False) -> List[CommitInfo]:
         """
         Analyzes the commit history of a cloned repository asynchronously.
 
         Args:
             repo_path: The local path to the cloned repository to analyze
+            fields: Optional subset of keys to include in each commit dict (None = all keys)
+            author_email: Optional list of author emails to keep (case-insensitive). Like
+                multiple `git log --author` flags, a commit matching any entry is kept.
+            ref: Optional branch, tag or commit to walk history from instead of HEAD, so a
+                branch can be analyzed without checking it out. Pass "--all" to analyze the
+                commits reachable from every ref; each commit is reported once.
+            parse_trailers: Parse message trailers such as "Co-authored-by:" into each
+                commit's "trailers" dict (lowercased key -> list of values), e.g. to credit
+                co-authors. Off by default, leaving "trailers" empty.
+            max_count: Optional limit on the number of commits returned, like `git log -n`.
+                Commits are ordered newest first, so these are the most recent ones; the
+                limit applies after the author_email and ref filters.
+            path: Optional file or directory, relative to the repository root, to scope the
+                analysis to, like `git log -- <path>`. Only commits that change something
+                under it are returned, and their additions, deletions and files count only
+                changes there. The path must exist in the analyzed ref (HEAD for "--all").
+            file_stats: Fill each commit's "files" with per-file changes, detecting renames
+                like `git log -M` so a moved file counts only its edited lines. Off by default
+                since it diffs every file separately: "files" is then empty, and the totals
+                count a renamed file as deleted and re-added.
 
         Returns:
-            List of commit information objects
+            List of commit information objects, newest first (empty for a repository with
+            no commits yet)
 
         Raises:
-            ValueError: If the repository path is invalid or not a valid git repository
+            ValueError: If the repository path is invalid, not a valid git repository,
+                an unknown field is requested, the ref cannot be resolved, or
+                path doesn't exist
         """
-        result = await self._rust_manager.analyze_commits(repo_path)
+        result = await self._rust_manager.analyze_commits(
+            repo_path, fields, author_email, ref, parse_trailers, max_count, path, file_stats)
         if not isinstance(result, list):
             raise TypeError(f"Expected List[CommitInfo], got {type(result)}")
         return result
 
-    async def fetch_collaborators(self, repo_urls: List[str], max_pages: Optional[int] = None) -> Dict[str, List[CollaboratorInfo]]:
+    async def stream_commits_to_file(self, repo_url: str, path: str) -> int:
+        """
+        Writes the commit history of a cloned repository to a file as JSON lines.
+
+        Each line is one commit object with the same keys as analyze_commits returns.
+        Commits are written as they are processed, so histories with hundreds of
+        thousands of commits never have to fit in memory.
+
+        Args:
+            repo_url: URL of a managed repository that has finished cloning
+            path: File to write; it is created or truncated
+
+        Returns:
+            The number of commits written
+        """
+        result = await self._rust_manager.stream_commits_to_file(repo_url, path)
+        if not isinstance(result, int):
+            raise TypeError(f"Expected int, got {type(result)}")
+        return result
+
+    async def detect_force_pushes(self, repo_url: str) -> List[ForcePushInfo]:
+        """
+        Looks for force-pushes (history rewrites) of the default branch in a clone's reflog.
+
+        The checked-out branch and its remote-tracking branch are scanned for updates git
+        recorded as forced, or where the new tip doesn't contain the old one. Only updates
+        this clone has seen are visible, so keep the clone and fetch it regularly.
+
+        Args:
+            repo_url: URL of a managed repository that has finished cloning
+
+        Returns:
+            List of detected force-pushes, oldest first
+        """
+        result = await self._rust_manager.detect_force_pushes(repo_url)
+        if not isinstance(result, list):
+            raise TypeError(f"Expected List[ForcePushInfo], got {type(result)}")
+        return result
+
+    async def diff_stats(self, repo_url: str, base: str, head: str) -> DiffStats:
+        """
+        Summarizes the changes between two revisions, like `git diff --numstat base..head`.
+
+        Only the two trees are compared, so no history is walked. Pass base with a trailing
+        "..." (e.g. "main...") to diff from the merge base of base and head instead, like
+        `git diff main...head`: only the changes made on head's side are shown.
+
+        Args:
+            repo_url: URL of a managed repository that has finished cloning
+            base: Branch, tag or commit to diff from, optionally suffixed with "..."
+            head: Branch, tag or commit to diff to
+
+        Returns:
+            The resolved base and head SHAs, total additions and deletions, and per-file
+            changes (binary files count no lines)
+        """
+        result = await self._rust_manager.diff_stats(repo_url, base, head)
+        if not isinstance(result, dict):
+            raise TypeError(f"Expected DiffStats, got {type(result)}")
+        return result
+
+    async def analyze_commits_bulk(self, repo_urls: List[str], fields: Optional[List[str]] = None, file_stats: bool = False) -> Dict[str, Union[List[CommitInfo], str]]:
+        """
+        Analyzes the commit history of several cloned repositories in parallel.
+
+        Runs the analysis for all repositories at once (bounded by the CPU count)
+        instead of awaiting analyze_commits for each repository in turn.
+
+        Args:
+            repo_urls: List of repository URLs to analyze
+            fields: Optional subset of keys to include in each commit dict (None = all keys)
+            file_stats: Fill each commit's "files", like analyze_commits (off by default)
+
+        Returns:
+            Dictionary mapping repository URLs to either lists of commit information or error
+            strings; repositories that are not managed or not cloned yet get an error string
+        """
+        result = await self._rust_manager.analyze_commits_bulk(repo_urls, fields, file_stats)
+        if not isinstance(result, dict):
+            raise TypeError(
+                f"Expected Dict[str, Union[List[CommitInfo], str]], got {type(result)}")
+        return result
+
+    async def analyze_commits_since(self, repo_path: str, last_sha: str, fields: Optional[List[str]] = None) -> Tuple[List[CommitInfo], str, bool]:
+        """
+        Analyzes only the commits added since a previous analysis, like
+        `git log <last_sha>..HEAD`.
+
+        Persist the returned head SHA and pass it as last_sha next time. If last_sha is
+        no longer in HEAD's history (e.g. the branch was force-pushed) or is unknown, the
+        full history is returned instead and the full_history flag is set.
+
+        Args:
+            repo_path: The local path to the cloned repository to analyze
+            last_sha: HEAD SHA returned by the previous call
+            fields: Optional subset of keys to include in each commit dict (None = all keys)
+
+        Returns:
+            Tuple of (new commits, current HEAD SHA, full_history)
+        """
+        result = await self._rust_manager.analyze_commits_since(repo_path, last_sha, fields)
+        if not isinstance(result, tuple):
+            raise TypeError(f"Expected Tuple[List[CommitInfo], str, bool], got {type(result)}")
+        return result
+
+    async def fetch_collaborators(self, repo_urls: List[str], max_pages: Optional[int] = None, affiliation: Optional[str] = None) -> Dict[str, List[CollaboratorInfo]]:
         """
         Fetches collaborator information for multiple repositories.
 
         Args:
             repo_urls: List of repository URLs to analyze
             max_pages: Optional maximum number of pages to fetch (None = fetch all)
+            affiliation: Optional collaborator filter: "outside", "direct" or "all" (None = "all")
+
+        Each collaborator's "affiliation" tells how they have access: "outside" for outside
+        collaborators (e.g. a TA who is not a member of the organization), "direct" for
+        people added to the repository itself, and "organization" for members with access
+        only through the organization or a team. Tagging takes one or two extra list
+        requests per repository, each limited by max_pages; if one fails, the collaborators
+        it was needed for are tagged "unknown".
 
         Returns:
             Dictionary mapping repository URLs to lists of collaborator information
         """
-        result = await self._rust_manager.fetch_collaborators(repo_urls, max_pages)
+        result = await self._rust_manager.fetch_collaborators(repo_urls, max_pages, affiliation)
         if not isinstance(result, dict):
             raise TypeError(
                 f"Expected Dict[str, List[CollaboratorInfo]], got {type(result)}")
         return result
 
-    async def fetch_issues(self, repo_urls: List[str], state: Optional[str] = None, max_pages: Optional[int] = None) -> Dict[str, Union[List[IssueInfo], str]]:
+    async def fetch_issues(self, repo_urls: List[str], state: Optional[str] = None, max_pages: Optional[int] = None, fields: Optional[List[str]] = None, since: Optional[str] = None, strip_markdown: bool = False) -> Dict[str, Union[List[IssueInfo], str]]:
         """
         Fetches issue information for multiple repositories.
 
@@ -145,17 +632,85 @@ class RepoManager:
             repo_urls: List of repository URLs to analyze
             state: Optional filter for issue state ("open", "closed", or "all")
             max_pages: Optional maximum number of pages to fetch (None = fetch all)
+            fields: Optional subset of keys to include in each issue dict (None = all keys)
+            since: Optional ISO 8601 timestamp (e.g. "2024-01-31T12:00:00Z"). Only issues
+                updated at or after it are returned; this filters on updated_at, so older
+                issues that were since edited, commented on or reopened are included.
+            strip_markdown: Convert each body from Markdown to plain text (code fences,
+                heading markers and link syntax removed); the original body is kept under
+                "raw_body"
 
         Returns:
             Dictionary mapping repository URLs to either lists of issue information or error strings
         """
-        result = await self._rust_manager.fetch_issues(repo_urls, state, max_pages)
+        result = await self._rust_manager.fetch_issues(repo_urls, state, max_pages, fields, since, strip_markdown)
         if not isinstance(result, dict):
             raise TypeError(
                 f"Expected Dict[str, Union[List[IssueInfo], str]], got {type(result)}")
         return result
 
-    async def fetch_pull_requests(self, repo_urls: List[str], state: Optional[str] = None, max_pages: Optional[int] = None) -> Dict[str, Union[List[PullRequestInfo], str]]:
+    async def search_issues(self, query: str, max_pages: Optional[int] = None, fields: Optional[List[str]] = None) -> IssueSearchResults:
+        """
+        Searches issues and pull requests across repositories via the GitHub search API.
+
+        Cheaper than fetching every issue of many repositories and filtering locally.
+        Search has its own rate limit of 30 requests per minute; when it runs out
+        between pages the search waits for it to reset.
+
+        Args:
+            query: GitHub search query, e.g. "is:pr is:open TODO repo:owner/a repo:owner/b"
+            max_pages: Optional maximum number of 100-result pages to fetch (None = fetch all)
+            fields: Optional subset of keys to include in each item dict (None = all keys)
+
+        Returns:
+            Dictionary with "items" (issue-shaped dicts; pull requests have is_pull_request
+            set), "total_count" (all matches) and "truncated", which is True when fewer than
+            total_count items were returned. GitHub returns at most 1000 results per query.
+        """
+        result = await self._rust_manager.search_issues(query, max_pages, fields)
+        if not isinstance(result, dict):
+            raise TypeError(f"Expected IssueSearchResults, got {type(result)}")
+        return result
+
+    async def fetch_issue(self, repo_url: str, number: int, fields: Optional[List[str]] = None) -> Optional[IssueInfo]:
+        """
+        Fetches a single issue by number with one API request.
+
+        Much cheaper than listing every issue of a repository and filtering.
+
+        Args:
+            repo_url: Repository URL (e.g. "https://github.com/owner/repo")
+            number: The issue number
+            fields: Optional subset of keys to include in the dict (None = all keys)
+
+        Returns:
+            The issue information, or None if no such issue exists
+        """
+        result = await self._rust_manager.fetch_issue(repo_url, number, fields)
+        if result is not None and not isinstance(result, dict):
+            raise TypeError(f"Expected Optional[IssueInfo], got {type(result)}")
+        return result
+
+    async def fetch_issue_events(self, repo_url: str, number: int) -> List[EventInfo]:
+        """
+        Fetches the timeline events of one issue or pull request, e.g. who labeled,
+        assigned or closed it and when.
+
+        Event types gradelib doesn't know about are still returned with their raw "event" string.
+
+        Args:
+            repo_url: Repository URL (e.g. "https://github.com/owner/repo")
+            number: The issue or pull request number
+
+        Returns:
+            List of events, oldest first
+        """
+        result = await self._rust_manager.fetch_issue_events(repo_url, number)
+        if not isinstance(result, list):
+            raise TypeError(f"Expected List[EventInfo], got {type(result)}")
+        return result
+
+    async def fetch_pull_requests(self, repo_urls: List[str], state: Optional[str] = None, max_pages: Optional[int] = None, fields: Optional[List[str]] = None, use_graphql: bool = False, strip_markdown: bool = False, fetch_details: bool = True, max_concurrent_details: Optional[int] = None) -> Dict[str, Union[List[PullRequestInfo], str]]:
         """
         Fetches pull request information for multiple repositories.
 
@@ -163,16 +718,51 @@ class RepoManager:
             repo_urls: List of repository URLs to analyze
             state: Optional filter for pull request state ("open", "closed", or "all")
             max_pages: Optional maximum number of pages to fetch (None = fetch all)
+            fields: Optional subset of keys to include in each pull request dict (None = all keys).
+                If none of the detail-only keys (comments, commits, additions, deletions,
+                changed_files, mergeable, merged_by) is selected, the per pull request detail
+                requests are skipped as with fetch_details=False
+            use_graphql: Fetch through the GraphQL API, which returns 100 fully detailed pull
+                requests per request instead of making an extra REST request per pull request
+            strip_markdown: Convert each body from Markdown to plain text (code fences,
+                heading markers and link syntax removed); the original body is kept under
+                "raw_body"
+            fetch_details: Make one extra REST request per pull request for the detail-only
+                fields (default True). With False, roughly half the requests are made, but
+                comments, commits, additions, deletions and changed_files are 0, mergeable and
+                merged_by are None, and merged is derived from merged_at. Ignored with use_graphql
+            max_concurrent_details: Most detail requests in flight at once, shared by all
+                repositories of the call (None = 8). Keeps a large pull request fan-out from
+                monopolizing the connection budget
 
         Returns:
             Dictionary mapping repository URLs to either lists of pull request information or error strings
         """
-        result = await self._rust_manager.fetch_pull_requests(repo_urls, state, max_pages)
+        result = await self._rust_manager.fetch_pull_requests(repo_urls, state, max_pages, fields, use_graphql, strip_markdown, fetch_details, max_concurrent_details)
         if not isinstance(result, dict):
             raise TypeError(
                 f"Expected Dict[str, Union[List[PullRequestInfo], str]], got {type(result)}")
         return result
 
+    async def fetch_pull_request(self, repo_url: str, number: int, fields: Optional[List[str]] = None) -> Optional[PullRequestInfo]:
+        """
+        Fetches a single pull request by number with one API request.
+
+        Much cheaper than listing every pull request of a repository and filtering.
+
+        Args:
+            repo_url: Repository URL (e.g. "https://github.com/owner/repo")
+            number: The pull request number
+            fields: Optional subset of keys to include in the dict (None = all keys)
+
+        Returns:
+            The pull request information, or None if no such pull request exists
+        """
+        result = await self._rust_manager.fetch_pull_request(repo_url, number, fields)
+        if result is not None and not isinstance(result, dict):
+            raise TypeError(f"Expected Optional[PullRequestInfo], got {type(result)}")
+        return result
+
     async def fetch_code_reviews(self, repo_urls: List[str], max_pages: Optional[int] = None) -> Dict[str, Union[Dict[str, List[CodeReviewInfo]], str]]:
         """
         Fetches code review information for multiple repositories.
@@ -190,6 +780,85 @@ class RepoManager:
                 f"Expected Dict[str, Union[Dict[str, List[CodeReviewInfo]], str]], got {type(result)}")
         return result
 
+    async def fetch_pull_request_reviews(self, repo_url: str, number: int) -> PullRequestReviews:
+        """
+        Fetches the reviews and pending review requests for a single pull request.
+
+        Args:
+            repo_url: Repository URL the pull request belongs to
+            number: Number of the pull request
+
+        Returns:
+            Dictionary with "reviews" (list of code review information), "requested_reviewers"
+            (logins) and "requested_teams" (team slugs). Lists are empty when there are none.
+        """
+        result = await self._rust_manager.fetch_pull_request_reviews(repo_url, number)
+        if not isinstance(result, dict):
+            raise TypeError(f"Expected PullRequestReviews, got {type(result)}")
+        return result
+
+    async def fetch_pull_request_files(self, repo_url: str, number: int) -> List[PullRequestFileInfo]:
+        """
+        Fetches the files changed by a single pull request.
+
+        Args:
+            repo_url: Repository URL the pull request belongs to
+            number: Number of the pull request
+
+        Returns:
+            List of changed files with their status ("added", "modified", "removed", ...),
+            line counts and diff. "patch" is None for binary files and very large diffs.
+        """
+        result = await self._rust_manager.fetch_pull_request_files(repo_url, number)
+        if not isinstance(result, list):
+            raise TypeError(
+                f"Expected List[PullRequestFileInfo], got {type(result)}")
+        return result
+
+    async def fetch_pull_request_review_comments(self, repo_url: str, number: int) -> List[ReviewCommentInfo]:
+        """
+        Fetches the inline review comments of a single pull request.
+
+        These are the comments reviewers leave on lines of the diff, as opposed to the
+        pull request's conversation comments.
+
+        Args:
+            repo_url: Repository URL the pull request belongs to
+            number: Number of the pull request
+
+        Returns:
+            List of review comments, oldest first. "line" is None for outdated comments
+            whose line is no longer in the diff ("original_line" still has it). Replies
+            have "in_reply_to_id" set to the comment they answer, so threads can be
+            rebuilt by grouping on the first comment's id.
+        """
+        result = await self._rust_manager.fetch_pull_request_review_comments(repo_url, number)
+        if not isinstance(result, list):
+            raise TypeError(
+                f"Expected List[ReviewCommentInfo], got {type(result)}")
+        return result
+
+    async def fetch_pull_request_commits(self, repo_url: str, number: int) -> PullRequestCommits:
+        """
+        Fetches the commits on a single pull request.
+
+        GitHub lists at most 250 commits per pull request. When that many are returned,
+        "truncated" is True and later commits may be missing; use analyze_commits on a
+        clone for the full history.
+
+        Args:
+            repo_url: Repository URL the pull request belongs to
+            number: Number of the pull request
+
+        Returns:
+            Dictionary with "commits" (sha, message, author_login, author_email and
+            authored_at for each commit, oldest first) and "truncated"
+        """
+        result = await self._rust_manager.fetch_pull_request_commits(repo_url, number)
+        if not isinstance(result, dict):
+            raise TypeError(f"Expected PullRequestCommits, got {type(result)}")
+        return result
+
     async def fetch_comments(self, repo_urls: List[str], comment_types: Optional[List[str]] = None, max_pages: Optional[int] = None) -> Dict[str, Union[List[CommentInfo], str]]:
         """
         Fetches comments of various types for multiple repositories.
@@ -208,6 +877,191 @@ class RepoManager:
                 f"Expected Dict[str, Union[List[CommentInfo], str]], got {type(result)}")
         return result
 
+    async def fetch_issue_comments(self, repo_url: str, issue_number: int, max_pages: Optional[int] = None) -> List[CommentInfo]:
+        """
+        Fetches the comments on a single issue.
+
+        Args:
+            repo_url: Repository URL the issue belongs to
+            issue_number: Number of the issue
+            max_pages: Optional maximum number of pages to fetch (None = fetch all)
+
+        Returns:
+            List of comment information (empty if the issue has no comments)
+        """
+        result = await self._rust_manager.fetch_issue_comments(repo_url, issue_number, max_pages)
+        if not isinstance(result, list):
+            raise TypeError(
+                f"Expected List[CommentInfo], got {type(result)}")
+        return result
+
+    async def summary(self, repo_url: str) -> RepoSummary:
+        """
+        Returns headline numbers for a cloned repository.
+
+        Args:
+            repo_url: URL of a repository that has finished cloning
+
+        Returns:
+            Dictionary of commit, contributor and branch counts, open issues and PRs,
+            default branch, last commit date and primary language. API-derived values
+            are None when the GitHub API is unavailable for the URL.
+        """
+        result = await self._rust_manager.summary(repo_url)
+        if not isinstance(result, dict):
+            raise TypeError(f"Expected RepoSummary, got {type(result)}")
+        return result
+
+    async def fetch_repo_report(self, repo_url: str) -> RepoReport:
+        """
+        Gathers everything about a cloned repository in one call.
+
+        Commit analysis, branch analysis, collaborators, issues and pull requests are
+        fetched concurrently, with the same defaults as the individual methods. The
+        existing clone is used; nothing is re-cloned. Each section fails on its own, so
+        e.g. a local repository still gets its commits and branches when the GitHub API
+        calls fail.
+
+        Args:
+            repo_url: URL of a managed repository that has finished cloning
+
+        Returns:
+            Dictionary with "commits", "branches", "collaborators", "issues" and
+            "pull_requests", each a list of results or an error string for that section
+        """
+        result = await self._rust_manager.fetch_repo_report(repo_url)
+        if not isinstance(result, dict):
+            raise TypeError(f"Expected RepoReport, got {type(result)}")
+        return result
+
+    async def list_files(self, repo_url: str, glob: Optional[str] = None) -> List[str]:
+        """
+        Lists the files tracked in a cloned repository, like `git ls-files`.
+
+        Useful for discovering the paths to pass to bulk_blame.
+
+        Args:
+            repo_url: URL of a repository that has finished cloning
+            glob: Optional pattern to filter paths. "*" and "?" don't match "/", while "**"
+                matches any number of directories (e.g. "src/**/*.py")
+
+        Returns:
+            Sorted list of paths relative to the repository root
+        """
+        result = await self._rust_manager.list_files(repo_url, glob)
+        if not isinstance(result, list):
+            raise TypeError(f"Expected List[str], got {type(result)}")
+        return result
+
+    async def find_conflict_markers(self, repo_url: str, paths: Optional[List[str]] = None) -> Dict[str, List[int]]:
+        """
+        Scans the committed files of a cloned repository for merge conflict markers.
+
+        Args:
+            repo_url: URL of a repository that has finished cloning
+            paths: Optional list of file paths to scan (None = all tracked files)
+
+        Returns:
+            Dictionary mapping file paths to the 1-based line numbers of conflict markers.
+            Files without markers and binary files are omitted.
+        """
+        result = await self._rust_manager.find_conflict_markers(repo_url, paths)
+        if not isinstance(result, dict):
+            raise TypeError(f"Expected Dict[str, List[int]], got {type(result)}")
+        return result
+
+    async def file_encoding_report(self, repo_url: str, file_paths: List[str]) -> Dict[str, FileEncoding]:
+        """
+        Reports line endings and encoding of files committed in a cloned repository.
+
+        Each file's bytes are read as committed at HEAD, e.g. to flag submissions with
+        Windows line endings, a byte-order mark or text that isn't UTF-8.
+
+        Args:
+            repo_url: URL of a repository that has finished cloning
+            file_paths: Paths of the files to inspect, relative to the repository root
+
+        Returns:
+            Dictionary mapping each path to "has_crlf" (any "\\r\\n" line ending), "is_utf8",
+            "has_bom" (starts with a UTF-8 or UTF-16 byte-order mark) and "byte_count"
+        """
+        result = await self._rust_manager.file_encoding_report(repo_url, file_paths)
+        if not isinstance(result, dict):
+            raise TypeError(f"Expected Dict[str, FileEncoding], got {type(result)}")
+        return result
+
+    async def check_access(self, repo_urls: List[str]) -> Dict[str, Union[RepoAccess, str]]:
+        """
+        Checks that each repository exists and is accessible, e.g. before queuing clones.
+
+        Issues one GitHub API request per URL.
+
+        Args:
+            repo_urls: List of repository URLs to check
+
+        Returns:
+            Dictionary mapping repository URLs to access information or error strings.
+            A repository GitHub reports as missing has exists=False; note that GitHub also
+            reports private repositories the token can't see as missing. A 403 (the token
+            is denied access) is reported as an error string.
+        """
+        result = await self._rust_manager.check_access(repo_urls)
+        if not isinstance(result, dict):
+            raise TypeError(f"Expected Dict[str, Union[RepoAccess, str]], got {type(result)}")
+        return result
+
+    async def list_org_repos(self, org: str, name_prefix: Optional[str] = None) -> List[OwnerRepoInfo]:
+        """
+        Lists the repositories of a GitHub organization, e.g. to discover every student
+        repository of an assignment instead of passing explicit URLs.
+
+        Falls back to the user's repositories when no organization has that name.
+
+        Args:
+            org: Organization (or user) login
+            name_prefix: Only keep repositories whose name starts with this prefix,
+                ignoring case (e.g. "assignment1-")
+
+        Returns:
+            List of repositories; clone_url can be passed to clone or the fetch methods
+        """
+        result = await self._rust_manager.list_org_repos(org, name_prefix)
+        if not isinstance(result, list):
+            raise TypeError(f"Expected List[OwnerRepoInfo], got {type(result)}")
+        return result
+
+    async def get_rate_limit_status(self) -> Optional[RateLimitStatus]:
+        """
+        Fetches the core GitHub API rate limit status for this manager's token.
+
+        Returns:
+            Dictionary with "limit", "remaining", "reset_time" (seconds since epoch) and
+            "resource", or None if the status could not be retrieved
+        """
+        result = await self._rust_manager.get_rate_limit_status()
+        if result is not None and not isinstance(result, dict):
+            raise TypeError(f"Expected Optional[RateLimitStatus], got {type(result)}")
+        return result
+
+    async def commit_summary(self, repo_url: str, path: Optional[str] = None) -> CommitSummary:
+        """
+        Computes aggregate commit statistics for a cloned repository.
+
+        Args:
+            repo_url: URL of a repository that has finished cloning
+            path: Optional file or directory to summarize, e.g. one student's folder in a
+                shared repository; only commits and lines under it are counted
+
+        Returns:
+            Dictionary with "total_commits", "total_additions", "total_deletions",
+            "first_commit_timestamp", "last_commit_timestamp" (seconds since epoch,
+            None for an empty history) and "unique_authors" (distinct author emails)
+        """
+        result = await self._rust_manager.commit_summary(repo_url, path)
+        if not isinstance(result, dict):
+            raise TypeError(f"Expected CommitSummary, got {type(result)}")
+        return result
+
     async def analyze_branches(self, repo_urls: List[str]) -> Dict[str, Union[List[BranchInfo], str]]:
         """
         Analyzes branches in cloned repositories.
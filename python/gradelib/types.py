@@ -46,6 +46,7 @@ class CloneTask:
     url: str
     status: CloneStatus
     temp_dir: Optional[str] = None
+    attempts: int = 0  # Clone attempts made, including retries
 
     @classmethod
     def from_rust(cls, rust_task) -> CloneTask:
@@ -53,31 +54,92 @@ class CloneTask:
         return cls(
             url=rust_task.url,
             status=CloneStatus.from_rust(rust_task.status),
-            temp_dir=rust_task.temp_dir
+            temp_dir=rust_task.temp_dir,
+            attempts=rust_task.attempts
         )
 
 
 # TypedDict classes for return types
+class CloneFailure(dict):
+    """A failed clone task and its error message."""
+    pass
+
+
+class CloneSummary(dict):
+    """Counts of clone tasks per status, with the failed ones listed."""
+    pass
+
+
+class FileEncoding(dict):
+    """Line endings and encoding of a committed file."""
+    pass
+
+
 class CommitInfo(dict):
     """Information about a git commit."""
     pass
 
 
+class DiffStats(dict):
+    """Line changes between two revisions of a repository."""
+    pass
+
+
+class CoauthorEdge(dict):
+    """Two people who wrote commits together, and how many."""
+    pass
+
+
+class CoauthorshipGraph(dict):
+    """Who wrote commits with whom, from Co-authored-by trailers."""
+    pass
+
+
+class CommitSummary(dict):
+    """Aggregate statistics over a repository's commits."""
+    pass
+
+
 class BlameLineInfo(dict):
     """Information about a single line from git blame."""
     pass
 
 
+class Authorship(dict):
+    """Surviving line counts and percentages per author email."""
+    pass
+
+
+class BlameAuthorship(dict):
+    """Blame authorship per file and in total, plus files that failed to blame."""
+    pass
+
+
 class CollaboratorInfo(dict):
     """Information about a repository collaborator."""
     pass
 
 
+class MilestoneInfo(dict):
+    """The milestone a GitHub issue is assigned to, with its due date."""
+    pass
+
+
 class IssueInfo(dict):
     """Information about a GitHub issue."""
     pass
 
 
+class IssueSearchResults(dict):
+    """Issues and pull requests matching a search query."""
+    pass
+
+
+class EventInfo(dict):
+    """A timeline event of a GitHub issue or pull request."""
+    pass
+
+
 class PullRequestInfo(dict):
     """Information about a GitHub pull request."""
     pass
@@ -88,6 +150,31 @@ class CodeReviewInfo(dict):
     pass
 
 
+class PullRequestReviews(dict):
+    """Reviews and pending review requests for a GitHub pull request."""
+    pass
+
+
+class PullRequestFileInfo(dict):
+    """Information about a file changed by a GitHub pull request."""
+    pass
+
+
+class ReviewCommentInfo(dict):
+    """An inline review comment on a line of a GitHub pull request's diff."""
+    pass
+
+
+class PullRequestCommitInfo(dict):
+    """Information about a commit on a GitHub pull request."""
+    pass
+
+
+class PullRequestCommits(dict):
+    """Commits on a GitHub pull request."""
+    pass
+
+
 class CommentInfo(dict):
     """Information about a GitHub comment."""
     pass
@@ -98,6 +185,36 @@ class BranchInfo(dict):
     pass
 
 
+class OwnerRepoInfo(dict):
+    """A repository owned by a GitHub organization or user."""
+    pass
+
+
+class RateLimitStatus(dict):
+    """GitHub API rate limit status."""
+    pass
+
+
+class RepoAccess(dict):
+    """Whether a repository exists and how it can be accessed."""
+    pass
+
+
+class ForcePushInfo(dict):
+    """A force-push of a repository's default branch found in its reflog."""
+    pass
+
+
+class RepoSummary(dict):
+    """Headline numbers for a repository."""
+    pass
+
+
+class RepoReport(dict):
+    """Every section of a repository's analysis, each a result list or an error string."""
+    pass
+
+
 # Type conversion functions
 def convert_clone_tasks(rust_tasks: Dict[str, Any]) -> Dict[str, CloneTask]:
     """Convert Rust CloneTask objects to Python CloneTask dataclasses."""
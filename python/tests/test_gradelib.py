@@ -1,6 +1,9 @@
 import os
+import json
 import pytest
 import asyncio
+import contextlib
+from urllib.parse import parse_qs, urlsplit
 
 import gradelib
 
@@ -23,6 +26,130 @@ def set_github_token(monkeypatch):
     return token
 
 
+@contextlib.contextmanager
+def mock_github_api(tmp_path, monkeypatch, respond, requests=None):
+    """Serves api.github.com from `respond(path)` through a local proxy.
+
+    `respond` returns `(status, body)` or `(status, body, headers)`; a body other than
+    bytes is sent as JSON. POST requests call `respond(path, json_body)` instead.
+    Each request (with its `path` and `headers`) is appended to `requests` if given.
+
+    The proxy terminates the CONNECT tunnel with a throwaway certificate that the client
+    is made to trust, so each request's path is visible. Yields the proxy URL to pass as
+    proxy_url. Skips the test when the openssl command is not available.
+    """
+    import http.server
+    import shutil
+    import socket
+    import ssl
+    import subprocess
+    import threading
+
+    if shutil.which("openssl") is None:
+        pytest.skip("openssl not available")
+    cert, key = tmp_path / "api.pem", tmp_path / "api.key"
+    subprocess.run(
+        ["openssl", "req", "-x509", "-newkey", "rsa:2048", "-nodes", "-days", "1",
+         "-keyout", str(key), "-out", str(cert), "-subj", "/CN=api.github.com",
+         "-addext", "subjectAltName=DNS:api.github.com"],
+        check=True, capture_output=True)
+    monkeypatch.setenv("SSL_CERT_FILE", str(cert))
+    tls = ssl.SSLContext(ssl.PROTOCOL_TLS_SERVER)
+    tls.load_cert_chain(cert, key)
+
+    class Api(http.server.BaseHTTPRequestHandler):
+        protocol_version = "HTTP/1.1"
+
+        def do_GET(self, *args):
+            if requests is not None:
+                requests.append(self)
+            status, body, *headers = respond(self.path, *args)
+            is_json = not isinstance(body, bytes)
+            data = json.dumps(body).encode() if is_json else body
+            self.send_response(status)
+            for name, value in dict(*headers).items():
+                self.send_header(name, value)
+            self.send_header("Content-Type", "application/json" if is_json else "text/html")
+            self.send_header("Content-Length", str(len(data)))
+            self.end_headers()
+            self.wfile.write(data)
+
+        def do_POST(self):
+            length = int(self.headers.get("Content-Length", 0))
+            self.do_GET(json.loads(self.rfile.read(length) or b"null"))
+
+        def log_message(self, *args):
+            pass
+
+    class Proxy(http.server.BaseHTTPRequestHandler):
+        def do_CONNECT(self):
+            self.send_response(200)
+            self.end_headers()
+            # Headers and body go out as separate writes; don't let Nagle hold the second
+            self.connection.setsockopt(socket.IPPROTO_TCP, socket.TCP_NODELAY, 1)
+            with tls.wrap_socket(self.connection, server_side=True) as tunnel:
+                Api(tunnel, self.client_address, self.server)
+            self.close_connection = True
+
+        def log_message(self, *args):
+            pass
+
+    server = http.server.ThreadingHTTPServer(("127.0.0.1", 0), Proxy)
+    threading.Thread(target=server.serve_forever, daemon=True).start()
+    try:
+        yield f"http://127.0.0.1:{server.server_address[1]}"
+    finally:
+        server.shutdown()
+        server.server_close()
+
+
+def mock_issue(number, **fields):
+    """An issue as the REST API returns it, with `fields` replacing the defaults"""
+    return dict({
+        "id": number, "number": number, "title": f"Issue {number}", "state": "open",
+        "created_at": "2024-01-01T00:00:00Z", "updated_at": "2024-01-02T00:00:00Z",
+        "closed_at": None, "user": {"login": "student", "id": 1}, "body": None,
+        "comments": 0, "labels": [], "assignees": [], "milestone": None, "locked": False,
+        "html_url": f"https://github.com/octocat/Hello-World/issues/{number}",
+    }, **fields)
+
+
+def mock_pull_request(number, **fields):
+    """A pull request as the list endpoint returns it, with `fields` replacing the defaults"""
+    branch = {"sha": "0" * 40, "repo": {"full_name": "octocat/Hello-World"}}
+    return dict({
+        "id": number, "number": number, "title": f"PR {number}", "state": "closed",
+        "created_at": "2024-01-01T00:00:00Z", "updated_at": "2024-01-02T00:00:00Z",
+        "closed_at": "2024-01-02T00:00:00Z", "merged_at": "2024-01-02T00:00:00Z",
+        "user": {"login": "student", "id": 1}, "body": None, "draft": False, "labels": [],
+        "base": dict(branch, ref="main"), "head": dict(branch, ref=f"feature-{number}"),
+    }, **fields)
+
+
+def make_repo(tmp_path, name, files=None, message="Initial", branch=None,
+              user="Test User", email="test@example.com"):
+    """Creates a git repository at `tmp_path / name` and returns its directory
+
+    With `files` (path -> text or bytes), they are written and committed with `message`;
+    without, the repository is left empty. `email=None` leaves user.email unset.
+    """
+    repo_dir = tmp_path / name
+    repo_dir.mkdir(parents=True, exist_ok=True)
+    os.system(f"git init -q {f'-b {branch} ' if branch else ''}{repo_dir}")
+    os.system(f"git -C {repo_dir} config user.name '{user}'")
+    if email is not None:
+        os.system(f"git -C {repo_dir} config user.email '{email}'")
+    if files:
+        for path, content in files.items():
+            (repo_dir / path).parent.mkdir(parents=True, exist_ok=True)
+            if isinstance(content, bytes):
+                (repo_dir / path).write_bytes(content)
+            else:
+                (repo_dir / path).write_text(content)
+        os.system(f"git -C {repo_dir} add -A && git -C {repo_dir} commit -q -m '{message}'")
+    return repo_dir
+
+
 @pytest.mark.asyncio
 async def test_setup_async():
     gradelib.setup_async()
@@ -31,17 +158,8 @@ async def test_setup_async():
 @pytest.mark.asyncio
 async def test_local_clone_and_blame(tmp_path, monkeypatch):
     """Tests cloning a locally created repo and performing blame."""
-    repo_dir = tmp_path / "mini_repo"
-    repo_dir.mkdir()
-
-    # Initialize repo
-    os.system(f"git init {repo_dir}")
-    os.system(f"git -C {repo_dir} config user.name 'Test User'")
-    os.system(f"git -C {repo_dir} config user.email 'test@example.com'")
-    readme = repo_dir / "workflow_usage.md"
-    readme.write_text("Hello\n")
-    os.system(f"git -C {repo_dir} add workflow_usage.md")
-    os.system(f"git -C {repo_dir} commit -m 'initial commit'")
+    repo_dir = make_repo(tmp_path, "mini_repo", files={"workflow_usage.md": "Hello\n"},
+                         message="initial commit")
 
     local_path = str(repo_dir)
     manager = gradelib.RepoManager([local_path], "user", "token")
@@ -60,16 +178,8 @@ async def test_local_clone_and_blame(tmp_path, monkeypatch):
 @pytest.mark.asyncio
 async def test_analyze_commits(tmp_path, monkeypatch):
     """Test commit analysis on a local repo."""
-    repo_dir = tmp_path / "commit_repo"
-    repo_dir.mkdir()
-    os.system(f"git init {repo_dir}")
-    os.system(f"git -C {repo_dir} config user.name 'Test User'")
-    os.system(f"git -C {repo_dir} config user.email 'test@example.com'")
-    file = repo_dir / "main.py"
-    file.write_text("print('Hello')\n")
-    os.system(
-        f"git -C {repo_dir} add main.py && git -C {repo_dir} commit -m 'Initial'")
-    file.write_text("print('Updated')\n")
+    repo_dir = make_repo(tmp_path, "commit_repo", files={"main.py": "print('Hello')\n"})
+    (repo_dir / "main.py").write_text("print('Updated')\n")
     os.system(
         f"git -C {repo_dir} add main.py && git -C {repo_dir} commit -m 'Second commit'")
 
@@ -188,6 +298,28 @@ async def test_fetch_issues_max_pages(set_github_token):
             assert isinstance(data, str)
 
 
+@pytest.mark.asyncio
+async def test_fetch_issues_all_pages(tmp_path, monkeypatch):
+    """Issues are fetched 100 per page until the last page, without an extra request."""
+    requested = []
+    url = "https://api.github.com/repos/octocat/Hello-World/issues"
+
+    def respond(path):
+        requested.append(path)
+        if "page=2" in path:
+            return 200, [mock_issue(n) for n in range(101, 151)]
+        return 200, [mock_issue(n) for n in range(1, 101)], {
+            "Link": f'<{url}?state=all&per_page=100&page=2>; rel="next"'}
+
+    repo_url = "https://github.com/octocat/Hello-World"
+    with mock_github_api(tmp_path, monkeypatch, respond) as proxy_url:
+        manager = gradelib.RepoManager([], "user", "token", proxy_url=proxy_url)
+        issues = (await manager.fetch_issues([repo_url]))[repo_url]
+    assert [issue["number"] for issue in issues] == list(range(1, 151))
+    assert len(requested) == 2
+    assert all("per_page=100" in path for path in requested)
+
+
 @pytest.mark.asyncio
 async def test_fetch_pull_requests_max_pages(set_github_token):
     manager = gradelib.RepoManager(
@@ -248,6 +380,56 @@ async def test_fetch_collaborators_max_pages(set_github_token):
             assert isinstance(data, str)
 
 
+@pytest.mark.asyncio
+async def test_pagination_follows_link_header(tmp_path, monkeypatch):
+    """Pages are followed by their rel="next" link, so a full last page ends the listing."""
+
+    requested = []
+
+    def respond(path):
+        requested.append(path)
+        if path.startswith("/users/"):
+            login = path.rsplit("/", 1)[1]
+            return 200, {"login": login, "id": 1, "name": None, "email": None, "avatar_url": None}
+        url = "https://api.github.com" + urlsplit(path).path
+        page = int(parse_qs(urlsplit(path).query).get("page", ["1"])[0])
+        numbers = range((page - 1) * 100 + 1, page * 100 + 1)
+        if "/issues" in path:
+            items = [mock_issue(n) for n in numbers]
+        elif "/pulls" in path:
+            items = [mock_pull_request(n) for n in numbers]
+        else:
+            items = [{"login": f"user{n}"} for n in numbers]
+        # Next and last, next and prev, then prev only; the first next link has commas
+        links = {
+            1: f'<{url}?labels=bug,help&page=2>; rel="next", <{url}?page=3>; rel="last"',
+            2: f'<{url}?page=1>; rel="prev", <{url}?page=3>; rel="next"',
+            3: f'<{url}?page=2>; rel="prev"',
+        }
+        return 200, items, {"Link": links[page]}
+
+    repo_url = "https://github.com/octocat/Hello-World"
+    with mock_github_api(tmp_path, monkeypatch, respond) as proxy_url:
+        manager = gradelib.RepoManager([], "user", "token", proxy_url=proxy_url)
+        fetches = {
+            "issues": lambda **kw: manager.fetch_issues([repo_url], **kw),
+            "pulls": lambda **kw: manager.fetch_pull_requests([repo_url], fetch_details=False, **kw),
+            "collaborators": lambda **kw: manager.fetch_collaborators(
+                [repo_url], affiliation="outside", **kw),
+        }
+        for endpoint, fetch in fetches.items():
+            requested.clear()
+            results = (await fetch())[repo_url]
+            pages = [path for path in requested if f"/{endpoint}" in path]
+            assert len(pages) == 3, pages
+            assert "labels=bug,help&page=2" in pages[1]
+            assert len(results) == 300
+
+            requested.clear()
+            assert len((await fetch(max_pages=2))[repo_url]) == 200
+            assert len([path for path in requested if f"/{endpoint}" in path]) == 2
+
+
 @pytest.mark.asyncio
 async def test_fetch_code_reviews_max_pages(set_github_token):
     manager = gradelib.RepoManager(
@@ -267,3 +449,2465 @@ async def test_fetch_code_reviews_max_pages(set_github_token):
             assert len(data) <= 200
         else:
             assert isinstance(data, str)
+
+
+@pytest.mark.asyncio
+async def test_analyze_commits_fields(tmp_path):
+    """Test that analyze_commits only emits the requested fields."""
+    repo_dir = make_repo(tmp_path, "fields_repo", files={"main.py": "print('Hello')\n"})
+
+    local_path = str(repo_dir)
+    manager = gradelib.RepoManager([local_path], "user", "token")
+    await manager.clone_all()
+    commits = await manager.analyze_commits(local_path, fields=["sha", "additions"])
+    assert len(commits) == 1
+    assert set(commits[0].keys()) == {"sha", "additions"}
+
+    with pytest.raises(ValueError):
+        await manager.analyze_commits(local_path, fields=["not_a_field"])
+
+
+@pytest.mark.asyncio
+async def test_ssh_and_https_urls_are_deduplicated():
+    """SSH and HTTPS URLs for the same repo map to a single clone task."""
+    urls = [
+        "https://github.com/octocat/Hello-World",
+        "git@github.com:octocat/Hello-World.git",
+    ]
+    manager = gradelib.RepoManager(urls, "user", "token")
+    assert manager.canonical_url(urls[1]) == "https://github.com/octocat/Hello-World"
+    assert manager.canonical_url(urls[0]) == manager.canonical_url(urls[1])
+
+    tasks = await manager.fetch_clone_tasks()
+    assert list(tasks.keys()) == [urls[0]]
+
+
+@pytest.mark.asyncio
+async def test_summary_local_repo(tmp_path):
+    """Test the git-derived numbers of a repository summary."""
+    repo_dir = make_repo(tmp_path, "summary_repo", files={"a.txt": "a\n"},
+                         message="First", branch="main")
+    (repo_dir / "b.txt").write_text("b\n")
+    os.system(
+        f"git -C {repo_dir} add b.txt && git -C {repo_dir} "
+        f"-c user.email=other@example.com commit -m 'Second'")
+    os.system(f"git -C {repo_dir} branch feature")
+
+    local_path = str(repo_dir)
+    manager = gradelib.RepoManager([local_path], "user", "token")
+    with pytest.raises(ValueError):
+        await manager.summary(local_path)
+
+    await manager.clone_all()
+    summary = await manager.summary(local_path)
+    assert summary["commit_count"] == 2
+    assert summary["contributor_count"] == 2
+    assert summary["branch_count"] == 2
+    assert summary["default_branch"] == "main"
+    assert isinstance(summary["last_commit_date"], int)
+    assert summary["open_issues"] is None
+    assert summary["open_prs"] is None
+
+
+@pytest.mark.asyncio
+async def test_find_conflict_markers(tmp_path):
+    """Test detection of committed merge conflict markers."""
+    repo_dir = make_repo(tmp_path, "conflict_repo", files={
+        "clean.py": "print('ok')\n",
+        "broken.py": "x = 1\n<<<<<<< HEAD\ny = 2\n=======\ny = 3\n>>>>>>> feature\n",
+        "blob.bin": b"\x00<<<<<<< HEAD\n",
+    })
+
+    local_path = str(repo_dir)
+    manager = gradelib.RepoManager([local_path], "user", "token")
+    await manager.clone_all()
+
+    markers = await manager.find_conflict_markers(local_path)
+    assert markers == {"broken.py": [2, 4, 6]}
+
+    markers = await manager.find_conflict_markers(local_path, ["clean.py"])
+    assert markers == {}
+
+
+@pytest.mark.asyncio
+async def test_file_encoding_report(tmp_path):
+    """CRLF endings, a BOM and invalid UTF-8 are reported from the committed bytes."""
+    repo_dir = make_repo(tmp_path, "encoding_repo", files={
+        "unix.py": b"a = 1\nb = 2\n",
+        "windows.py": b"a = 1\r\nb = 2\n",
+        "bom.py": b"\xef\xbb\xbfa = 1\n",
+        "latin1.txt": "caf\u00e9\n".encode("latin-1"),
+    })
+
+    local_path = str(repo_dir)
+    manager = gradelib.RepoManager([local_path], "user", "token")
+    with pytest.raises(ValueError):
+        await manager.file_encoding_report(local_path, ["unix.py"])
+    await manager.clone_all()
+
+    report = await manager.file_encoding_report(
+        local_path, ["unix.py", "windows.py", "bom.py", "latin1.txt"])
+    assert report["unix.py"] == {"has_crlf": False, "is_utf8": True, "has_bom": False, "byte_count": 12}
+    assert report["windows.py"]["has_crlf"]
+    assert report["bom.py"]["has_bom"] and report["bom.py"]["is_utf8"]
+    assert not report["latin1.txt"]["is_utf8"]
+
+    with pytest.raises(ValueError):
+        await manager.file_encoding_report(local_path, ["missing.py"])
+
+
+@pytest.mark.asyncio
+async def test_fetch_pull_request_reviews_invalid_url():
+    """An unparseable repository URL is reported as a ValueError."""
+    manager = gradelib.RepoManager([], "user", "token")
+    with pytest.raises(ValueError):
+        await manager.fetch_pull_request_reviews("not-a-repo-url", 1)
+
+
+@pytest.mark.asyncio
+async def test_persistent_clone_dir_is_reused(tmp_path):
+    """Clones into target_dir persist and are reused by a new manager."""
+    repo_dir = make_repo(tmp_path, "persist_repo", files={"main.py": "print('Hello')\n"})
+
+    local_path = str(repo_dir)
+    target_dir = tmp_path / "clones"
+    manager = gradelib.RepoManager(
+        [local_path], "user", "token", target_dir=str(target_dir))
+    await manager.clone_all()
+    tasks = await manager.fetch_clone_tasks()
+    clone_path = tasks[local_path].temp_dir
+    assert clone_path == str(target_dir / "persist_repo")
+    marker = target_dir / "persist_repo" / "untracked.txt"
+    marker.write_text("kept\n")
+
+    # A commit pushed between runs is fetched and checked out
+    (repo_dir / "main.py").write_text("print('Updated')\n")
+    os.system(f"git -C {repo_dir} commit -q -am 'Update'")
+    manager = gradelib.RepoManager(
+        [local_path], "user", "token", target_dir=str(target_dir))
+    await manager.clone_all()
+    tasks = await manager.fetch_clone_tasks()
+    assert tasks[local_path].status.status_type == "completed"
+    assert tasks[local_path].temp_dir == clone_path
+    assert marker.exists()
+    messages = [c["message"] for c in await manager.analyze_commits(clone_path)]
+    assert messages == ["Update", "Initial"]
+    assert (target_dir / "persist_repo" / "main.py").read_text() == "print('Updated')\n"
+
+    # The branch option applies to a reused clone too
+    os.system(f"git -C {repo_dir} checkout -q -b feature")
+    os.system(f"git -C {repo_dir} commit -q --allow-empty -m 'Feature'")
+    os.system(f"git -C {repo_dir} checkout -q -")
+    manager = gradelib.RepoManager(
+        [local_path], "user", "token", target_dir=str(target_dir))
+    await manager.clone(local_path, branch="feature")
+    messages = [c["message"] for c in await manager.analyze_commits(clone_path)]
+    assert messages == ["Feature", "Update", "Initial"]
+
+    # Another repository with the same directory name is not mistaken for this clone
+    other_dir = make_repo(tmp_path, "other/persist_repo")
+    os.system(f"git -C {other_dir} commit -q --allow-empty -m 'Other'")
+    manager = gradelib.RepoManager(
+        [str(other_dir)], "user", "token", target_dir=str(target_dir))
+    await manager.clone_all()
+    tasks = await manager.fetch_clone_tasks()
+    assert tasks[str(other_dir)].status.status_type == "failed"
+    assert "already holds a clone of" in tasks[str(other_dir)].status.error
+
+
+@pytest.mark.asyncio
+async def test_clone_dir_scheme(tmp_path):
+    """Same-named repositories of different owners get distinct persistent clones."""
+    sources = {}
+    for owner in ("alice", "bob"):
+        src = make_repo(tmp_path, f"src/{owner}/repo", files={"owner.txt": f"{owner}\n"})
+        sources[owner] = src
+    urls = {owner: f"https://github.com/{owner}/repo" for owner in sources}
+
+    layouts = [
+        (None, lambda owner: f"{owner}__repo"),
+        ("full-slug", lambda owner: os.path.join(owner, "repo")),
+        ("students/{owner}-{repo}", lambda owner: os.path.join("students", f"{owner}-repo")),
+    ]
+    for index, (scheme, expected) in enumerate(layouts):
+        target_dir = tmp_path / f"clones{index}"
+        # Existing clones of each URL are fetched from the local source instead of the network
+        for owner, src in sources.items():
+            clone_dir = target_dir / expected(owner)
+            os.system(f"git clone -q {src} {clone_dir}")
+            os.system(f"git -C {clone_dir} remote set-url origin {urls[owner]}")
+            os.system(f"git -C {clone_dir} config url.{src}.insteadOf {urls[owner]}")
+
+        manager = gradelib.RepoManager(
+            list(urls.values()), "user", "token", target_dir=str(target_dir),
+            clone_dir_scheme=scheme)
+        await manager.clone_all()
+        tasks = await manager.fetch_clone_tasks()
+        for owner, url in urls.items():
+            assert tasks[url].status.status_type == "completed"
+            assert tasks[url].temp_dir == str(target_dir / expected(owner))
+            assert (target_dir / expected(owner) / "owner.txt").read_text() == f"{owner}\n"
+
+    for bad in ("../{repo}", "/abs/{repo}", "{owner}/./{repo}", "nested"):
+        with pytest.raises(ValueError):
+            gradelib.RepoManager([], "user", "token", clone_dir_scheme=bad)
+
+
+@pytest.mark.asyncio
+async def test_empty_repository(tmp_path):
+    """A repository without commits clones and analyzes to empty results."""
+    repo_dir = make_repo(tmp_path, "empty_repo")
+
+    local_path = str(repo_dir)
+    manager = gradelib.RepoManager([local_path], "user", "token")
+    await manager.clone_all()
+    tasks = await manager.fetch_clone_tasks()
+    assert tasks[local_path].status.status_type == "completed"
+    clone_path = tasks[local_path].temp_dir
+
+    assert await manager.analyze_commits(clone_path) == []
+    assert await manager.analyze_commits(clone_path, ref="--all") == []
+    branches = await manager.analyze_branches([local_path])
+    assert branches[local_path] == []
+
+    blame = await manager.bulk_blame(clone_path, ["main.py"])
+    assert blame["main.py"].startswith("EmptyRepository")
+    with pytest.raises(ValueError, match="EmptyRepository"):
+        await manager.analyze_commits(clone_path, ref="main")
+
+
+@pytest.mark.asyncio
+async def test_diff_stats(tmp_path):
+    """diff_stats reports per-file and total line changes between two revisions."""
+    repo_dir = make_repo(tmp_path, "diff_repo", files={"a.py": "one\ntwo\nthree\n"},
+                         message="Base", branch="main")
+    os.system(f"git -C {repo_dir} tag base")
+    os.system(f"git -C {repo_dir} checkout -b feature")
+    (repo_dir / "a.py").write_text("one\n2\nthree\nfour\n")
+    (repo_dir / "b.py").write_text("new\n")
+    os.system(f"git -C {repo_dir} add a.py b.py && git -C {repo_dir} commit -m 'Change'")
+    os.system(f"git -C {repo_dir} checkout main")
+    (repo_dir / "c.py").write_text("main only\n")
+    os.system(f"git -C {repo_dir} add c.py && git -C {repo_dir} commit -m 'Main work'")
+
+    local_path = str(repo_dir)
+    manager = gradelib.RepoManager([local_path], "user", "token")
+    await manager.clone_all()
+
+    stats = await manager.diff_stats(local_path, "base", "origin/feature")
+    files = {f["path"]: f for f in stats["files"]}
+    assert set(files) == {"a.py", "b.py"}
+    assert (files["a.py"]["additions"], files["a.py"]["deletions"]) == (2, 1)
+    assert files["a.py"]["status"] == "modified"
+    assert (files["b.py"]["additions"], files["b.py"]["status"]) == (1, "added")
+    assert (stats["additions"], stats["deletions"]) == (3, 1)
+
+    # main has moved on; a two-dot diff includes its change, a merge-base diff doesn't
+    two_dot = await manager.diff_stats(local_path, "main", "origin/feature")
+    assert "c.py" in {f["path"] for f in two_dot["files"]}
+    merge_base = await manager.diff_stats(local_path, "main...", "origin/feature")
+    assert {f["path"] for f in merge_base["files"]} == {"a.py", "b.py"}
+    assert merge_base["base_sha"] == stats["base_sha"]
+
+    with pytest.raises(ValueError):
+        await manager.diff_stats(local_path, "no-such-ref", "main")
+
+
+@pytest.mark.asyncio
+async def test_api_error_includes_message(tmp_path, monkeypatch):
+    """A refused request quotes the API message, not just the status code."""
+    docs = "https://docs.github.com/rest/issues/issues#list-repository-issues"
+    page = b"<html>" + b"x" * 2000 + b"</html>"
+
+    def respond(path):
+        if "Hello-World" in path:
+            return 422, {"message": "Validation Failed", "documentation_url": docs}
+        return 502, page
+
+    with mock_github_api(tmp_path, monkeypatch, respond) as proxy_url:
+        manager = gradelib.RepoManager([], "user", "token", proxy_url=proxy_url)
+        url = "https://github.com/octocat/Hello-World"
+        result = (await manager.fetch_issues([url], max_pages=1))[url]
+        assert "422" in result
+        assert "Validation Failed" in result and docs in result
+
+        # A body that isn't GitHub's JSON is quoted, cut short
+        url = "https://github.com/octocat/Spoon-Knife"
+        result = (await manager.fetch_issues([url], max_pages=1))[url]
+        assert "502" in result
+        assert "<html>xxx" in result and result.endswith("...")
+        assert len(result) < 600
+
+
+@pytest.mark.asyncio
+async def test_bulk_blame_binary_file(tmp_path):
+    """Binary files get the BinaryFile marker instead of garbled blame lines."""
+    repo_dir = make_repo(tmp_path, "binary_repo", files={
+        "text.py": "print(1)\n",
+        "image.png": b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR\n",
+        "data.bin": "looks like text\n",
+        ".gitattributes": "*.bin binary\n",
+    })
+
+    manager = gradelib.RepoManager([], "user", "token")
+    files = ["text.py", "image.png", "data.bin"]
+    for rev in (None, "HEAD"):
+        results = await manager.bulk_blame(str(repo_dir), files, rev=rev)
+        assert len(results["text.py"]) == 1
+        assert results["image.png"].startswith("BinaryFile:")
+        assert results["data.bin"].startswith("BinaryFile:")
+
+
+@pytest.mark.asyncio
+async def test_get_rate_limit_status():
+    """Rate limit status is either None or a plausible dict."""
+    manager = gradelib.RepoManager([], "user", os.getenv("GITHUB_TOKEN", "fake-token"))
+    await manager.fetch_issues(["https://github.com/octocat/Hello-World"], max_pages=1)
+    status = await manager.get_rate_limit_status()
+    if status is not None:
+        assert status["resource"] == "core"
+        assert 0 <= status["remaining"] <= status["limit"]
+        assert status["reset_time"] > 0
+
+
+@pytest.mark.asyncio
+async def test_clone_and_wait(tmp_path):
+    """The waiting variants resolve once the clone is done and raise when it failed."""
+    repo_dir = make_repo(tmp_path, "wait_repo", files={"a.py": "a = 1\n"})
+    local_path = str(repo_dir)
+    missing_path = str(tmp_path / "missing_repo")
+
+    manager = gradelib.RepoManager([local_path, missing_path], "user", "token")
+    status = await manager.clone_and_wait(local_path)
+    assert status.status_type == "completed"
+    # Already finished by the time the call resolved, without any polling
+    tasks = await manager.fetch_clone_tasks()
+    assert tasks[local_path].status.status_type == "completed"
+    assert os.path.isfile(os.path.join(tasks[local_path].temp_dir, "a.py"))
+
+    with pytest.raises(ValueError):
+        await manager.clone_and_wait(missing_path)
+
+    # A URL the manager wasn't given is refused before anything is cloned
+    target_dir = tmp_path / "clones"
+    manager = gradelib.RepoManager([], "user", "token", target_dir=str(target_dir))
+    with pytest.raises(ValueError, match="not managed"):
+        await manager.clone_and_wait(local_path)
+    assert not target_dir.exists() or not any(target_dir.iterdir())
+    assert await manager.fetch_clone_tasks() == {}
+
+    manager = gradelib.RepoManager([local_path], "user", "token")
+    statuses = await manager.clone_all_and_wait()
+    assert {url: s.status_type for url, s in statuses.items()} == {local_path: "completed"}
+
+    manager = gradelib.RepoManager([local_path, missing_path], "user", "token")
+    with pytest.raises(ValueError, match="1 of 2 clones failed"):
+        await manager.clone_all_and_wait()
+
+
+@pytest.mark.asyncio
+async def test_clone_summary(tmp_path):
+    """The summary counts every task once and lists the failed URL with its error."""
+    repo_dir = make_repo(tmp_path, "summary_repo", files={"a.py": "a = 1\n"})
+    local_path = str(repo_dir)
+    bad_path = str(tmp_path / "no_such_repo")
+
+    manager = gradelib.RepoManager([local_path, bad_path], "user", "token")
+    before = manager.clone_summary()
+    assert before["failures"] == []
+    assert before["queued"] + before["cloning"] + before["completed"] + before["failed"] == 2
+
+    await manager.clone_all()
+    summary = manager.clone_summary()
+    assert (summary["queued"], summary["cloning"], summary["completed"], summary["failed"]) \
+        == (0, 0, 1, 1)
+    assert [failure["url"] for failure in summary["failures"]] == [bad_path]
+    assert summary["failures"][0]["error"]
+
+
+@pytest.mark.asyncio
+async def test_update_token(tmp_path, monkeypatch):
+    """Rotating the token keeps clone state and is used from the next request on."""
+    repo_dir = make_repo(tmp_path, "rotate_repo", files={"a.py": "a = 1\n"})
+    local_path = str(repo_dir)
+    repo_url = "https://github.com/octocat/Hello-World"
+
+    requests = []
+    with mock_github_api(tmp_path, monkeypatch, lambda path: (200, []), requests) as proxy_url:
+        manager = gradelib.RepoManager([local_path], "user", "old-token", proxy_url=proxy_url)
+        await manager.clone_all()
+        await manager.fetch_issues([repo_url])
+        manager.update_token("new-token")
+        assert manager.clone_summary()["completed"] == 1
+        assert len(await manager.analyze_commits(local_path)) == 1
+        assert (await manager.commit_summary(local_path))["total_commits"] == 1
+        await manager.fetch_issues([repo_url])
+
+        # A rejected token leaves the current one in place
+        with pytest.raises(ValueError):
+            manager.update_token("")
+        with pytest.raises(ValueError, match="header"):
+            manager.update_token("newer-token\n")
+        await manager.fetch_issues([repo_url])
+    assert (await manager.commit_summary(local_path))["total_commits"] == 1
+    assert [r.headers["Authorization"] for r in requests] == [
+        "token old-token", "token new-token", "token new-token"]
+
+
+@pytest.mark.asyncio
+async def test_clone_branch(tmp_path):
+    """Cloning a named branch checks it out; a missing branch fails the task."""
+    repo_dir = make_repo(tmp_path, "branch_repo", files={"main.py": "print('main')\n"},
+                         branch="main")
+    os.system(f"git -C {repo_dir} checkout -b feature")
+    (repo_dir / "feature.py").write_text("print('feature')\n")
+    os.system(
+        f"git -C {repo_dir} add feature.py && git -C {repo_dir} commit -m 'Feature'")
+    os.system(f"git -C {repo_dir} checkout main")
+
+    local_path = str(repo_dir)
+    manager = gradelib.RepoManager([local_path], "user", "token")
+    await manager.clone(local_path, branch="feature")
+    tasks = await manager.fetch_clone_tasks()
+    assert tasks[local_path].status.status_type == "completed"
+    assert os.path.exists(os.path.join(tasks[local_path].temp_dir, "feature.py"))
+
+    manager = gradelib.RepoManager([local_path], "user", "token")
+    await manager.clone(local_path, branch="does-not-exist")
+    tasks = await manager.fetch_clone_tasks()
+    assert tasks[local_path].status.status_type == "failed"
+    assert "does-not-exist" in tasks[local_path].status.error
+
+
+@pytest.mark.asyncio
+async def test_clone_checkout(tmp_path):
+    """A branch is checked out as a branch, a SHA as a detached HEAD, anything else fails."""
+    repo_dir = make_repo(tmp_path, "checkout_repo", files={"main.py": "version = 1\n"},
+                         message="First", branch="main")
+    first_sha = os.popen(f"git -C {repo_dir} rev-parse HEAD").read().strip()
+    (repo_dir / "main.py").write_text("version = 2\n")
+    os.system(f"git -C {repo_dir} commit -am 'Second'")
+    os.system(f"git -C {repo_dir} checkout -b feature")
+    (repo_dir / "feature.py").write_text("print('feature')\n")
+    os.system(f"git -C {repo_dir} add feature.py && git -C {repo_dir} commit -m 'Feature'")
+    os.system(f"git -C {repo_dir} checkout main")
+
+    def head_of(path):
+        return os.popen(f"git -C {path} rev-parse --abbrev-ref HEAD").read().strip()
+
+    local_path = str(repo_dir)
+    manager = gradelib.RepoManager([local_path], "user", "token")
+    await manager.clone(local_path, checkout="feature")
+    task = (await manager.fetch_clone_tasks())[local_path]
+    assert task.status.status_type == "completed"
+    assert os.path.exists(os.path.join(task.temp_dir, "feature.py"))
+    assert head_of(task.temp_dir) == "feature"
+
+    manager = gradelib.RepoManager([local_path], "user", "token")
+    await manager.clone(local_path, checkout=first_sha)
+    task = (await manager.fetch_clone_tasks())[local_path]
+    assert task.status.status_type == "completed"
+    with open(os.path.join(task.temp_dir, "main.py")) as f:
+        assert f.read() == "version = 1\n"
+    assert head_of(task.temp_dir) == "HEAD"  # Detached
+    commits = await manager.analyze_commits(task.temp_dir)
+    assert [c["sha"] for c in commits] == [first_sha]
+
+    manager = gradelib.RepoManager([local_path], "user", "token")
+    await manager.clone(local_path, checkout="no-such-ref")
+    task = (await manager.fetch_clone_tasks())[local_path]
+    assert task.status.status_type == "failed"
+    assert "no-such-ref" in task.status.error
+
+
+@pytest.mark.asyncio
+async def test_clone_extra_args(tmp_path):
+    """Supported clone flags are applied; flags that could redirect the clone are rejected."""
+    repo_dir = make_repo(tmp_path, "extra_args_repo", branch="main")
+    for i in range(2):
+        (repo_dir / "main.py").write_text(f"a = {i}\n")
+        os.system(
+            f"git -C {repo_dir} add main.py && git -C {repo_dir} commit -m 'Commit {i}'")
+    os.system(f"git -C {repo_dir} tag v1")
+
+    local_path = str(repo_dir)
+    manager = gradelib.RepoManager([local_path], "user", "token")
+    await manager.clone(local_path, extra_args=["--no-tags", "--branch", "main"])
+    tasks = await manager.fetch_clone_tasks()
+    assert tasks[local_path].status.status_type == "completed"
+    clone_dir = tasks[local_path].temp_dir
+    assert os.path.exists(os.path.join(clone_dir, "main.py"))
+    assert not os.path.exists(os.path.join(clone_dir, ".git", "refs", "tags", "v1"))
+
+    for bad_args in (["--upload-pack=touch /tmp/pwned"], ["-c", "core.editor=x"],
+                     ["--depth", "zero"], ["--separate-git-dir", "x"]):
+        with pytest.raises(ValueError):
+            await manager.clone(local_path, extra_args=bad_args)
+    # Partial clones are a known limitation, reported as such rather than as a bad flag
+    for filter_args in (["--filter=blob:none"], ["--filter", "blob:none"]):
+        with pytest.raises(ValueError, match="cannot make partial clones"):
+            await manager.clone(local_path, extra_args=filter_args)
+
+
+@pytest.mark.asyncio
+async def test_commit_summary(tmp_path):
+    """Test per-repository commit aggregates."""
+    repo_dir = make_repo(tmp_path, "commit_summary_repo")
+    (repo_dir / "main.py").write_text("a = 1\nb = 2\n")
+    os.system(
+        f"git -C {repo_dir} add main.py && "
+        f"GIT_AUTHOR_DATE='2024-01-01T00:00:00Z' git -C {repo_dir} commit -m 'First'")
+    (repo_dir / "main.py").write_text("a = 1\nc = 3\n")
+    os.system(
+        f"git -C {repo_dir} add main.py && GIT_AUTHOR_DATE='2024-02-01T00:00:00Z' "
+        f"git -C {repo_dir} -c user.email=Other@Example.com commit -m 'Second'")
+
+    local_path = str(repo_dir)
+    manager = gradelib.RepoManager([local_path], "user", "token")
+    with pytest.raises(ValueError):
+        await manager.commit_summary(local_path)
+
+    await manager.clone_all()
+    summary = await manager.commit_summary(local_path)
+    assert summary["total_commits"] == 2
+    assert summary["total_additions"] == 3
+    assert summary["total_deletions"] == 1
+    assert summary["first_commit_timestamp"] == 1704067200
+    assert summary["last_commit_timestamp"] == 1706745600
+    assert summary["unique_authors"] == 2
+
+
+@pytest.mark.asyncio
+async def test_analyze_commits_author_filter(tmp_path):
+    """Test that the author_email filter isolates one contributor."""
+    repo_dir = make_repo(tmp_path, "authors_repo", files={"a.py": "a = 1\n"},
+                         message="Alice", email="alice@example.com")
+    (repo_dir / "b.py").write_text("b = 1\n")
+    os.system(
+        f"git -C {repo_dir} add b.py && "
+        f"git -C {repo_dir} -c user.email=bob@example.com commit -m 'Bob'")
+
+    local_path = str(repo_dir)
+    manager = gradelib.RepoManager([local_path], "user", "token")
+    await manager.clone_all()
+
+    commits = await manager.analyze_commits(local_path, author_email=["BOB@example.com"])
+    assert [c["message"].strip() for c in commits] == ["Bob"]
+
+    commits = await manager.analyze_commits(local_path, author_email=["nobody@example.com"])
+    assert commits == []
+
+    commits = await manager.analyze_commits(local_path)
+    assert len(commits) == 2
+
+
+@pytest.mark.asyncio
+async def test_bulk_blame_ignore_whitespace(tmp_path):
+    """A whitespace-only reformat isn't credited when ignore_whitespace is set."""
+    repo_dir = make_repo(tmp_path, "ws_repo", files={"main.py": "def f():\n  return 1\n"},
+                         email="author@example.com")
+    (repo_dir / "main.py").write_text("def f():\n    return 1\n")
+    os.system(
+        f"git -C {repo_dir} add main.py && "
+        f"git -C {repo_dir} -c user.email=formatter@example.com commit -m 'Reformat'")
+
+    local_path = str(repo_dir)
+    manager = gradelib.RepoManager([local_path], "user", "token")
+    await manager.clone_all()
+
+    plain = await manager.bulk_blame(local_path, ["main.py"])
+    assert plain["main.py"][1]["author_email"] == "formatter@example.com"
+
+    ignored = await manager.bulk_blame(local_path, ["main.py"], ignore_whitespace=True)
+    assert ignored["main.py"][1]["author_email"] == "author@example.com"
+
+
+@pytest.mark.asyncio
+async def test_gitlab_nested_group_urls_are_deduplicated():
+    """GitLab URLs with nested groups canonicalize to the full project path."""
+    urls = [
+        "https://gitlab.com/group/subgroup/project.git",
+        "git@gitlab.com:group/subgroup/project.git",
+    ]
+    manager = gradelib.RepoManager(urls, "user", "token")
+    assert manager.canonical_url(urls[1]) == "https://gitlab.com/group/subgroup/project"
+    assert manager.canonical_url(urls[0]) == manager.canonical_url(urls[1])
+
+    tasks = await manager.fetch_clone_tasks()
+    assert list(tasks.keys()) == [urls[0]]
+
+
+@pytest.mark.asyncio
+async def test_analyze_branches_ahead_behind(tmp_path):
+    """Local branches report how far they diverge from the default branch."""
+    repo_dir = make_repo(tmp_path, "ahead_repo", branch="main")
+    os.system(f"git -C {repo_dir} commit --allow-empty -m 'Base'")
+    os.system(f"git -C {repo_dir} checkout -b feature")
+    os.system(f"git -C {repo_dir} commit --allow-empty -m 'Feature 1'")
+    os.system(f"git -C {repo_dir} commit --allow-empty -m 'Feature 2'")
+    os.system(f"git -C {repo_dir} checkout main")
+    os.system(f"git -C {repo_dir} commit --allow-empty -m 'Main 1'")
+
+    local_path = str(repo_dir)
+    manager = gradelib.RepoManager([local_path], "user", "token")
+    await manager.clone(local_path, branch="feature")
+    branches = await manager.analyze_branches([local_path])
+    by_name = {b["name"]: b for b in branches[local_path]}
+    assert by_name["feature"]["ahead"] == 2
+    assert by_name["feature"]["behind"] == 1
+    assert by_name["origin/feature"]["ahead"] is None
+
+
+@pytest.mark.asyncio
+async def test_analyze_branches_is_merged(tmp_path):
+    """Branches reachable from the default branch are flagged as merged, remote ones too."""
+    repo_dir = make_repo(tmp_path, "merged_repo", branch="main")
+    os.system(f"git -C {repo_dir} commit --allow-empty -m 'Base'")
+    os.system(f"git -C {repo_dir} checkout -b done")
+    os.system(f"git -C {repo_dir} commit --allow-empty -m 'Done work'")
+    os.system(f"git -C {repo_dir} checkout main")
+    os.system(f"git -C {repo_dir} merge --no-ff -m 'Merge done' done")
+    os.system(f"git -C {repo_dir} checkout -b abandoned")
+    os.system(f"git -C {repo_dir} commit --allow-empty -m 'Abandoned work'")
+    os.system(f"git -C {repo_dir} checkout main")
+
+    local_path = str(repo_dir)
+    manager = gradelib.RepoManager([local_path], "user", "token")
+    await manager.clone(local_path)
+    branches = await manager.analyze_branches([local_path])
+    by_name = {b["name"]: b for b in branches[local_path]}
+    assert by_name["main"]["is_merged"] is True
+    assert by_name["origin/done"]["is_merged"] is True
+    assert by_name["origin/abandoned"]["is_merged"] is False
+
+
+@pytest.mark.asyncio
+async def test_fetch_collaborators_invalid_affiliation():
+    """An unknown affiliation filter is rejected before any request is made."""
+    manager = gradelib.RepoManager([], "user", "token")
+    with pytest.raises(ValueError):
+        await manager.fetch_collaborators(TEST_REPOS, affiliation="everyone")
+
+
+@pytest.mark.asyncio
+async def test_fetch_collaborators_permission(tmp_path, monkeypatch):
+    """Each collaborator's permission flags collapse to the highest level granted."""
+    def flags(*granted):
+        return {level: level in granted for level in ("admin", "maintain", "push", "triage", "pull")}
+
+    listing = [
+        {"login": "owner", "permissions": flags("admin", "maintain", "push", "triage", "pull")},
+        {"login": "lead", "permissions": flags("maintain", "push", "triage", "pull")},
+        {"login": "student", "permissions": flags("push", "pull")},
+        {"login": "ta", "permissions": flags("triage", "pull")},
+        {"login": "auditor", "permissions": flags("pull")},
+        # Its profile lookup fails below, but the permission still comes from the listing
+        {"login": "ghost", "permissions": flags("push", "pull")},
+    ]
+    requested = []
+
+    def respond(path):
+        requested.append(path)
+        if path == "/users/ghost":
+            return 404, {"message": "Not Found"}
+        if path.startswith("/users/"):
+            login = path.rsplit("/", 1)[1]
+            return 200, {"login": login, "id": 1, "name": None, "email": None, "avatar_url": None}
+        return 200, listing
+
+    repo_url = "https://github.com/octocat/Hello-World"
+    with mock_github_api(tmp_path, monkeypatch, respond) as proxy_url:
+        manager = gradelib.RepoManager([], "user", "token", proxy_url=proxy_url)
+        collaborators = (await manager.fetch_collaborators(
+            [repo_url], affiliation="outside"))[repo_url]
+    assert {c["login"]: c["permission"] for c in collaborators} == {
+        "owner": "admin", "lead": "maintain", "student": "push", "ta": "triage",
+        "auditor": "pull", "ghost": "push"}
+    assert all(c["affiliation"] == "outside" for c in collaborators)
+    # The outside listing needs no further affiliation lookups
+    assert [p for p in requested if not p.startswith("/users/")] == [
+        "/repos/octocat/Hello-World/collaborators?affiliation=outside&per_page=100"]
+
+
+@pytest.mark.asyncio
+async def test_fetch_collaborators_affiliation_tags():
+    """Every collaborator is tagged; an outside-only listing is tagged outside throughout."""
+    manager = gradelib.RepoManager([], "user", os.getenv("GITHUB_TOKEN", "fake-token"))
+    repo_url = TEST_REPOS[0]
+    everyone = (await manager.fetch_collaborators([repo_url]))[repo_url]
+    outside = (await manager.fetch_collaborators([repo_url], affiliation="outside"))[repo_url]
+    if isinstance(everyone, str) or isinstance(outside, str):
+        return  # No network or API access
+    assert {c["affiliation"] for c in everyone} <= {"outside", "direct", "organization"}
+    assert all(c["affiliation"] == "outside" for c in outside)
+    # Outside collaborators are tagged as such in the combined listing, not as direct
+    outside_logins = {c["login"] for c in outside}
+    for collaborator in everyone:
+        assert (collaborator["affiliation"] == "outside") == (collaborator["login"] in outside_logins)
+
+
+@pytest.mark.asyncio
+async def test_fetch_collaborators_affiliation_lookup_failure(tmp_path, monkeypatch):
+    """A failed affiliation lookup tags collaborators unknown; max_pages caps the lookups."""
+    requested = []
+    direct_url = "https://api.github.com/repos/octocat/Hello-World/collaborators?affiliation=direct"
+
+    def respond(path):
+        requested.append(path)
+        if path.startswith("/users/"):
+            login = path.rsplit("/", 1)[1]
+            return 200, {"login": login, "id": 1, "name": None, "email": None, "avatar_url": None}
+        if "affiliation=outside" in path:
+            return 502, {"message": "Server Error"}
+        if "affiliation=direct" in path and "page=2" in path:
+            return 200, [{"login": "carol"}]
+        if "affiliation=direct" in path:
+            return 200, [{"login": "alice"}], {"Link": f'<{direct_url}&page=2>; rel="next"'}
+        return 200, [{"login": login} for login in ("alice", "bob", "carol")]
+
+    repo_url = "https://github.com/octocat/Hello-World"
+    with mock_github_api(tmp_path, monkeypatch, respond) as proxy_url:
+        manager = gradelib.RepoManager([], "user", "token", proxy_url=proxy_url)
+        # Outside collaborators are direct ones too, so only those listed as direct are unknown
+        collaborators = (await manager.fetch_collaborators([repo_url]))[repo_url]
+        assert {c["login"]: c["affiliation"] for c in collaborators} == {
+            "alice": "unknown", "bob": "organization", "carol": "unknown"}
+
+        requested.clear()
+        collaborators = (await manager.fetch_collaborators([repo_url], max_pages=1))[repo_url]
+        assert not [path for path in requested if "page=2" in path]
+        assert {c["login"]: c["affiliation"] for c in collaborators} == {
+            "alice": "unknown", "bob": "organization", "carol": "organization"}
+
+
+@pytest.mark.asyncio
+async def test_cancel_clone(tmp_path):
+    """Cancelling mid-clone leaves the task failed; cancelling again is a no-op."""
+    repo_dir = make_repo(tmp_path, "big_repo", files={
+        f"file_{i}.bin": os.urandom(16384) for i in range(2000)}, message="Big")
+
+    # file:// forces the regular transport (with progress callbacks) over a local copy
+    local_path = f"file://{repo_dir}"
+    manager = gradelib.RepoManager([local_path], "user", "token")
+    assert manager.cancel_clone(local_path) is False
+
+    clone = asyncio.ensure_future(manager.clone(local_path))
+    while True:
+        tasks = await manager.fetch_clone_tasks()
+        if tasks[local_path].status.status_type == "cloning":
+            break
+        await asyncio.sleep(0.001)
+    assert manager.cancel_all() == 1
+    await clone
+
+    tasks = await manager.fetch_clone_tasks()
+    assert tasks[local_path].status.status_type == "failed"
+    assert tasks[local_path].status.error == "cancelled"
+    assert manager.cancel_clone(local_path) is False
+
+
+@pytest.mark.asyncio
+async def test_bulk_blame_many_files_bounded(tmp_path):
+    """Every file gets a result when blames are capped, including per-file errors."""
+    paths = [f"file_{i}.txt" for i in range(50)]
+    repo_dir = make_repo(tmp_path, "many_files_repo",
+                         files={path: f"{path}\n" for path in paths}, message="Many")
+
+    results = await gradelib.RepoManager([], "user", "token").bulk_blame(
+        str(repo_dir), paths + ["missing.txt"], max_parallel=2)
+    assert set(results.keys()) == set(paths) | {"missing.txt"}
+    for path in paths:
+        assert results[path][0]["line_content"] == path
+    assert isinstance(results["missing.txt"], str)
+
+
+@pytest.mark.asyncio
+async def test_bulk_blame_shared_workers(tmp_path):
+    """Workers sharing one repository handle give the same results at any parallelism."""
+    paths = [f"src/module_{i}.py" for i in range(200)]
+    repo_dir = make_repo(tmp_path, "shared_workers_repo",
+                         files={path: f"# {path}\nvalue = 1\n" for path in paths}, message="Many")
+
+    serial = await gradelib.RepoManager([], "user", "token").bulk_blame(
+        str(repo_dir), paths, max_parallel=1)
+    parallel = await gradelib.RepoManager([], "user", "token").bulk_blame(
+        str(repo_dir), paths + ["missing.py"], max_parallel=8, rev="HEAD")
+    for path in paths:
+        assert [line["line_content"] for line in serial[path]] == [f"# {path}", "value = 1"]
+        assert parallel[path] == serial[path]
+    assert isinstance(parallel["missing.py"], str)
+
+
+@pytest.mark.asyncio
+async def test_export_json_commits(tmp_path):
+    """Exported commit results round-trip through a JSON file."""
+    repo_dir = make_repo(tmp_path, "export_repo", files={"main.py": "print('Hello')\n"})
+
+    local_path = str(repo_dir)
+    out_file = tmp_path / "commits.json"
+    manager = gradelib.RepoManager([local_path], "user", "token")
+    with pytest.raises(ValueError):
+        manager.export_json(str(out_file), "commits", local_path)
+
+    commits = await manager.analyze_commits(local_path)
+    manager.export_json(str(out_file), "commits", local_path)
+    exported = json.loads(out_file.read_text())
+    assert [c["sha"] for c in exported] == [c["sha"] for c in commits]
+    assert exported[0]["author_email"] == "test@example.com"
+
+    with pytest.raises(ValueError):
+        manager.export_json(str(out_file), "not_a_kind", local_path)
+
+
+@pytest.mark.asyncio
+async def test_repo_manager_from_env():
+    """from_env requires GITHUB_TOKEN and builds a working manager."""
+    saved = os.environ.pop("GITHUB_TOKEN", None)
+    try:
+        with pytest.raises(ValueError):
+            gradelib.RepoManager.from_env(TEST_REPOS)
+
+        os.environ["GITHUB_TOKEN"] = saved or "fake-token"
+        manager = gradelib.RepoManager.from_env(TEST_REPOS)
+        assert isinstance(manager, gradelib.RepoManager)
+        tasks = await manager.fetch_clone_tasks()
+        assert set(tasks.keys()) == set(TEST_REPOS)
+    finally:
+        if saved is None:
+            os.environ.pop("GITHUB_TOKEN", None)
+        else:
+            os.environ["GITHUB_TOKEN"] = saved
+
+
+@pytest.mark.asyncio
+async def test_bulk_blame_at_revision(tmp_path):
+    """Blaming at different revisions yields different attributions."""
+    repo_dir = make_repo(tmp_path, "rev_repo", files={"main.py": "x = 1\n"},
+                         message="First", email="first@example.com")
+    os.system(f"git -C {repo_dir} tag v1")
+    (repo_dir / "main.py").write_text("x = 2\n")
+    os.system(
+        f"git -C {repo_dir} add main.py && "
+        f"git -C {repo_dir} -c user.email=second@example.com commit -m 'Second'")
+
+    manager = gradelib.RepoManager([], "user", "token")
+    local_path = str(repo_dir)
+    at_head = await manager.bulk_blame(local_path, ["main.py"], rev="HEAD")
+    at_v1 = await manager.bulk_blame(local_path, ["main.py"], rev="v1")
+    assert at_head["main.py"][0]["author_email"] == "second@example.com"
+    assert at_v1["main.py"][0]["author_email"] == "first@example.com"
+    assert at_v1["main.py"][0]["line_content"] == "x = 1"
+
+    missing = await manager.bulk_blame(local_path, ["main.py"], rev="no-such-rev")
+    assert "no-such-rev" in missing["main.py"]
+
+
+@pytest.mark.asyncio
+async def test_bulk_blame_cache(tmp_path):
+    """A repeated blame is served from the cache until HEAD moves."""
+    repo_dir = make_repo(tmp_path, "cache_repo", files={"main.py": "x = 1\n"}, message="First")
+
+    manager = gradelib.RepoManager([], "user", "token")
+    local_path = str(repo_dir)
+    first = await manager.bulk_blame(local_path, ["main.py"])
+    second = await manager.bulk_blame(local_path, ["main.py"])
+    assert first == second
+    assert manager.blame_cache_info() == {"hits": 1, "misses": 1, "entries": 1}
+
+    # Different options are cached separately
+    await manager.bulk_blame(local_path, ["main.py"], ignore_whitespace=True)
+    assert manager.blame_cache_info()["misses"] == 2
+
+    # A new HEAD invalidates the repository's entries
+    (repo_dir / "main.py").write_text("x = 2\n")
+    os.system(f"git -C {repo_dir} commit -am 'Second'")
+    third = await manager.bulk_blame(local_path, ["main.py"])
+    assert third["main.py"][0]["line_content"] == "x = 2"
+    assert manager.blame_cache_info() == {"hits": 1, "misses": 3, "entries": 1}
+
+    manager.clear_blame_cache()
+    assert manager.blame_cache_info() == {"hits": 0, "misses": 0, "entries": 0}
+
+
+@pytest.mark.asyncio
+async def test_bulk_blame_author_time(tmp_path):
+    """Each blamed line carries when it was authored and the author's timezone offset."""
+    repo_dir = make_repo(tmp_path, "blame_time_repo")
+    (repo_dir / "main.py").write_text("x = 1\n")
+    os.system(
+        f"git -C {repo_dir} add main.py && "
+        f"GIT_AUTHOR_DATE='2024-01-31T12:00:00+02:00' git -C {repo_dir} commit -m 'Dated'")
+
+    manager = gradelib.RepoManager([], "user", "token")
+    result = await manager.bulk_blame(str(repo_dir), ["main.py"])
+    line = result["main.py"][0]
+    assert line["author_time"] == 1706695200
+    assert line["author_offset"] == 120
+
+
+@pytest.mark.asyncio
+async def test_fetch_issues_since(tmp_path, monkeypatch):
+    """since is validated locally and sent as a query parameter, offsets included."""
+    queries = []
+
+    def respond(path):
+        query = parse_qs(urlsplit(path).query)
+        queries.append(query)
+        # GitHub does the filtering; answer as if only issue 2 was updated since then
+        return 200, [mock_issue(2, updated_at="2024-03-05T00:00:00Z")] if "since" in query else [
+            mock_issue(1), mock_issue(2, updated_at="2024-03-05T00:00:00Z")]
+
+    repo_url = "https://github.com/octocat/Hello-World"
+    with mock_github_api(tmp_path, monkeypatch, respond) as proxy_url:
+        manager = gradelib.RepoManager([], "user", "token", proxy_url=proxy_url)
+        with pytest.raises(ValueError):
+            await manager.fetch_issues([repo_url], since="last tuesday")
+        assert queries == []
+
+        results = await manager.fetch_issues([repo_url], since="2024-03-01T00:00:00Z")
+        assert [i["number"] for i in results[repo_url]] == [2]
+        assert queries[-1]["since"] == ["2024-03-01T00:00:00Z"]
+
+        # A "+" offset must reach GitHub as "+", not as an encoded space
+        await manager.fetch_issues([repo_url], since="2024-03-01T02:00:00+02:00")
+        assert queries[-1]["since"] == ["2024-03-01T02:00:00+02:00"]
+
+        results = await manager.fetch_issues([repo_url])
+        assert len(results[repo_url]) == 2 and "since" not in queries[-1]
+
+
+@pytest.mark.asyncio
+async def test_fetch_pull_request_files_invalid_url():
+    """An unparseable repository URL is reported as a ValueError."""
+    manager = gradelib.RepoManager([], "user", "token")
+    with pytest.raises(ValueError):
+        await manager.fetch_pull_request_files("not-a-repo-url", 1)
+
+
+@pytest.mark.asyncio
+async def test_fetch_pull_request_review_comments(tmp_path, monkeypatch):
+    """Review comments keep their thread links; outdated ones have no current line."""
+    def comment(id, line, original_line, in_reply_to_id=None, user="ta"):
+        return {"id": id, "user": user and {"login": user, "id": 2}, "body": f"comment {id}",
+                "path": "src/main.py", "line": line, "original_line": original_line,
+                "commit_id": "c" * 40, "original_commit_id": "d" * 40,
+                "created_at": f"2024-01-0{id}T00:00:00Z", "in_reply_to_id": in_reply_to_id,
+                "diff_hunk": "@@ -1,3 +1,4 @@"}
+
+    thread = [
+        comment(1, 12, 10),
+        comment(2, 12, 10, in_reply_to_id=1, user="student"),
+        # Outdated: the line it was left on has since been changed
+        comment(3, None, 4),
+        comment(4, None, 4, in_reply_to_id=3, user=None),
+    ]
+    requested = []
+
+    def respond(path):
+        requested.append(path)
+        return 200, thread
+
+    with mock_github_api(tmp_path, monkeypatch, respond) as proxy_url:
+        manager = gradelib.RepoManager([], "user", "token", proxy_url=proxy_url)
+        with pytest.raises(ValueError):
+            await manager.fetch_pull_request_review_comments("not-a-repo-url", 1)
+        comments = await manager.fetch_pull_request_review_comments(
+            "https://github.com/octocat/Hello-World", 5)
+
+    assert requested == ["/repos/octocat/Hello-World/pulls/5/comments?per_page=100&page=1"]
+    assert comments[0] == {
+        "id": 1, "user_login": "ta", "body": "comment 1", "path": "src/main.py", "line": 12,
+        "original_line": 10, "commit_id": "c" * 40, "created_at": "2024-01-01T00:00:00Z",
+        "in_reply_to_id": None}
+    assert [(c["id"], c["in_reply_to_id"]) for c in comments] == [
+        (1, None), (2, 1), (3, None), (4, 3)]
+    assert [(c["line"], c["original_line"]) for c in comments[2:]] == [(None, 4), (None, 4)]
+    assert comments[3]["user_login"] is None
+
+
+@pytest.mark.asyncio
+async def test_analyze_commits_max_count(tmp_path):
+    """max_count returns only the newest commits, after the author filter."""
+    repo_dir = make_repo(tmp_path, "max_count_repo", email=None)
+    for i in range(12):
+        email = "other@example.com" if i % 2 else "test@example.com"
+        os.system(f"git -C {repo_dir} -c user.email={email} "
+                  f"commit -q --allow-empty -m 'Commit {i}'")
+
+    manager = gradelib.RepoManager([], "user", "token")
+    commits = await manager.analyze_commits(str(repo_dir), max_count=5)
+    assert [c["message"].strip() for c in commits] == [f"Commit {i}" for i in range(11, 6, -1)]
+
+    by_author = await manager.analyze_commits(
+        str(repo_dir), author_email=["test@example.com"], max_count=3)
+    assert [c["message"].strip() for c in by_author] == ["Commit 10", "Commit 8", "Commit 6"]
+    assert len(await manager.analyze_commits(str(repo_dir), max_count=100)) == 12
+
+
+@pytest.mark.asyncio
+async def test_analyze_commits_path(tmp_path):
+    """path keeps only commits touching one folder and counts only its lines."""
+    repo_dir = make_repo(tmp_path, "path_repo", files={"students/alice/a.py": "a = 1\n"},
+                         message="Alice starts")
+    (repo_dir / "students" / "bob").mkdir()
+    (repo_dir / "students" / "bob" / "b.py").write_text("b = 1\nb = 2\n")
+    os.system(f"git -C {repo_dir} add . && git -C {repo_dir} commit -m 'Bob starts'")
+    (repo_dir / "students" / "alice" / "a.py").write_text("a = 2\n")
+    (repo_dir / "students" / "bob" / "b.py").write_text("b = 3\n")
+    os.system(f"git -C {repo_dir} add . && git -C {repo_dir} commit -m 'Both edit'")
+
+    local_path = str(repo_dir)
+    manager = gradelib.RepoManager([local_path], "user", "token")
+    commits = await manager.analyze_commits(local_path, path="students/alice")
+    assert [c["message"] for c in commits] == ["Both edit", "Alice starts"]
+    assert (commits[0]["additions"], commits[0]["deletions"]) == (1, 1)
+    assert commits[0]["files"] == []
+
+    commits = await manager.analyze_commits(local_path, path="students/alice", file_stats=True)
+    assert [f["path"] for f in commits[0]["files"]] == ["students/alice/a.py"]
+    assert (commits[0]["additions"], commits[0]["deletions"]) == (1, 1)
+
+    # A trailing slash is accepted; a sibling with the same prefix is not matched
+    assert len(await manager.analyze_commits(local_path, path="students/alice/")) == 2
+    with pytest.raises(ValueError):
+        await manager.analyze_commits(local_path, path="students/ali")
+
+    await manager.clone_all()
+    summary = await manager.commit_summary(local_path, path="students/bob")
+    assert summary["total_commits"] == 2
+    assert summary["total_additions"] == 3
+    assert summary["total_deletions"] == 2
+
+
+@pytest.mark.asyncio
+async def test_commit_signature_status(tmp_path):
+    """An SSH-signed commit is reported as signed, a plain one as unsigned."""
+    key = tmp_path / "signing_key"
+    if os.system(f"ssh-keygen -q -t ed25519 -N '' -f {key}") != 0:
+        pytest.skip("ssh-keygen not available")
+    repo_dir = make_repo(tmp_path, "signed_repo")
+    os.system(f"git -C {repo_dir} commit -q --allow-empty -m 'Unsigned'")
+    if os.system(f"git -C {repo_dir} -c gpg.format=ssh -c user.signingkey={key} "
+                 f"commit -q -S --allow-empty -m 'Signed'") != 0:
+        pytest.skip("git can't sign commits with SSH keys")
+
+    manager = gradelib.RepoManager([], "user", "token")
+    commits = await manager.analyze_commits(
+        str(repo_dir), fields=["message", "signature_status"])
+    assert commits == [
+        {"message": "Signed", "signature_status": "signed"},
+        {"message": "Unsigned", "signature_status": "unsigned"},
+    ]
+
+
+@pytest.mark.asyncio
+async def test_analyze_commits_ref(tmp_path):
+    """A branch-only commit is analyzed via ref without being on HEAD."""
+    repo_dir = make_repo(tmp_path, "ref_repo", files={"a.py": "a = 1\n"},
+                         message="Base", email="author@example.com")
+    os.system(f"git -C {repo_dir} checkout -b feature")
+    (repo_dir / "b.py").write_text("b = 1\n")
+    os.system(f"git -C {repo_dir} add b.py && git -C {repo_dir} commit -m 'Feature work'")
+    os.system(f"git -C {repo_dir} checkout -")
+
+    local_path = str(repo_dir)
+    manager = gradelib.RepoManager([local_path], "user", "token")
+
+    head_messages = [c["message"] for c in await manager.analyze_commits(local_path)]
+    assert head_messages == ["Base"]
+
+    feature_messages = [c["message"] for c in await manager.analyze_commits(local_path, ref="feature")]
+    assert feature_messages == ["Feature work", "Base"]
+
+    all_messages = [c["message"] for c in await manager.analyze_commits(local_path, ref="--all")]
+    assert sorted(all_messages) == ["Base", "Feature work"]
+
+    with pytest.raises(ValueError):
+        await manager.analyze_commits(local_path, ref="no-such-branch")
+
+
+@pytest.mark.asyncio
+async def test_analyze_commits_merge_parents(tmp_path):
+    """A merge commit reports both parent SHAs."""
+    repo_dir = make_repo(tmp_path, "merge_repo", files={"a.py": "a = 1\n"},
+                         message="Base", email="author@example.com")
+    os.system(f"git -C {repo_dir} checkout -b feature")
+    (repo_dir / "b.py").write_text("b = 1\n")
+    os.system(f"git -C {repo_dir} add b.py && git -C {repo_dir} commit -m 'Feature'")
+    os.system(f"git -C {repo_dir} checkout -")
+    (repo_dir / "c.py").write_text("c = 1\n")
+    os.system(f"git -C {repo_dir} add c.py && git -C {repo_dir} commit -m 'Main'")
+    os.system(f"git -C {repo_dir} merge --no-ff feature -m 'Merge feature'")
+
+    manager = gradelib.RepoManager([str(repo_dir)], "user", "token")
+    commits = {c["message"]: c for c in await manager.analyze_commits(str(repo_dir))}
+
+    merge = commits["Merge feature"]
+    assert merge["is_merge"]
+    assert merge["parents"] == [commits["Main"]["sha"], commits["Feature"]["sha"]]
+    assert commits["Main"]["parents"] == [commits["Base"]["sha"]]
+    assert commits["Base"]["parents"] == []
+
+
+@pytest.mark.asyncio
+async def test_analyze_commits_detects_renames(tmp_path):
+    """A renamed file is reported as a rename rather than a delete plus an add."""
+    repo_dir = make_repo(tmp_path, "rename_repo", files={
+        "old_name.py": "".join(f"line_{i} = {i}\n" for i in range(20)),
+    }, message="Add", email="author@example.com")
+    os.system(f"git -C {repo_dir} mv old_name.py new_name.py && git -C {repo_dir} commit -m 'Rename'")
+
+    manager = gradelib.RepoManager([str(repo_dir)], "user", "token")
+    # Without per-file stats the rename counts as the file deleted and re-added
+    commits = {c["message"]: c for c in await manager.analyze_commits(str(repo_dir))}
+    assert commits["Rename"]["files"] == []
+    assert (commits["Rename"]["additions"], commits["Rename"]["deletions"]) == (20, 20)
+
+    commits = {
+        c["message"]: c
+        for c in await manager.analyze_commits(str(repo_dir), file_stats=True)
+    }
+    rename = commits["Rename"]
+    assert rename["files"] == [{
+        "path": "new_name.py",
+        "old_path": "old_name.py",
+        "status": "renamed",
+        "additions": 0,
+        "deletions": 0,
+    }]
+    assert rename["additions"] == 0 and rename["deletions"] == 0
+
+    added = commits["Add"]["files"]
+    assert [(f["path"], f["status"], f["additions"]) for f in added] == [("old_name.py", "added", 20)]
+    assert commits["Add"]["additions"] == 20
+
+
+@pytest.mark.asyncio
+async def test_watch_clone_progress(tmp_path):
+    """Clone progress is streamed until every task has finished."""
+    repo_dir = make_repo(tmp_path, "watched_repo", files={
+        f"file_{i}.txt": f"content {i}\n" for i in range(50)}, message="Files")
+
+    # file:// forces the regular transport (with progress callbacks) over a local copy
+    good_url = f"file://{repo_dir}"
+    bad_url = f"file://{tmp_path / 'missing_repo'}"
+    manager = gradelib.RepoManager([good_url, bad_url], "user", "token")
+
+    stream = manager.watch_clone_progress()
+    clone = asyncio.ensure_future(manager.clone_all())
+    events = [event async for event in stream]
+    await clone
+
+    good_events = [(status, progress) for url, status, progress in events if url == good_url]
+    assert good_events[0] == ("cloning", 0)
+    assert good_events[-1] == ("completed", None)
+    progress = [p for status, p in good_events if status == "cloning"]
+    assert progress == sorted(progress)
+    assert len(progress) == len(set(progress))
+
+    bad_events = [status for url, status, _ in events if url == bad_url]
+    assert bad_events[-1] == "failed"
+
+
+@pytest.mark.asyncio
+async def test_max_concurrent_clones(tmp_path):
+    """With a limit of 2, the other clones stay queued until a slot frees up."""
+    urls = []
+    for i in range(5):
+        repo_dir = make_repo(tmp_path, f"queued_repo_{i}")
+        os.system(f"git -C {repo_dir} commit --allow-empty -m 'Initial'")
+        urls.append(str(repo_dir))
+
+    with pytest.raises(ValueError):
+        gradelib.RepoManager(urls, "user", "token", max_concurrent_clones=0)
+
+    manager = gradelib.RepoManager(urls, "user", "token", max_concurrent_clones=2)
+    stream = manager.watch_clone_progress()
+    clone = asyncio.ensure_future(manager.clone_all())
+    cloning, most_at_once = set(), 0
+    async for url, status, _ in stream:
+        if status == "cloning":
+            cloning.add(url)
+        else:
+            cloning.discard(url)
+        most_at_once = max(most_at_once, len(cloning))
+    await clone
+
+    assert most_at_once == 2
+    tasks = await manager.fetch_clone_tasks()
+    assert all(task.status.status_type == "completed" for task in tasks.values())
+
+    # Once everything has finished, a new stream ends immediately
+    assert [event async for event in manager.watch_clone_progress()] == []
+
+
+@pytest.mark.asyncio
+async def test_fetch_pull_requests_graphql(tmp_path, monkeypatch):
+    """The GraphQL path pages by cursor, maps nodes like REST and reports errors per repository."""
+    def node(number, **fields):
+        return dict({
+            "databaseId": number, "number": number, "title": f"PR {number}", "state": "MERGED",
+            "createdAt": "2024-01-01T00:00:00Z", "updatedAt": "2024-01-02T00:00:00Z",
+            "closedAt": "2024-01-02T00:00:00Z", "mergedAt": "2024-01-02T00:00:00Z",
+            "body": None, "isDraft": False, "merged": True, "mergeable": "MERGEABLE",
+            "additions": 10, "deletions": 2, "changedFiles": 3,
+            "baseRefName": "main", "baseRefOid": "a" * 40,
+            "headRefName": f"feature-{number}", "headRefOid": "b" * 40,
+            "isCrossRepository": False, "authorAssociation": "CONTRIBUTOR",
+            "headRepository": {"nameWithOwner": "octocat/Hello-World"},
+            "author": {"login": "student", "databaseId": 7}, "mergedBy": {"login": "ta"},
+            "comments": {"totalCount": 4}, "commits": {"totalCount": 5},
+            "labels": {"nodes": [{"name": "graded"}]},
+        }, **fields)
+
+    queries = []
+
+    def respond(path, query):
+        assert path == "/graphql"
+        queries.append(query["variables"])
+        if query["variables"]["name"] == "broken":
+            return 200, {"data": None, "errors": [{"message": "Something went wrong"}]}
+        if query["variables"]["cursor"] is None:
+            nodes, page_info = [node(1)], {"hasNextPage": True, "endCursor": "c1"}
+        else:
+            nodes = [node(2, state="OPEN", merged=False, mergedAt=None, closedAt=None,
+                          mergeable="CONFLICTING", author=None, mergedBy=None,
+                          headRepository=None)]
+            page_info = {"hasNextPage": False, "endCursor": "c2"}
+        return 200, {"data": {"repository": {"pullRequests": {
+            "pageInfo": page_info, "nodes": nodes}}}}
+
+    repo_url = "https://github.com/octocat/Hello-World"
+    broken_url = "https://github.com/octocat/broken"
+    with mock_github_api(tmp_path, monkeypatch, respond) as proxy_url:
+        manager = gradelib.RepoManager([], "user", "token", proxy_url=proxy_url)
+        results = await manager.fetch_pull_requests(
+            ["not-a-repo-url", repo_url, broken_url], state="closed", use_graphql=True)
+        assert isinstance(results["not-a-repo-url"], str)
+        assert results[broken_url] == "GitHub GraphQL error: Something went wrong"
+        merged, open_pr = results[repo_url]
+        assert [q["cursor"] for q in queries if q["name"] == "Hello-World"] == [None, "c1"]
+        assert all(q["states"] == ["CLOSED", "MERGED"] for q in queries)
+
+        assert (merged["state"], merged["merged"], merged["mergeable"]) == ("closed", True, True)
+        assert (merged["user_login"], merged["user_id"], merged["merged_by"]) == ("student", 7, "ta")
+        assert (merged["comments"], merged["commits"], merged["additions"]) == (4, 5, 10)
+        assert merged["labels"] == ["graded"] and not merged["is_cross_fork"]
+        assert (open_pr["state"], open_pr["merged"], open_pr["mergeable"]) == ("open", False, False)
+        assert open_pr["user_login"] == "ghost" and open_pr["is_cross_fork"]
+
+        queries.clear()
+        results = await manager.fetch_pull_requests([repo_url], max_pages=1, use_graphql=True)
+        assert len(results[repo_url]) == 1 and len(queries) == 1
+        assert queries[0]["states"] is None
+
+
+@pytest.mark.asyncio
+async def test_list_files(tmp_path):
+    """Tracked files are listed and narrowed by a glob."""
+    repo_dir = make_repo(tmp_path, "files_repo", files={
+        "README.md": "# Readme\n",
+        "setup.py": "setup()\n",
+        "src/main.py": "main()\n",
+        "src/pkg/util.py": "util()\n",
+    })
+
+    local_path = str(repo_dir)
+    manager = gradelib.RepoManager([local_path], "user", "token")
+    with pytest.raises(ValueError):
+        await manager.list_files(local_path)
+
+    await manager.clone_all()
+    assert await manager.list_files(local_path) == [
+        "README.md", "setup.py", "src/main.py", "src/pkg/util.py"]
+    assert await manager.list_files(local_path, "*.py") == ["setup.py"]
+    assert await manager.list_files(local_path, "**/*.py") == [
+        "setup.py", "src/main.py", "src/pkg/util.py"]
+    assert await manager.list_files(local_path, "src/*.py") == ["src/main.py"]
+    assert await manager.list_files(local_path, "*.[mM][dD]") == ["README.md"]
+    with pytest.raises(ValueError):
+        await manager.list_files(local_path, "src/[abc")
+
+
+@pytest.mark.asyncio
+async def test_clone_timeout(tmp_path):
+    """A clone exceeding clone_timeout fails with a timeout error."""
+    repo_dir = make_repo(tmp_path, "slow_repo", files={
+        f"file_{i}.bin": os.urandom(16384) for i in range(500)}, message="Big")
+
+    with pytest.raises(ValueError):
+        gradelib.RepoManager([], "user", "token", clone_timeout=0)
+
+    # file:// forces the regular transport (with progress callbacks) over a local copy
+    local_path = f"file://{repo_dir}"
+    manager = gradelib.RepoManager([local_path], "user", "token", clone_timeout=0.001)
+    await manager.clone_all()
+
+    tasks = await manager.fetch_clone_tasks()
+    assert tasks[local_path].status.status_type == "failed"
+    assert tasks[local_path].status.error == "timed out after 0.001s"
+
+    # A timed-out clone's thread keeps running until its next progress callback and
+    # holds the only clone slot until then, so the second clone waits for it. That
+    # callback must not reopen the failed task.
+    small_dir = make_repo(tmp_path, "small_repo")
+    os.system(f"git -C {small_dir} commit -q --allow-empty -m 'Small'")
+    small_path = str(small_dir)
+    manager = gradelib.RepoManager(
+        [local_path, small_path], "user", "token", clone_timeout=0.3, max_concurrent_clones=1)
+    await manager.clone(local_path)
+    await manager.clone(small_path)
+    for _ in range(10):
+        summary = manager.clone_summary()
+        assert (summary["cloning"], summary["completed"], summary["failed"]) == (0, 1, 1)
+        await asyncio.sleep(0.5)
+
+
+@pytest.mark.asyncio
+async def test_http_timeout(monkeypatch):
+    """A stalled API connection fails with a timeout error instead of hanging."""
+    import socket
+    import threading
+    import time
+
+    # A proxy that accepts connections but never answers stalls every request
+    server = socket.socket()
+    server.bind(("127.0.0.1", 0))
+    server.listen()
+    accepted = []
+    threading.Thread(target=lambda: accepted.append(server.accept()), daemon=True).start()
+    monkeypatch.setenv("HTTPS_PROXY", f"http://127.0.0.1:{server.getsockname()[1]}")
+
+    with pytest.raises(ValueError):
+        gradelib.RepoManager([], "user", "token", http_timeout=-1)
+
+    manager = gradelib.RepoManager([], "user", "token", http_timeout=0.5)
+    start = time.monotonic()
+    with pytest.raises(ValueError, match="timed out"):
+        await manager.fetch_pull_request_files("https://github.com/octocat/Hello-World", 1)
+    assert time.monotonic() - start < 10
+    server.close()
+
+
+@pytest.mark.asyncio
+async def test_validate_urls():
+    """Each managed URL is reported as valid or with the reason it is not."""
+    valid = [
+        "https://github.com/owner/repo",
+        "https://github.com/owner/repo2.git",
+        "https://github.com/owner/repo3/",
+        "git@github.com:owner/repo4.git",
+    ]
+    invalid = [
+        "https://github.com/just-a-repo",
+        "https://gitlab.com/owner/repo",
+        "not a url",
+    ]
+    manager = gradelib.RepoManager(valid + invalid, "user", "token")
+    results = manager.validate_urls()
+
+    assert set(results) == set(valid + invalid)
+    for url in valid:
+        assert results[url] is None, url
+    for url in invalid:
+        assert isinstance(results[url], str) and results[url], url
+    assert "gitlab.com" in results["https://gitlab.com/owner/repo"]
+
+
+@pytest.mark.asyncio
+async def test_url_normalization_variants():
+    """Every common spelling of a GitHub URL yields the same owner and repository."""
+    canonical = "https://github.com/owner/repo"
+    equivalent = [
+        "https://github.com/owner/repo",
+        "https://github.com/owner/repo/",
+        "https://github.com/owner/repo//",
+        "https://github.com/owner/repo.git",
+        "https://github.com/owner/repo.git/",
+        "http://github.com/owner/repo",
+        "https://user@github.com/owner/repo.git",
+        "  https://github.com/owner/repo  ",
+        "git@github.com:owner/repo",
+        "git@github.com:owner/repo.git",
+        "ssh://git@github.com/owner/repo.git",
+        "ssh://git@github.com:22/owner/repo",
+    ]
+    dotted = {
+        "https://github.com/owner/owner.github.io": "https://github.com/owner/owner.github.io",
+        "git@github.com:owner/my.repo.git": "https://github.com/owner/my.repo",
+    }
+    malformed = [
+        "https://github.com/owner",
+        "https://github.com/owner/",
+        "https://github.com//repo",
+        "https://github.com/owner/repo/tree/main",
+        "git@github.com:owner",
+        "github.com/owner/repo",
+        "not a url",
+        "",
+    ]
+
+    manager = gradelib.RepoManager([], "user", "token")
+    for url in equivalent:
+        assert manager.canonical_url(url) == canonical, url
+    for url, expected in dotted.items():
+        assert manager.canonical_url(url) == expected, url
+
+    # Equivalent URLs share one clone task, so each is validated by its own manager
+    for url in equivalent + list(dotted) + malformed:
+        error = gradelib.RepoManager([url], "user", "token").validate_urls()[url]
+        if url in malformed:
+            assert isinstance(error, str) and error, url
+        else:
+            assert error is None, url
+
+
+@pytest.mark.asyncio
+async def test_fetch_pull_request_commits(tmp_path, monkeypatch):
+    """Commits are paged in full and flagged as truncated at GitHub's 250-commit cap."""
+    def commit(n):
+        return {
+            "sha": f"{n:040x}",
+            "commit": {"message": f"commit {n}",
+                       "author": {"email": f"dev{n}@example.com", "date": "2024-01-01T00:00:00Z"}},
+            # Unlinked author emails come back with a null author
+            "author": {"login": f"dev{n}", "id": n} if n % 2 else None,
+        }
+
+    sizes = {1: 150, 2: 400}
+    requests = []
+
+    def respond(path):
+        url = urlsplit(path)
+        pr_number = int(url.path.split("/")[-2])
+        query = parse_qs(url.query)
+        per_page, page = int(query["per_page"][0]), int(query["page"][0])
+        start = (page - 1) * per_page
+        # Like GitHub, never list more than 250 commits however many the PR has
+        end = min(page * per_page, sizes[pr_number], 250)
+        return 200, [commit(n) for n in range(start, end)]
+
+    repo_url = "https://github.com/octocat/Hello-World"
+    with mock_github_api(tmp_path, monkeypatch, respond, requests) as proxy_url:
+        manager = gradelib.RepoManager([], "user", "token", proxy_url=proxy_url)
+        with pytest.raises(ValueError):
+            await manager.fetch_pull_request_commits("not-a-repo-url", 1)
+
+        result = await manager.fetch_pull_request_commits(repo_url, 1)
+        assert len(requests) == 2
+        assert len(result["commits"]) == 150 and result["truncated"] is False
+        first, second = result["commits"][:2]
+        assert first == {"sha": "0" * 40, "message": "commit 0", "author_login": None,
+                         "author_email": "dev0@example.com",
+                         "authored_at": "2024-01-01T00:00:00Z"}
+        assert second["author_login"] == "dev1"
+
+        requests.clear()
+        result = await manager.fetch_pull_request_commits(repo_url, 2)
+        assert [urlsplit(r.path).path for r in requests] == [
+            "/repos/octocat/Hello-World/pulls/2/commits"] * 3
+        assert len(result["commits"]) == 250 and result["truncated"] is True
+
+
+@pytest.mark.asyncio
+async def test_analyze_commits_since(tmp_path):
+    """Only new commits are returned, with a full-history fallback after a force-push."""
+    repo_dir = make_repo(tmp_path, "incremental_repo", email="author@example.com")
+    for name in ("one", "two"):
+        (repo_dir / f"{name}.py").write_text(f"{name} = 1\n")
+        os.system(f"git -C {repo_dir} add {name}.py && git -C {repo_dir} commit -m '{name}'")
+
+    path = str(repo_dir)
+    manager = gradelib.RepoManager([path], "user", "token")
+    full = await manager.analyze_commits(path)
+    last_sha = full[0]["sha"]
+
+    commits, head_sha, full_history = await manager.analyze_commits_since(path, last_sha)
+    assert (commits, head_sha, full_history) == ([], last_sha, False)
+
+    (repo_dir / "three.py").write_text("three = 1\n")
+    os.system(f"git -C {repo_dir} add three.py && git -C {repo_dir} commit -m 'three'")
+    commits, head_sha, full_history = await manager.analyze_commits_since(path, last_sha)
+    assert [c["message"] for c in commits] == ["three"]
+    assert head_sha == commits[0]["sha"]
+    assert not full_history
+
+    # Rewrite history so the old SHA is no longer an ancestor of HEAD
+    os.system(f"git -C {repo_dir} reset -q --hard HEAD~2")
+    (repo_dir / "rewritten.py").write_text("rewritten = 1\n")
+    os.system(f"git -C {repo_dir} add rewritten.py && git -C {repo_dir} commit -m 'rewritten'")
+    commits, head_sha, full_history = await manager.analyze_commits_since(path, head_sha)
+    assert sorted(c["message"] for c in commits) == ["one", "rewritten"]
+    assert full_history
+
+    commits, _, full_history = await manager.analyze_commits_since(path, "not-a-sha")
+    assert len(commits) == 2 and full_history
+
+
+@pytest.mark.asyncio
+async def test_fetch_single_issue_and_pull_request(tmp_path, monkeypatch):
+    """Single issues and pull requests come back as one dict, or None when missing."""
+    def respond(path):
+        if path == "/repos/octocat/Hello-World/issues/1":
+            return 200, mock_issue(1, title="Broken build")
+        if path == "/repos/octocat/Hello-World/pulls/2":
+            return 200, mock_pull_request(
+                2, mergeable=True, merged=True, merged_by={"login": "ta", "id": 2},
+                comments=1, commits=2, additions=3, deletions=4, changed_files=5)
+        if path == "/repos/octocat/Hello-World/issues/3":
+            return 500, {"message": "Server Error"}
+        return 404, {"message": "Not Found"}
+
+    repo_url = "https://github.com/octocat/Hello-World"
+    requests = []
+    with mock_github_api(tmp_path, monkeypatch, respond, requests) as proxy_url:
+        manager = gradelib.RepoManager([], "user", "token", proxy_url=proxy_url)
+        with pytest.raises(ValueError):
+            await manager.fetch_issue("not-a-repo-url", 1)
+        with pytest.raises(ValueError):
+            await manager.fetch_pull_request("not-a-repo-url", 1)
+
+        issue = await manager.fetch_issue(repo_url, 1)
+        assert (issue["number"], issue["title"]) == (1, "Broken build")
+        assert await manager.fetch_issue(repo_url, 999) is None
+        # Only a 404 means "missing"; other failures still raise
+        with pytest.raises(ValueError, match="Server Error"):
+            await manager.fetch_issue(repo_url, 3)
+
+        pr = await manager.fetch_pull_request(repo_url, 2)
+        assert (pr["number"], pr["merged"], pr["merged_by"]) == (2, True, "ta")
+        assert (pr["commits"], pr["changed_files"]) == (2, 5)
+        pr = await manager.fetch_pull_request(repo_url, 2, fields=["number", "merged"])
+        assert pr == {"number": 2, "merged": True}
+        assert await manager.fetch_pull_request(repo_url, 999) is None
+
+    # One request per lookup; no list endpoint is paged through
+    assert [r.path for r in requests] == [
+        "/repos/octocat/Hello-World/issues/1", "/repos/octocat/Hello-World/issues/999",
+        "/repos/octocat/Hello-World/issues/3", "/repos/octocat/Hello-World/pulls/2",
+        "/repos/octocat/Hello-World/pulls/2", "/repos/octocat/Hello-World/pulls/999"]
+
+
+@pytest.mark.asyncio
+async def test_analyze_commits_bulk(tmp_path):
+    """Commits of several repositories are analyzed together; unknown ones get an error."""
+    urls = []
+    for i in range(3):
+        repo_dir = make_repo(tmp_path, f"bulk_repo_{i}")
+        for n in range(i + 1):
+            os.system(f"git -C {repo_dir} commit --allow-empty -m 'Commit {n}'")
+        urls.append(str(repo_dir))
+
+    manager = gradelib.RepoManager(urls, "user", "token")
+    await manager.clone_all()
+    missing = str(tmp_path / "not_managed")
+    results = await manager.analyze_commits_bulk(urls + [missing], fields=["sha", "message"])
+    for i, url in enumerate(urls):
+        assert len(results[url]) == i + 1
+        assert set(results[url][0]) == {"sha", "message"}
+    assert isinstance(results[missing], str)
+
+
+@pytest.mark.asyncio
+async def test_proxy_url(monkeypatch):
+    """API requests and clones go through proxy_url; invalid proxies are rejected up front."""
+    import socket
+    import threading
+
+    monkeypatch.delenv("HTTPS_PROXY", raising=False)
+    monkeypatch.delenv("https_proxy", raising=False)
+    with pytest.raises(ValueError, match="proxy_url"):
+        gradelib.RepoManager([], "user", "token", proxy_url="not a url")
+
+    # A proxy that accepts connections but never answers stalls everything routed through it
+    server = socket.socket()
+    server.bind(("127.0.0.1", 0))
+    server.listen()
+    accepted = []
+    threading.Thread(target=lambda: accepted.append(server.accept()), daemon=True).start()
+    proxy_url = f"http://127.0.0.1:{server.getsockname()[1]}"
+
+    repo_url = "https://github.com/octocat/Hello-World"
+    manager = gradelib.RepoManager(
+        [repo_url], "user", "token", proxy_url=proxy_url, http_timeout=0.5, clone_timeout=1)
+    with pytest.raises(ValueError, match="timed out"):
+        await manager.fetch_pull_request_files(repo_url, 1)
+
+    await manager.clone_all()
+    tasks = await manager.fetch_clone_tasks()
+    assert tasks[repo_url].status.error == "timed out after 1s"
+    assert accepted
+    server.close()
+
+
+@pytest.mark.asyncio
+async def test_check_access(tmp_path, monkeypatch):
+    """200 maps to access details, 404 to a missing repository and 403 to an error."""
+    def respond(path):
+        if path == "/repos/octocat/Hello-World":
+            return 200, {"private": True, "default_branch": "trunk",
+                         "permissions": {"admin": False, "maintain": False, "push": True,
+                                         "triage": True, "pull": True}}
+        if path == "/repos/octocat/public-read":
+            # No permissions block, as for unauthenticated requests
+            return 200, {"private": False, "default_branch": "main"}
+        if path == "/repos/octocat/sso-protected":
+            return 403, {"message": "Resource protected by organization SAML enforcement"}
+        return 404, {"message": "Not Found"}
+
+    repo_url = "https://github.com/octocat/Hello-World"
+    public_url = "https://github.com/octocat/public-read"
+    forbidden_url = "https://github.com/octocat/sso-protected"
+    missing_url = "https://github.com/octocat/missing"
+    with mock_github_api(tmp_path, monkeypatch, respond) as proxy_url:
+        manager = gradelib.RepoManager([], "user", "token", proxy_url=proxy_url)
+        results = await manager.check_access(
+            ["not-a-repo-url", repo_url, public_url, forbidden_url, missing_url])
+    assert isinstance(results["not-a-repo-url"], str)
+    assert results[repo_url] == {
+        "exists": True, "private": True, "permission": "push", "default_branch": "trunk"}
+    assert results[public_url] == {
+        "exists": True, "private": False, "permission": None, "default_branch": "main"}
+    assert results[forbidden_url] == "Access denied to octocat/sso-protected (403 Forbidden)"
+    assert results[missing_url] == {
+        "exists": False, "private": None, "permission": None, "default_branch": None}
+
+
+@pytest.mark.asyncio
+async def test_client_headers(monkeypatch):
+    """A custom User-Agent is sent on outgoing requests; Authorization can't be overridden."""
+    import socket
+    import threading
+
+    with pytest.raises(ValueError, match="Authorization"):
+        gradelib.RepoManager([], "user", "token", client_headers={"Authorization": "token x"})
+    with pytest.raises(ValueError):
+        gradelib.RepoManager([], "user", "token", client_headers={"Bad Name": "x"})
+
+    # A proxy that records the CONNECT request, which carries the User-Agent, and never answers
+    server = socket.socket()
+    server.bind(("127.0.0.1", 0))
+    server.listen()
+    received = []
+
+    def record():
+        conn, _ = server.accept()
+        received.append(conn.recv(4096).decode(errors="replace"))
+
+    thread = threading.Thread(target=record, daemon=True)
+    thread.start()
+    manager = gradelib.RepoManager(
+        [], "user", "token", http_timeout=0.5,
+        proxy_url=f"http://127.0.0.1:{server.getsockname()[1]}",
+        client_headers={"User-Agent": "example-grader/1.0", "X-Institution": "Example U"})
+    with pytest.raises(ValueError):
+        await manager.fetch_pull_request_files("https://github.com/octocat/Hello-World", 1)
+    thread.join(timeout=5)
+    server.close()
+    assert "example-grader/1.0" in received[0]
+
+
+@pytest.mark.asyncio
+async def test_search_issues(tmp_path, monkeypatch):
+    """The query is sent verbatim and results are paged, totalled and flagged as truncated."""
+    queries = []
+
+    def respond(path):
+        url = urlsplit(path)
+        assert url.path == "/search/issues"
+        query = parse_qs(url.query)
+        queries.append(query)
+        page = int(query["page"][0])
+        numbers = range(1, 101) if page == 1 else range(101, 131)
+        return 200, {"total_count": 130, "incomplete_results": False,
+                     "items": [mock_issue(n) for n in numbers]}
+
+    query = 'repo:octocat/Hello-World is:issue label:"good first issue" author:student'
+    with mock_github_api(tmp_path, monkeypatch, respond) as proxy_url:
+        manager = gradelib.RepoManager([], "user", "token", proxy_url=proxy_url)
+        result = await manager.search_issues(query, fields=["number", "title"])
+        assert queries == [
+            {"q": [query], "per_page": ["100"], "page": ["1"]},
+            {"q": [query], "per_page": ["100"], "page": ["2"]},
+        ]
+        assert set(result) == {"items", "total_count", "truncated"}
+        assert (len(result["items"]), result["total_count"], result["truncated"]) == (130, 130, False)
+        assert result["items"][0] == {"number": 1, "title": "Issue 1"}
+
+        queries.clear()
+        result = await manager.search_issues(query, max_pages=1)
+        assert len(queries) == 1
+        assert (len(result["items"]), result["truncated"]) == (100, True)
+
+
+@pytest.mark.asyncio
+async def test_analyze_commits_amended_or_rebased(tmp_path):
+    """Commits rebased by someone other than their author are flagged."""
+    repo_dir = make_repo(tmp_path, "rebased_repo", branch="main",
+                         user="Student", email="student@example.com")
+    os.system(f"git -C {repo_dir} commit --allow-empty -m 'Base'")
+    os.system(f"git -C {repo_dir} checkout -b work")
+    (repo_dir / "work.py").write_text("x = 1\n")
+    os.system(f"git -C {repo_dir} add work.py && git -C {repo_dir} commit -m 'Student work'")
+    os.system(f"git -C {repo_dir} checkout main")
+    (repo_dir / "main.py").write_text("y = 1\n")
+    os.system(f"git -C {repo_dir} add main.py && git -C {repo_dir} commit -m 'Main work'")
+    # A TA rebases the student's branch: author is kept, committer changes
+    os.system(
+        f"git -C {repo_dir} -c user.name=TA -c user.email=ta@example.com rebase main work")
+
+    manager = gradelib.RepoManager([], "user", "token")
+    commits = await manager.analyze_commits(
+        str(repo_dir), fields=["message", "author_email", "amended_or_rebased"])
+    by_message = {c["message"]: c for c in commits}
+    assert by_message["Student work"]["author_email"] == "student@example.com"
+    assert by_message["Student work"]["amended_or_rebased"] is True
+    assert by_message["Main work"]["amended_or_rebased"] is False
+    assert by_message["Base"]["amended_or_rebased"] is False
+
+
+@pytest.mark.asyncio
+async def test_register_local_repo(tmp_path):
+    """An existing checkout can be analyzed by identifier without cloning it."""
+    repo_dir = make_repo(tmp_path, "checked_out_repo")
+    os.system(f"git -C {repo_dir} commit --allow-empty -m 'First'")
+    os.system(f"git -C {repo_dir} commit --allow-empty -m 'Second'")
+
+    identifier = "https://github.com/example/checked-out"
+    manager = gradelib.RepoManager([], "user", "token")
+    with pytest.raises(ValueError):
+        manager.register_local_repo(identifier, str(tmp_path / "not_a_repo"))
+
+    manager.register_local_repo(identifier, str(repo_dir))
+    tasks = await manager.fetch_clone_tasks()
+    assert tasks[identifier].status.status_type == "completed"
+    assert tasks[identifier].temp_dir == str(repo_dir)
+
+    commits = await manager.analyze_commits_bulk([identifier], fields=["message"])
+    assert [c["message"] for c in commits[identifier]] == ["Second", "First"]
+    branches = await manager.analyze_branches([identifier])
+    assert len(branches[identifier]) == 1
+
+
+def test_markdown_to_text():
+    """Issue and pull request bodies reduce to plain text."""
+    body = "\n".join([
+        "## Summary",
+        "",
+        "Fixes the **parser** for [the docs](https://example.com/docs) and `snake_case` names.",
+        "",
+        "```python",
+        "print('hello')",
+        "```",
+        "",
+        "- [x] Tests added",
+        "- See ![diagram](img.png)",
+        "",
+        "> Quoted _note_",
+        "<!-- template hint -->",
+    ])
+    assert gradelib.markdown_to_text(body) == "\n".join([
+        "Summary",
+        "",
+        "Fixes the parser for the docs and snake_case names.",
+        "",
+        "print('hello')",
+        "",
+        "Tests added",
+        "See diagram",
+        "",
+        "Quoted note",
+    ])
+
+
+@pytest.mark.asyncio
+async def test_fetch_issues_strip_markdown(set_github_token):
+    """strip_markdown keeps the original body under raw_body."""
+    manager = gradelib.RepoManager([], "user", set_github_token)
+    repo = "https://github.com/octocat/Hello-World"
+    try:
+        results = await manager.fetch_issues([repo], max_pages=1, strip_markdown=True)
+    except ValueError:
+        pytest.skip("GitHub API not reachable")
+    if isinstance(results[repo], str):
+        pytest.skip(results[repo])
+    for issue in results[repo]:
+        assert "raw_body" in issue
+        if issue["raw_body"] is not None:
+            assert issue["body"] == gradelib.markdown_to_text(issue["raw_body"])
+
+
+@pytest.mark.asyncio
+async def test_fetch_issue_events(tmp_path, monkeypatch):
+    """Timeline events keep their raw type and carry the label or assignee they refer to."""
+    def event(kind, created_at, **fields):
+        return dict({"id": 1, "event": kind, "actor": {"login": "ta", "id": 2},
+                     "created_at": created_at, "commit_id": None}, **fields)
+
+    timeline = [
+        event("labeled", "2024-01-01T00:00:00Z", label={"name": "bug", "color": "d73a4a"}),
+        event("assigned", "2024-01-02T00:00:00Z", assignee={"login": "student", "id": 1}),
+        # An event type gradelib has no special handling for, from a deleted account
+        event("connected", "2024-01-03T00:00:00Z", actor=None),
+        event("closed", "2024-01-04T00:00:00Z", commit_id="a" * 40),
+    ]
+    requested = []
+
+    def respond(path):
+        requested.append(path)
+        return 200, timeline
+
+    with mock_github_api(tmp_path, monkeypatch, respond) as proxy_url:
+        manager = gradelib.RepoManager([], "user", "token", proxy_url=proxy_url)
+        with pytest.raises(ValueError):
+            await manager.fetch_issue_events("not-a-repo-url", 1)
+        events = await manager.fetch_issue_events("https://github.com/octocat/Hello-World", 7)
+
+    assert requested == ["/repos/octocat/Hello-World/issues/7/events?per_page=100&page=1"]
+    assert events == [
+        {"event": "labeled", "actor_login": "ta", "created_at": "2024-01-01T00:00:00Z",
+         "label": "bug", "assignee": None},
+        {"event": "assigned", "actor_login": "ta", "created_at": "2024-01-02T00:00:00Z",
+         "label": None, "assignee": "student"},
+        {"event": "connected", "actor_login": None, "created_at": "2024-01-03T00:00:00Z",
+         "label": None, "assignee": None},
+        {"event": "closed", "actor_login": "ta", "created_at": "2024-01-04T00:00:00Z",
+         "label": None, "assignee": None},
+    ]
+
+
+@pytest.mark.asyncio
+async def test_fetch_issues_milestone(tmp_path, monkeypatch):
+    """Milestones carry their due date, with the title also kept as milestone_title."""
+    milestone = {"id": 9, "number": 3, "title": "Sprint 1", "state": "closed",
+                 "due_on": "2024-02-01T08:00:00Z", "closed_at": "2024-02-02T10:00:00Z",
+                 "description": "First sprint", "open_issues": 0, "closed_issues": 4}
+
+    def respond(path):
+        return 200, [mock_issue(1, milestone=milestone), mock_issue(2)]
+
+    repo_url = "https://github.com/octocat/Hello-World"
+    with mock_github_api(tmp_path, monkeypatch, respond) as proxy_url:
+        manager = gradelib.RepoManager([], "user", "token", proxy_url=proxy_url)
+        results = await manager.fetch_issues([repo_url], state="all")
+        with_milestone, without_milestone = results[repo_url]
+        assert with_milestone["milestone"] == {
+            "title": "Sprint 1", "number": 3, "state": "closed",
+            "due_on": "2024-02-01T08:00:00Z", "closed_at": "2024-02-02T10:00:00Z"}
+        assert with_milestone["milestone_title"] == "Sprint 1"
+        assert without_milestone["milestone"] is None
+        assert without_milestone["milestone_title"] is None
+
+        results = await manager.fetch_issues([repo_url], fields=["number", "milestone_title"])
+        assert results[repo_url] == [
+            {"number": 1, "milestone_title": "Sprint 1"}, {"number": 2, "milestone_title": None}]
+
+
+@pytest.mark.asyncio
+async def test_fetch_repo_report(tmp_path):
+    """A report has all five sections, API ones failing on their own for local repositories."""
+    repo_dir = make_repo(tmp_path, "report_repo", files={"a.txt": "a\n"},
+                         message="First", branch="main")
+
+    local_path = str(repo_dir)
+    manager = gradelib.RepoManager([local_path], "user", "token")
+    with pytest.raises(ValueError):
+        await manager.fetch_repo_report(local_path)
+
+    await manager.clone_all()
+    clone_path = (await manager.fetch_clone_tasks())[local_path].temp_dir
+    report = await manager.fetch_repo_report(local_path)
+    assert set(report) == {"commits", "branches", "collaborators", "issues", "pull_requests"}
+    assert [c["message"] for c in report["commits"]] == ["First"]
+    assert "main" in [b["name"] for b in report["branches"]]
+    # A local path has no GitHub API counterpart
+    for section in ("collaborators", "issues", "pull_requests"):
+        assert isinstance(report[section], str)
+    # The existing clone was reused
+    assert (await manager.fetch_clone_tasks())[local_path].temp_dir == clone_path
+
+
+@pytest.mark.asyncio
+async def test_stream_commits_to_file(tmp_path):
+    """Each commit becomes one JSON line with the same keys analyze_commits returns."""
+    repo_dir = make_repo(tmp_path, "stream_repo")
+    for n in range(25):
+        (repo_dir / "file.txt").write_text(f"line {n}\n")
+        os.system(f"git -C {repo_dir} add file.txt")
+        os.system(f"git -C {repo_dir} commit -m 'Commit {n}'")
+
+    url = str(repo_dir)
+    manager = gradelib.RepoManager([url], "user", "token")
+    await manager.clone_all()
+    output = tmp_path / "commits.jsonl"
+    count = await manager.stream_commits_to_file(url, str(output))
+    assert count == 25
+
+    lines = output.read_text().splitlines()
+    assert len(lines) == 25
+    streamed = [json.loads(line) for line in lines]
+    analyzed = await manager.analyze_commits(url)
+    assert [c["sha"] for c in streamed] == [c["sha"] for c in analyzed]
+    assert set(streamed[0]) == set(analyzed[0])
+    assert streamed[0]["message"] == "Commit 24"
+
+    with pytest.raises(ValueError):
+        await manager.stream_commits_to_file(str(tmp_path / "not_managed"), str(output))
+
+
+@pytest.mark.asyncio
+async def test_detect_force_pushes(tmp_path):
+    """A rewritten upstream branch shows up as a forced update in the clone's reflog."""
+    upstream = make_repo(tmp_path, "upstream")
+    for message in ("A", "B", "C"):
+        os.system(f"git -C {upstream} commit --allow-empty -m '{message}'")
+
+    clone = tmp_path / "clone"
+    shallow = tmp_path / "shallow"
+    os.system(f"git clone {upstream} {clone}")
+    os.system(f"git clone --depth 1 file://{upstream} {shallow}")
+
+    manager = gradelib.RepoManager([], "user", "token")
+    manager.register_local_repo("https://github.com/example/clean", str(clone))
+    assert await manager.detect_force_pushes("https://github.com/example/clean") == []
+
+    # Rewrite the last commit upstream, as a force-push would, then fetch it
+    old_sha = os.popen(f"git -C {upstream} rev-parse HEAD").read().strip()
+    os.system(f"git -C {upstream} reset --hard HEAD~1")
+    os.system(f"git -C {upstream} commit --allow-empty -m 'C rewritten'")
+    new_sha = os.popen(f"git -C {upstream} rev-parse HEAD").read().strip()
+    os.system(f"git -C {clone} fetch origin")
+
+    force_pushes = await manager.detect_force_pushes("https://github.com/example/clean")
+    assert len(force_pushes) == 1
+    assert force_pushes[0]["old_sha"] == old_sha
+    assert force_pushes[0]["new_sha"] == new_sha
+    assert force_pushes[0]["ref_name"].startswith("refs/remotes/origin/")
+    assert isinstance(force_pushes[0]["timestamp"], int)
+
+    manager.register_local_repo("https://github.com/example/shallow", str(shallow))
+    with pytest.raises(ValueError, match="shallow"):
+        await manager.detect_force_pushes("https://github.com/example/shallow")
+    with pytest.raises(ValueError):
+        await manager.detect_force_pushes("https://github.com/example/unknown")
+
+
+@pytest.mark.asyncio
+async def test_list_org_repos(tmp_path, monkeypatch):
+    """Repositories are paged, listed for users too and filtered by name prefix, ignoring case."""
+    def repo(owner, name):
+        return {"id": 1, "name": name, "full_name": f"{owner}/{name}", "private": True,
+                "clone_url": f"https://github.com/{owner}/{name}.git"}
+
+    course_repos = [repo("cse-course", f"assignment1-student{n}") for n in range(70)]
+    course_repos += [repo("cse-course", f"Assignment2-student{n}") for n in range(60)]
+    requested = []
+
+    def respond(path):
+        requested.append(path)
+        url = urlsplit(path)
+        page = int(parse_qs(url.query)["page"][0])
+        if url.path == "/orgs/cse-course/repos":
+            return 200, course_repos[(page - 1) * 100:page * 100]
+        if url.path == "/users/octocat/repos":
+            return 200, [repo("octocat", "Hello-World"), repo("octocat", "Spoon-Knife")]
+        if url.path == "/orgs/limited/repos":
+            return 403, {"message": "API rate limit exceeded"}, {
+                "X-RateLimit-Remaining": "0", "X-RateLimit-Reset": "1700000000"}
+        return 404, {"message": "Not Found"}
+
+    with mock_github_api(tmp_path, monkeypatch, respond) as proxy_url:
+        manager = gradelib.RepoManager([], "user", "token", proxy_url=proxy_url)
+        all_repos = await manager.list_org_repos("cse-course")
+        assert requested == [
+            "/orgs/cse-course/repos?type=all&per_page=100&page=1",
+            "/orgs/cse-course/repos?type=all&per_page=100&page=2",
+        ]
+        assert len(all_repos) == 130
+        assert all_repos[0] == {
+            "name": "assignment1-student0", "full_name": "cse-course/assignment1-student0",
+            "clone_url": "https://github.com/cse-course/assignment1-student0.git",
+            "private": True}
+
+        first = await manager.list_org_repos("cse-course", name_prefix="assignment1-")
+        assert [r["name"] for r in first] == [f"assignment1-student{n}" for n in range(70)]
+        second = await manager.list_org_repos("cse-course", name_prefix="ASSIGNMENT2-")
+        assert len(second) == 60
+
+        # Not an organization, so the user's repositories are listed instead
+        requested.clear()
+        user_repos = await manager.list_org_repos("octocat", name_prefix="hello")
+        assert [r["full_name"] for r in user_repos] == ["octocat/Hello-World"]
+        assert requested == [
+            "/orgs/octocat/repos?type=all&per_page=100&page=1",
+            "/users/octocat/repos?type=owner&per_page=100&page=1",
+        ]
+
+        with pytest.raises(ValueError, match="No organization or user named 'nobody'"):
+            await manager.list_org_repos("nobody")
+        with pytest.raises(ValueError, match="rate limit exceeded .*1700000000"):
+            await manager.list_org_repos("limited")
+
+
+@pytest.mark.asyncio
+async def test_analyze_commits_local_iso(tmp_path):
+    """Local ISO times apply each signature's own timezone offset."""
+    repo_dir = make_repo(tmp_path, "local_time_repo")
+    os.system(
+        f"GIT_AUTHOR_DATE='2024-02-29T23:30:00-05:30' GIT_COMMITTER_DATE='2024-03-01T05:00:00Z' "
+        f"git -C {repo_dir} commit --allow-empty -m 'Leap day'")
+
+    manager = gradelib.RepoManager([], "user", "token")
+    commits = await manager.analyze_commits(
+        str(repo_dir), fields=["author_timestamp", "author_offset", "author_local_iso",
+                               "committer_local_iso"])
+    assert commits[0]["author_offset"] == -330
+    assert commits[0]["author_local_iso"] == "2024-02-29T23:30:00-05:30"
+    assert commits[0]["committer_local_iso"] == "2024-03-01T05:00:00+00:00"
+
+
+@pytest.mark.asyncio
+async def test_analyze_commits_trailers(tmp_path):
+    """Trailers are parsed only on request, collecting repeated keys."""
+    repo_dir = make_repo(tmp_path, "trailer_repo")
+    message = tmp_path / "message.txt"
+    message.write_text(
+        "Pair programming session\n\n"
+        "Implemented the parser together.\n\n"
+        "Co-authored-by: Alice <alice@example.com>\n"
+        "Co-Authored-By: Bob <bob@example.com>\n"
+        "Signed-off-by: Test User <test@example.com>\n")
+    os.system(f"git -C {repo_dir} commit --allow-empty -F {message}")
+
+    manager = gradelib.RepoManager([], "user", "token")
+    commits = await manager.analyze_commits(str(repo_dir), parse_trailers=True)
+    trailers = commits[0]["trailers"]
+    assert trailers["co-authored-by"] == [
+        "Alice <alice@example.com>", "Bob <bob@example.com>"]
+    assert trailers["signed-off-by"] == ["Test User <test@example.com>"]
+    assert commits[0]["message"].startswith("Pair programming session")
+
+    commits = await manager.analyze_commits(str(repo_dir))
+    assert commits[0]["trailers"] == {}
+
+
+@pytest.mark.asyncio
+async def test_commit_activity(tmp_path):
+    """Commits on two days give two day bins; offsets decide which day a commit falls on."""
+    repo_dir = make_repo(tmp_path, "activity_repo")
+    dates = ["2024-01-01T10:00:00+00:00", "2024-01-01T12:00:00+00:00",
+             # 04:30 UTC on January 3rd, but still the evening of the 2nd for the author
+             "2024-01-02T23:30:00-05:00"]
+    for i, date in enumerate(dates):
+        (repo_dir / f"f{i}.py").write_text("x = 1\ny = 2\n")
+        os.system(f"git -C {repo_dir} add f{i}.py && GIT_AUTHOR_DATE='{date}' "
+                  f"git -C {repo_dir} commit -q -m 'Commit {i}'")
+    local_path = str(repo_dir)
+    manager = gradelib.RepoManager([local_path], "user", "token")
+    await manager.clone_all()
+
+    days = await manager.commit_activity(local_path)
+    assert days == [("2024-01-01", 2, 4, 0), ("2024-01-02", 1, 2, 0)]
+    utc_days = await manager.commit_activity(local_path, utc_offset_minutes=0)
+    assert [(start, count) for start, count, _, _ in utc_days] == [
+        ("2024-01-01", 2), ("2024-01-03", 1)]
+    # 2024-01-01 was a Monday
+    assert await manager.commit_activity(local_path, bucket="week") == [("2024-01-01", 3, 6, 0)]
+    assert await manager.commit_activity(local_path, bucket="month") == [("2024-01-01", 3, 6, 0)]
+
+    with pytest.raises(ValueError):
+        await manager.commit_activity(local_path, bucket="hour")
+
+
+@pytest.mark.asyncio
+async def test_coauthorship_graph(tmp_path):
+    """Co-authored commits become weighted edges; aliases merge through the identity map."""
+    repo_dir = make_repo(tmp_path, "coauthor_repo", user="Alice", email="alice@example.com")
+    local_path = str(repo_dir)
+
+    manager = gradelib.RepoManager([local_path], "user", "token")
+    await manager.clone_all()
+    graph = await manager.coauthorship_graph(local_path)
+    assert graph == {"nodes": {}, "edges": []}
+
+    messages = [
+        "Solo work\n",
+        "Pair work\n\nCo-authored-by: Bob <bob@example.com>\n",
+        "Mob work\n\nCo-authored-by: Bob <BOB@example.com>\n"
+        "Co-authored-by: Carol <carol@gmail.com>\n",
+    ]
+    for i, text in enumerate(messages):
+        message = tmp_path / f"message{i}.txt"
+        message.write_text(text)
+        os.system(f"git -C {repo_dir} commit --allow-empty -F {message}")
+
+    manager = gradelib.RepoManager([local_path], "user", "token")
+    await manager.clone_all()
+    graph = await manager.coauthorship_graph(local_path)
+    assert graph["edges"] == [
+        {"source": "alice@example.com", "target": "bob@example.com", "weight": 2},
+        {"source": "alice@example.com", "target": "carol@gmail.com", "weight": 1},
+    ]
+    assert graph["nodes"] == {
+        "alice@example.com": 3, "bob@example.com": 2, "carol@gmail.com": 1}
+
+    graph = await manager.coauthorship_graph(
+        local_path, identity_map={"carol@gmail.com": "carol@university.edu"})
+    assert {(e["source"], e["target"]) for e in graph["edges"]} == {
+        ("alice@example.com", "bob@example.com"),
+        ("alice@example.com", "carol@university.edu"),
+    }
+    assert graph["nodes"]["carol@university.edu"] == 1
+
+
+@pytest.mark.asyncio
+async def test_fetch_pull_requests_without_details(tmp_path, monkeypatch):
+    """With fetch_details=False, only the list endpoint is requested."""
+    requested = []
+
+    def respond(path):
+        requested.append(path.split("?")[0])
+        if path.startswith("/repos/octocat/Hello-World/pulls?"):
+            return 200, [mock_pull_request(1), dict(mock_pull_request(2), merged_at=None)]
+        return 200, {
+            "mergeable": True, "merged": True, "merged_by": {"login": "ta", "id": 2},
+            "comments": 1, "commits": 2, "additions": 3, "deletions": 4, "changed_files": 5}
+
+    repo_url = "https://github.com/octocat/Hello-World"
+    with mock_github_api(tmp_path, monkeypatch, respond) as proxy_url:
+        manager = gradelib.RepoManager([], "user", "token", proxy_url=proxy_url)
+        results = await manager.fetch_pull_requests(
+            [repo_url], state="closed", fetch_details=False)
+        assert requested == ["/repos/octocat/Hello-World/pulls"]
+        for pr in results[repo_url]:
+            assert pr["title"] is not None
+            assert (pr["comments"], pr["commits"], pr["additions"], pr["deletions"]) == (0, 0, 0, 0)
+            assert pr["changed_files"] == 0
+            assert pr["mergeable"] is None and pr["merged_by"] is None
+        assert [pr["merged"] for pr in results[repo_url]] == [True, False]
+
+        # With details, each pull request costs one more request
+        requested.clear()
+        results = await manager.fetch_pull_requests([repo_url], state="closed")
+        assert sorted(requested) == [
+            "/repos/octocat/Hello-World/pulls",
+            "/repos/octocat/Hello-World/pulls/1",
+            "/repos/octocat/Hello-World/pulls/2",
+        ]
+        assert [pr["additions"] for pr in results[repo_url]] == [3, 3]
+
+
+@pytest.mark.asyncio
+async def test_fetch_pull_requests_base_and_head(tmp_path, monkeypatch):
+    """Pull requests name their base and head branches; ones from forks are flagged."""
+    base = {"ref": "main", "sha": "a" * 40, "repo": {"full_name": "octocat/Hello-World"}}
+
+    def head(ref, full_name):
+        return {"ref": ref, "sha": "b" * 40, "repo": full_name and {"full_name": full_name}}
+
+    pulls = [
+        mock_pull_request(1, base=base, head=head("feature", "octocat/Hello-World")),
+        # Opened from a student's fork
+        mock_pull_request(2, base=base, head=head("main", "student/Hello-World")),
+        # The fork has since been deleted
+        mock_pull_request(3, base=base, head=head("fix", None)),
+        # Same repository, differently cased
+        mock_pull_request(4, base=base, head=head("docs", "OctoCat/hello-world")),
+    ]
+
+    def respond(path):
+        if path == "/repos/octocat/Hello-World/pulls/2":
+            return 200, dict(pulls[1], mergeable=None, merged=False, merged_by=None,
+                             comments=0, commits=1, additions=1, deletions=0, changed_files=1)
+        return 200, pulls
+
+    repo_url = "https://github.com/octocat/Hello-World"
+    with mock_github_api(tmp_path, monkeypatch, respond) as proxy_url:
+        manager = gradelib.RepoManager([], "user", "token", proxy_url=proxy_url)
+        results = await manager.fetch_pull_requests([repo_url], state="all", fetch_details=False)
+        same_repo, fork, deleted_fork, recased = results[repo_url]
+        assert (same_repo["base_ref"], same_repo["base_sha"]) == ("main", "a" * 40)
+        assert (same_repo["head_ref"], same_repo["head_sha"]) == ("feature", "b" * 40)
+        assert same_repo["head_repo_full_name"] == "octocat/Hello-World"
+        assert not same_repo["is_cross_fork"]
+        assert (fork["head_ref"], fork["head_repo_full_name"]) == ("main", "student/Hello-World")
+        assert fork["is_cross_fork"]
+        assert deleted_fork["head_repo_full_name"] is None and deleted_fork["is_cross_fork"]
+        assert not recased["is_cross_fork"]
+
+        single = await manager.fetch_pull_request(
+            repo_url, 2, fields=["head_repo_full_name", "is_cross_fork"])
+        assert single == {"head_repo_full_name": "student/Hello-World", "is_cross_fork": True}
+
+
+@pytest.mark.asyncio
+async def test_author_association(tmp_path, monkeypatch):
+    """Issues and pull requests carry the author association, e.g. OWNER or CONTRIBUTOR."""
+    def respond(path):
+        if path.startswith("/repos/octocat/Hello-World/issues?"):
+            # Older API responses can leave the association out
+            return 200, [mock_issue(1, author_association="MEMBER"), mock_issue(2)]
+        return 200, [mock_pull_request(3, author_association="MEMBER"),
+                     mock_pull_request(4, author_association="FIRST_TIME_CONTRIBUTOR")]
+
+    repo_url = "https://github.com/octocat/Hello-World"
+    with mock_github_api(tmp_path, monkeypatch, respond) as proxy_url:
+        manager = gradelib.RepoManager([], "user", "token", proxy_url=proxy_url)
+        issues = (await manager.fetch_issues([repo_url], fields=["author_association"]))[repo_url]
+        prs = (await manager.fetch_pull_requests(
+            [repo_url], fields=["author_association"], fetch_details=False))[repo_url]
+    assert issues == [{"author_association": "MEMBER"}, {"author_association": "NONE"}]
+    assert prs == [{"author_association": "MEMBER"},
+                   {"author_association": "FIRST_TIME_CONTRIBUTOR"}]
+
+
+@pytest.mark.asyncio
+async def test_fetch_pull_requests_list_level_fields(tmp_path, monkeypatch):
+    """Selecting only list-level fields gives the same values without detail requests."""
+    import re
+
+    requested = []
+
+    def respond(path):
+        requested.append(path)
+        if re.fullmatch(r"/repos/octocat/Hello-World/pulls\?.*", path):
+            return 200, [mock_pull_request(n) for n in range(1, 6)]
+        return 200, {
+            "mergeable": None, "merged": True, "merged_by": {"login": "ta", "id": 2},
+            "comments": 1, "commits": 2, "additions": 3, "deletions": 4, "changed_files": 5}
+
+    repo_url = "https://github.com/octocat/Hello-World"
+    fields = ["number", "merged", "merged_at"]
+    with mock_github_api(tmp_path, monkeypatch, respond) as proxy_url:
+        manager = gradelib.RepoManager([], "user", "token", proxy_url=proxy_url)
+        projected = await manager.fetch_pull_requests(
+            [repo_url], state="closed", max_pages=1, fields=fields)
+        assert not [path for path in requested if re.search(r"/pulls/\d+", path)]
+        assert [pr["number"] for pr in projected[repo_url]] == [1, 2, 3, 4, 5]
+        for pr in projected[repo_url]:
+            assert set(pr) == set(fields)
+            assert pr["merged"] == (pr["merged_at"] is not None)
+
+        # Asking for a detail-only field brings the detail requests back
+        requested.clear()
+        detailed = await manager.fetch_pull_requests(
+            [repo_url], state="closed", max_pages=1, fields=["number", "additions"])
+        assert len([path for path in requested if re.search(r"/pulls/\d+", path)]) == 5
+        assert all(pr["additions"] == 3 for pr in detailed[repo_url])
+
+
+@pytest.mark.asyncio
+async def test_fetch_pull_requests_detail_concurrency(tmp_path, monkeypatch):
+    """Detail requests run under their own cap without changing the results or their order."""
+    import re
+    import threading
+    import time
+
+    lock = threading.Lock()
+    in_flight, peak = 0, 0
+
+    def respond(path):
+        nonlocal in_flight, peak
+        if not re.fullmatch(r"/repos/octocat/Hello-World/pulls/\d+", path):
+            return 200, [mock_pull_request(n) for n in range(1, 13)]
+        with lock:
+            in_flight += 1
+            peak = max(peak, in_flight)
+        time.sleep(0.2)
+        with lock:
+            in_flight -= 1
+        number = int(path.rsplit("/", 1)[1])
+        return 200, {
+            "mergeable": True, "merged": True, "merged_by": None, "comments": 0,
+            "commits": number, "additions": 0, "deletions": 0, "changed_files": 0}
+
+    manager = gradelib.RepoManager([], "user", "token")
+    repo_url = "https://github.com/octocat/Hello-World"
+    with pytest.raises(ValueError):
+        await manager.fetch_pull_requests([repo_url], max_concurrent_details=0)
+
+    with mock_github_api(tmp_path, monkeypatch, respond) as proxy_url:
+        manager = gradelib.RepoManager([], "user", "token", proxy_url=proxy_url)
+        serial = await manager.fetch_pull_requests(
+            [repo_url], state="closed", max_pages=1, max_concurrent_details=1)
+        assert peak == 1
+        peak = 0
+        capped = await manager.fetch_pull_requests(
+            [repo_url], state="closed", max_pages=1, max_concurrent_details=3)
+        assert 1 < peak <= 3
+
+    expected = [(n, n) for n in range(1, 13)]
+    assert [(pr["number"], pr["commits"]) for pr in serial[repo_url]] == expected
+    assert [(pr["number"], pr["commits"]) for pr in capped[repo_url]] == expected
+
+
+@pytest.mark.asyncio
+async def test_clone_retries(tmp_path):
+    """A dropped connection is retried until the clone succeeds; auth failures are not retried."""
+    import http.server
+    import shutil
+    import socket
+    import subprocess
+    import threading
+
+    if shutil.which("git") is None or subprocess.run(
+            ["git", "daemon", "-h"], capture_output=True).returncode not in (0, 129):
+        pytest.skip("git daemon not available")
+
+    source = make_repo(tmp_path, "source")
+    os.system(f"git -C {source} commit --allow-empty -m 'Initial'")
+    os.system(f"git clone --bare {source} {tmp_path / 'served.git'}")
+
+    def free_port():
+        with socket.socket() as s:
+            s.bind(("127.0.0.1", 0))
+            return s.getsockname()[1]
+
+    daemon_port = free_port()
+    daemon = subprocess.Popen(
+        ["git", "daemon", "--export-all", f"--base-path={tmp_path}", "--listen=127.0.0.1",
+         f"--port={daemon_port}", str(tmp_path)])
+    try:
+        # Front the daemon with a listener that drops the first connection
+        front = socket.socket()
+        front.bind(("127.0.0.1", 0))
+        front.listen()
+        connections = []
+
+        def forward(src, dst):
+            try:
+                while data := src.recv(65536):
+                    dst.sendall(data)
+                dst.shutdown(socket.SHUT_WR)
+            except OSError:
+                pass  # The other side hung up first
+
+        def serve():
+            while True:
+                try:
+                    client, _ = front.accept()
+                except OSError:
+                    return
+                connections.append(client)
+                if len(connections) == 1:
+                    client.close()
+                    continue
+                upstream = socket.create_connection(("127.0.0.1", daemon_port))
+                threading.Thread(target=forward, args=(client, upstream), daemon=True).start()
+                threading.Thread(target=forward, args=(upstream, client), daemon=True).start()
+
+        for _ in range(50):
+            try:
+                socket.create_connection(("127.0.0.1", daemon_port)).close()
+                break
+            except OSError:
+                await asyncio.sleep(0.1)
+        threading.Thread(target=serve, daemon=True).start()
+
+        flaky_url = f"git://127.0.0.1:{front.getsockname()[1]}/served.git"
+        manager = gradelib.RepoManager([flaky_url], "", "", clone_timeout=30)
+        await manager.clone_all()
+        task = (await manager.fetch_clone_tasks())[flaky_url]
+        assert task.status.status_type == "completed", task.status.error
+        assert task.attempts == 2
+
+        no_retry = gradelib.RepoManager([flaky_url], "", "", clone_retries=0)
+        connections.clear()
+        await no_retry.clone_all()
+        task = (await no_retry.fetch_clone_tasks())[flaky_url]
+        assert task.status.status_type == "failed"
+        assert task.attempts == 1
+        front.close()
+    finally:
+        daemon.terminate()
+        daemon.wait()
+
+    class Unauthorized(http.server.BaseHTTPRequestHandler):
+        def do_GET(self):
+            self.send_response(401)
+            self.send_header("WWW-Authenticate", 'Basic realm="test"')
+            self.send_header("Content-Length", "0")
+            self.end_headers()
+
+        def log_message(self, *args):
+            pass
+
+    server = http.server.ThreadingHTTPServer(("127.0.0.1", 0), Unauthorized)
+    threading.Thread(target=server.serve_forever, daemon=True).start()
+    try:
+        auth_url = f"http://127.0.0.1:{server.server_address[1]}/owner/repo.git"
+        manager = gradelib.RepoManager([auth_url], "user", "bad-token", clone_timeout=30)
+        await manager.clone_all()
+        task = (await manager.fetch_clone_tasks())[auth_url]
+        assert task.status.status_type == "failed"
+        assert task.attempts == 1
+    finally:
+        server.shutdown()
+
+
+@pytest.mark.asyncio
+async def test_blame_authorship(tmp_path):
+    """Surviving lines are counted per author, with aliases merged and failures reported."""
+    repo_dir = make_repo(tmp_path, "authorship_repo")
+
+    def commit_as(name, email, message):
+        os.system(f"git -C {repo_dir} add -A")
+        os.system(
+            f"git -C {repo_dir} -c user.name='{name}' -c user.email='{email}' "
+            f"commit -m '{message}'")
+
+    (repo_dir / "main.py").write_text("a = 1\nb = 2\nc = 3\n")
+    commit_as("Alice", "alice@example.edu", "First lines")
+    (repo_dir / "main.py").write_text("a = 1\nb = 2\nc = 3\nd = 4\n")
+    commit_as("Bob", "bob@example.edu", "Fourth line")
+    (repo_dir / "util.py").write_text("x = 1\n")
+    commit_as("Alice", "Alice@Personal.example", "Alice from another address")
+
+    url = "https://github.com/example/authorship"
+    manager = gradelib.RepoManager([], "user", "token")
+    manager.register_local_repo(url, str(repo_dir))
+    result = await manager.blame_authorship(
+        url, ["main.py", "util.py", "missing.py"],
+        identity_map={"alice@personal.example": "alice@example.edu"})
+
+    assert result["files"]["main.py"]["lines"] == {"alice@example.edu": 3, "bob@example.edu": 1}
+    assert result["files"]["main.py"]["total_lines"] == 4
+    assert result["total"]["lines"] == {"alice@example.edu": 4, "bob@example.edu": 1}
+    assert result["total"]["percentages"] == {"alice@example.edu": 80.0, "bob@example.edu": 20.0}
+    for authorship in [result["total"], *result["files"].values()]:
+        assert abs(sum(authorship["percentages"].values()) - 100.0) < 1e-9
+    assert set(result["errors"]) == {"missing.py"}
+
+    with pytest.raises(ValueError):
+        await manager.blame_authorship("https://github.com/example/unknown", ["main.py"])
+
+
+@pytest.mark.asyncio
+async def test_token_type(tmp_path, monkeypatch):
+    """OAuth tokens are accepted alongside personal access tokens; other kinds are rejected."""
+    with pytest.raises(ValueError, match="token_type"):
+        gradelib.RepoManager([], "user", "token", token_type="basic")
+    gradelib.RepoManager([], "user", "token", token_type="PAT")
+
+    requests = []
+    with mock_github_api(tmp_path, monkeypatch, lambda path: (401, {}), requests) as proxy_url:
+        pat = gradelib.RepoManager([], "user", "ghp_pat", proxy_url=proxy_url)
+        assert await pat.get_rate_limit_status() is None
+        oauth = gradelib.RepoManager(
+            [], "user", "gho_oauth", token_type="oauth", proxy_url=proxy_url)
+        assert await oauth.get_rate_limit_status() is None
+        monkeypatch.setenv("GITHUB_TOKEN", "gho_from_env")
+        from_env = gradelib.RepoManager.from_env([], token_type="oauth", proxy_url=proxy_url)
+        assert await from_env.get_rate_limit_status() is None
+    assert [r.headers["Authorization"] for r in requests] == [
+        "token ghp_pat", "Bearer gho_oauth", "Bearer gho_from_env"]
+
+
+@pytest.mark.asyncio
+async def test_malformed_token_fails_each_url(monkeypatch):
+    """A token that can't be sent as a header is reported per URL rather than crashing."""
+    monkeypatch.setenv("GITHUB_TOKEN", "ghp_bad\ntoken")
+    url = "https://github.com/bmeddeb/gradelib"
+    manager = gradelib.RepoManager.from_env([url])
+    for results in (await manager.fetch_issues([url]), await manager.fetch_collaborators([url])):
+        assert "not a valid HTTP header value" in results[url]
@@ -0,0 +1,3 @@
+// Helpers shared across providers
+pub(crate) mod text;
+pub(crate) mod time;
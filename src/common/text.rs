@@ -0,0 +1,71 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    // Opening or closing fence of a fenced code block, e.g. ``` or ~~~rust
+    static ref RE_FENCE: Regex = Regex::new(r"^\s*(```|~~~)").unwrap();
+    // HTML comments and tags, e.g. <!-- template hint --> or <br/>
+    static ref RE_HTML: Regex = Regex::new(r"(?s)<!--.*?-->|</?[A-Za-z][^>]*>").unwrap();
+    // Link reference definitions: [ref]: https://example.com
+    static ref RE_LINK_DEF: Regex = Regex::new(r"^\s*\[[^\]]+\]:\s*\S+.*$").unwrap();
+    // Images and links keep their text: ![alt](src), [text](url), [text][ref]
+    static ref RE_IMAGE: Regex = Regex::new(r"!\[([^\]]*)\](\([^)]*\)|\[[^\]]*\])").unwrap();
+    static ref RE_LINK: Regex = Regex::new(r"\[([^\]]*)\](\([^)]*\)|\[[^\]]*\])").unwrap();
+    // Autolinks keep the URL: <https://example.com>
+    static ref RE_AUTOLINK: Regex = Regex::new(r"<((?:https?|mailto):[^>\s]+)>").unwrap();
+    static ref RE_HEADING: Regex = Regex::new(r"^\s{0,3}#{1,6}\s+(.*?)(\s+#+)?\s*$").unwrap();
+    static ref RE_RULE: Regex = Regex::new(r"^\s{0,3}([-*_])(\s*[-*_]){2,}\s*$").unwrap();
+    static ref RE_BLOCKQUOTE: Regex = Regex::new(r"^\s{0,3}(>\s?)+").unwrap();
+    static ref RE_LIST_ITEM: Regex =
+        Regex::new(r"^(\s*)([-*+]|\d+[.)])\s+(\[[ xX]\]\s+)?").unwrap();
+    static ref RE_INLINE_CODE: Regex = Regex::new(r"`+([^`]+)`+").unwrap();
+    // Emphasis markers; underscores only count at word boundaries so snake_case survives
+    static ref RE_STRONG: Regex = Regex::new(r"(\*\*|__)(\S(?:.*?\S)?)(\*\*|__)").unwrap();
+    static ref RE_STAR_EM: Regex = Regex::new(r"\*(\S(?:[^*]*?\S)?)\*").unwrap();
+    static ref RE_UNDERSCORE_EM: Regex = Regex::new(r"(^|\W)_(\S(?:[^_]*?\S)?)_(\W|$)").unwrap();
+    static ref RE_STRIKE: Regex = Regex::new(r"~~(.+?)~~").unwrap();
+    static ref RE_BLANK_LINES: Regex = Regex::new(r"\n{3,}").unwrap();
+}
+
+/// Reduces Markdown to plain text for text analysis.
+///
+/// This is a lightweight line-based pass, not a full CommonMark parser. It drops code fence
+/// lines (keeping the code), heading, quote and list markers, emphasis, HTML tags and
+/// comments, and replaces links and images with their text.
+pub fn markdown_to_text(markdown: &str) -> String {
+    let without_html = RE_HTML.replace_all(markdown, "");
+    let mut lines = Vec::new();
+    let mut in_code_block = false;
+    for line in without_html.lines() {
+        if RE_FENCE.is_match(line) {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            lines.push(line.to_string());
+            continue;
+        }
+        if RE_LINK_DEF.is_match(line) || RE_RULE.is_match(line) {
+            continue;
+        }
+        let line = RE_HEADING.replace(line, "$1");
+        let line = RE_BLOCKQUOTE.replace(&line, "");
+        let line = RE_LIST_ITEM.replace(&line, "$1");
+        lines.push(strip_inline(&line));
+    }
+    let text = lines.join("\n");
+    RE_BLANK_LINES.replace_all(text.trim(), "\n\n").into_owned()
+}
+
+/// Strips inline Markdown: code spans, images, links and emphasis.
+fn strip_inline(line: &str) -> String {
+    let line = RE_INLINE_CODE.replace_all(line, "$1");
+    let line = RE_IMAGE.replace_all(&line, "$1");
+    let line = RE_LINK.replace_all(&line, "$1");
+    let line = RE_AUTOLINK.replace_all(&line, "$1");
+    let line = RE_STRONG.replace_all(&line, "$2");
+    let line = RE_STAR_EM.replace_all(&line, "$1");
+    let line = RE_UNDERSCORE_EM.replace_all(&line, "$1$2$3");
+    let line = RE_STRIKE.replace_all(&line, "$1");
+    line.trim_end().to_string()
+}
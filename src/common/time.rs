@@ -0,0 +1,50 @@
+/// Formats a Unix timestamp as ISO 8601 local time for a timezone offset in minutes,
+/// e.g. `2024-01-31T14:00:00+02:00` for 12:00 UTC at +120.
+pub fn local_iso8601(timestamp: i64, offset_minutes: i32) -> String {
+    let local = timestamp + i64::from(offset_minutes) * 60;
+    let days = local.div_euclid(86_400);
+    let secs_of_day = local.rem_euclid(86_400);
+    let (year, month, day) = civil_from_days(days);
+
+    let sign = if offset_minutes < 0 { '-' } else { '+' };
+    let offset = offset_minutes.unsigned_abs();
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}{}{:02}:{:02}",
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        secs_of_day % 3600 / 60,
+        secs_of_day % 60,
+        sign,
+        offset / 60,
+        offset % 60
+    )
+}
+
+/// Days since 1970-01-01 of the local date of a Unix timestamp at an offset in minutes.
+pub fn local_days(timestamp: i64, offset_minutes: i32) -> i64 {
+    (timestamp + i64::from(offset_minutes) * 60).div_euclid(86_400)
+}
+
+/// Formats days since 1970-01-01 as an ISO 8601 date, e.g. `2024-01-31`.
+pub fn iso_date(days: i64) -> String {
+    let (year, month, day) = civil_from_days(days);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Converts days since 1970-01-01 to a proleptic Gregorian (year, month, day).
+/// See Howard Hinnant's `civil_from_days`.
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
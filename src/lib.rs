@@ -6,15 +6,20 @@ use pyo3::types::{PyDict, PyList};
 // Use pyo3-async-runtimes
 use pyo3_async_runtimes::tokio;
 
-use std::collections::HashMap;
+use ::tokio::sync::broadcast;
+use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
 use std::path::PathBuf;
 use std::sync::Arc; // Needed for calling method via Arc
+use std::time::Duration;
 
 // --- Declare modules ---
+pub(crate) mod common;
 pub(crate) mod providers;
 
 // Re-export GitHub provider modules
+pub(crate) use providers::github::access;
+pub(crate) use providers::github::api_client;
 pub(crate) use providers::github::blame;
 pub(crate) use providers::github::branch;
 pub(crate) use providers::github::clone;
@@ -22,10 +27,15 @@ pub(crate) use providers::github::code_review;
 pub(crate) use providers::github::collaborators;
 pub(crate) use providers::github::comments;
 pub(crate) use providers::github::commits;
+pub(crate) use providers::github::files;
 pub(crate) use providers::github::issues;
 pub(crate) use providers::github::oauth::GitHubOAuthClient;
+pub(crate) use providers::github::org_repos;
 pub(crate) use providers::github::pull_requests;
+pub(crate) use providers::github::rate_limit;
+pub(crate) use providers::github::reflog;
 pub(crate) use providers::github::repo;
+pub(crate) use providers::github::summary;
 
 // Re-export Taiga provider modules
 pub(crate) use providers::taiga::client;
@@ -33,7 +43,14 @@ pub(crate) use providers::taiga::orchestrator;
 
 // --- Import necessary items from modules ---
 // Import directly from source modules
-use crate::clone::{InternalCloneStatus, InternalRepoCloneTask};
+use crate::api_client::ApiClientConfig;
+use crate::clone::{CloneOptions, CloneProgressEvent, InternalCloneStatus, InternalRepoCloneTask};
+use crate::code_review::ReviewInfo;
+use crate::comments::CommentInfo;
+use crate::commits::{CommitAnalysisOptions, CommitInfo};
+use crate::common::text;
+use crate::issues::IssueInfo;
+use crate::pull_requests::PullRequestInfo;
 use repo::InternalRepoManagerLogic;
 // --- Exposed Python Class: CloneStatus ---
 #[pyclass(name = "CloneStatus", module = "gradelib")] // Add module for clarity
@@ -85,6 +102,8 @@ pub struct ExposedCloneTask {
     pub status: ExposedCloneStatus, // Uses the exposed status type
     #[pyo3(get)]
     pub temp_dir: Option<String>,
+    #[pyo3(get)]
+    pub attempts: u32,
 }
 
 // Conversion from internal Rust struct to exposed Python class
@@ -94,7 +113,455 @@ impl From<InternalRepoCloneTask> for ExposedCloneTask {
             url: task.url,
             status: task.status.into(), // Convert internal status via its From impl
             temp_dir: task.temp_dir.map(|p| p.to_string_lossy().to_string()),
+            attempts: task.attempts,
+        }
+    }
+}
+
+// --- Output field selection ---
+
+/// Keys emitted for each commit returned by `analyze_commits`.
+const COMMIT_FIELDS: &[&str] = &[
+    "sha",
+    "repo_name",
+    "message",
+    "author_name",
+    "author_email",
+    "author_timestamp",
+    "author_offset",
+    "author_local_iso",
+    "committer_name",
+    "committer_email",
+    "committer_timestamp",
+    "committer_offset",
+    "committer_local_iso",
+    "additions",
+    "deletions",
+    "is_merge",
+    "amended_or_rebased",
+    "parents",
+    "files",
+    "trailers",
+    "signature_status",
+];
+
+/// Keys emitted for each issue returned by `fetch_issues`.
+const ISSUE_FIELDS: &[&str] = &[
+    "id",
+    "number",
+    "title",
+    "state",
+    "created_at",
+    "updated_at",
+    "closed_at",
+    "user_login",
+    "user_id",
+    "body",
+    "comments_count",
+    "is_pull_request",
+    "labels",
+    "assignees",
+    "milestone",
+    "milestone_title",
+    "locked",
+    "html_url",
+    "author_association",
+];
+
+/// Keys emitted for each pull request returned by `fetch_pull_requests`.
+const PULL_REQUEST_FIELDS: &[&str] = &[
+    "id",
+    "number",
+    "title",
+    "state",
+    "created_at",
+    "updated_at",
+    "closed_at",
+    "merged_at",
+    "user_login",
+    "user_id",
+    "body",
+    "comments",
+    "commits",
+    "additions",
+    "deletions",
+    "changed_files",
+    "mergeable",
+    "labels",
+    "is_draft",
+    "merged",
+    "merged_by",
+    "base_ref",
+    "base_sha",
+    "head_ref",
+    "head_sha",
+    "head_repo_full_name",
+    "is_cross_fork",
+    "author_association",
+];
+
+/// Pull request keys only the per-PR detail request fills in (see `fetch_details`).
+/// "merged" is not among them, since the list's merged_at gives the same answer.
+const PULL_REQUEST_DETAIL_FIELDS: &[&str] = &[
+    "comments",
+    "commits",
+    "additions",
+    "deletions",
+    "changed_files",
+    "mergeable",
+    "merged_by",
+];
+
+/// Optional subset of keys to emit when building result dicts.
+/// Skipping unwanted keys avoids converting values that would be thrown away in Python.
+struct FieldSelection(Option<HashSet<String>>);
+
+impl FieldSelection {
+    /// Validates the requested fields against the known keys of a result type.
+    fn new(fields: Option<Vec<String>>, known: &[&str]) -> PyResult<Self> {
+        let Some(fields) = fields else {
+            return Ok(Self(None));
+        };
+        let unknown: Vec<&str> = fields
+            .iter()
+            .map(|f| f.as_str())
+            .filter(|f| !known.contains(f))
+            .collect();
+        if !unknown.is_empty() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Unknown field(s): {}. Valid fields are: {}",
+                unknown.join(", "),
+                known.join(", ")
+            )));
         }
+        Ok(Self(Some(fields.into_iter().collect())))
+    }
+
+    fn includes(&self, key: &str) -> bool {
+        self.0.as_ref().is_none_or(|set| set.contains(key))
+    }
+
+    fn includes_any(&self, keys: &[&str]) -> bool {
+        keys.iter().any(|key| self.includes(key))
+    }
+
+    /// Sets `key` on the dict only if it was selected.
+    fn set_item<'py, V>(&self, dict: &Bound<'py, PyDict>, key: &str, value: V) -> PyResult<()>
+    where
+        V: IntoPyObject<'py>,
+    {
+        if self.includes(key) {
+            dict.set_item(key, value)?;
+        }
+        Ok(())
+    }
+}
+
+// --- Conversions from result structs to Python dicts ---
+
+fn commit_to_dict<'py>(
+    py: Python<'py>,
+    info: &CommitInfo,
+    fields: &FieldSelection,
+) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    fields.set_item(&dict, "sha", &info.sha)?;
+    fields.set_item(&dict, "repo_name", &info.repo_name)?;
+    fields.set_item(&dict, "message", &info.message)?;
+    fields.set_item(&dict, "author_name", &info.author_name)?;
+    fields.set_item(&dict, "author_email", &info.author_email)?;
+    fields.set_item(&dict, "author_timestamp", info.author_timestamp)?;
+    fields.set_item(&dict, "author_offset", info.author_offset)?;
+    fields.set_item(&dict, "author_local_iso", &info.author_local_iso)?;
+    fields.set_item(&dict, "committer_name", &info.committer_name)?;
+    fields.set_item(&dict, "committer_email", &info.committer_email)?;
+    fields.set_item(&dict, "committer_timestamp", info.committer_timestamp)?;
+    fields.set_item(&dict, "committer_offset", info.committer_offset)?;
+    fields.set_item(&dict, "committer_local_iso", &info.committer_local_iso)?;
+    fields.set_item(&dict, "additions", info.additions)?;
+    fields.set_item(&dict, "deletions", info.deletions)?;
+    fields.set_item(&dict, "is_merge", info.is_merge)?;
+    fields.set_item(&dict, "amended_or_rebased", info.amended_or_rebased)?;
+    fields.set_item(&dict, "parents", &info.parents)?;
+    if fields.includes("files") {
+        let py_files = PyList::empty(py);
+        for change in &info.files {
+            py_files.append(file_change_to_dict(py, change)?)?;
+        }
+        dict.set_item("files", py_files)?;
+    }
+    fields.set_item(&dict, "trailers", &info.trailers)?;
+    fields.set_item(&dict, "signature_status", &info.signature_status)?;
+    Ok(dict)
+}
+
+fn file_change_to_dict<'py>(
+    py: Python<'py>,
+    change: &commits::FileChange,
+) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("path", &change.path)?;
+    dict.set_item("old_path", &change.old_path)?;
+    dict.set_item("status", change.status.as_str())?;
+    dict.set_item("additions", change.additions)?;
+    dict.set_item("deletions", change.deletions)?;
+    Ok(dict)
+}
+
+fn collaborator_to_dict<'py>(
+    py: Python<'py>,
+    collab: &collaborators::CollaboratorInfo,
+) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("login", &collab.login)?;
+    dict.set_item("github_id", collab.github_id)?;
+    dict.set_item("full_name", &collab.full_name)?;
+    dict.set_item("email", &collab.email)?;
+    dict.set_item("avatar_url", &collab.avatar_url)?;
+    dict.set_item("permission", &collab.permission)?;
+    dict.set_item("affiliation", &collab.affiliation)?;
+    Ok(dict)
+}
+
+fn branch_to_dict<'py>(py: Python<'py>, info: &branch::BranchInfo) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("name", &info.name)?;
+    dict.set_item("is_remote", info.is_remote)?;
+    dict.set_item("commit_id", &info.commit_id)?;
+    dict.set_item("commit_message", &info.commit_message)?;
+    dict.set_item("author_name", &info.author_name)?;
+    dict.set_item("author_email", &info.author_email)?;
+    dict.set_item("author_time", info.author_time)?;
+    dict.set_item("is_head", info.is_head)?;
+    dict.set_item("ahead", info.ahead)?;
+    dict.set_item("behind", info.behind)?;
+    dict.set_item("is_merged", info.is_merged)?;
+    dict.set_item("remote_name", &info.remote_name)?;
+    Ok(dict)
+}
+
+/// Converts one section of a repository report to a list of dicts, or its error string.
+fn report_section<'py, T>(
+    py: Python<'py>,
+    result: &Result<Vec<T>, String>,
+    to_dict: impl Fn(&T) -> PyResult<Bound<'py, PyDict>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    match result {
+        Ok(items) => {
+            let py_list = PyList::empty(py);
+            for item in items {
+                py_list.append(to_dict(item)?)?;
+            }
+            Ok(py_list.into_any())
+        }
+        Err(error) => Ok(error.into_pyobject(py)?.into_any()),
+    }
+}
+
+fn authorship_to_dict<'py>(
+    py: Python<'py>,
+    authorship: &blame::Authorship,
+) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("lines", &authorship.lines)?;
+    dict.set_item("percentages", authorship.percentages())?;
+    dict.set_item("total_lines", authorship.total_lines)?;
+    Ok(dict)
+}
+
+fn issue_to_dict<'py>(
+    py: Python<'py>,
+    issue: &IssueInfo,
+    fields: &FieldSelection,
+) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    fields.set_item(&dict, "id", issue.id)?;
+    fields.set_item(&dict, "number", issue.number)?;
+    fields.set_item(&dict, "title", &issue.title)?;
+    fields.set_item(&dict, "state", &issue.state)?;
+    fields.set_item(&dict, "created_at", &issue.created_at)?;
+    fields.set_item(&dict, "updated_at", &issue.updated_at)?;
+    fields.set_item(&dict, "closed_at", &issue.closed_at)?;
+    fields.set_item(&dict, "user_login", &issue.user_login)?;
+    fields.set_item(&dict, "user_id", issue.user_id)?;
+    fields.set_item(&dict, "body", &issue.body)?;
+    fields.set_item(&dict, "comments_count", issue.comments_count)?;
+    fields.set_item(&dict, "is_pull_request", issue.is_pull_request)?;
+    fields.set_item(&dict, "labels", &issue.labels)?;
+    fields.set_item(&dict, "assignees", &issue.assignees)?;
+    if fields.includes("milestone") {
+        let milestone = match &issue.milestone {
+            Some(m) => {
+                let milestone_dict = PyDict::new(py);
+                milestone_dict.set_item("title", &m.title)?;
+                milestone_dict.set_item("number", m.number)?;
+                milestone_dict.set_item("state", &m.state)?;
+                milestone_dict.set_item("due_on", &m.due_on)?;
+                milestone_dict.set_item("closed_at", &m.closed_at)?;
+                Some(milestone_dict)
+            }
+            None => None,
+        };
+        dict.set_item("milestone", milestone)?;
+    }
+    fields.set_item(&dict, "milestone_title", &issue.milestone_title)?;
+    fields.set_item(&dict, "locked", issue.locked)?;
+    fields.set_item(&dict, "html_url", &issue.html_url)?;
+    fields.set_item(&dict, "author_association", &issue.author_association)?;
+    Ok(dict)
+}
+
+fn pull_request_to_dict<'py>(
+    py: Python<'py>,
+    pr: &PullRequestInfo,
+    fields: &FieldSelection,
+) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    fields.set_item(&dict, "id", pr.id)?;
+    fields.set_item(&dict, "number", pr.number)?;
+    fields.set_item(&dict, "title", &pr.title)?;
+    fields.set_item(&dict, "state", &pr.state)?;
+    fields.set_item(&dict, "created_at", &pr.created_at)?;
+    fields.set_item(&dict, "updated_at", &pr.updated_at)?;
+    fields.set_item(&dict, "closed_at", &pr.closed_at)?;
+    fields.set_item(&dict, "merged_at", &pr.merged_at)?;
+    fields.set_item(&dict, "user_login", &pr.user_login)?;
+    fields.set_item(&dict, "user_id", pr.user_id)?;
+    fields.set_item(&dict, "body", &pr.body)?;
+    fields.set_item(&dict, "comments", pr.comments)?;
+    fields.set_item(&dict, "commits", pr.commits)?;
+    fields.set_item(&dict, "additions", pr.additions)?;
+    fields.set_item(&dict, "deletions", pr.deletions)?;
+    fields.set_item(&dict, "changed_files", pr.changed_files)?;
+    fields.set_item(&dict, "mergeable", pr.mergeable)?;
+    fields.set_item(&dict, "labels", &pr.labels)?;
+    fields.set_item(&dict, "is_draft", pr.draft)?;
+    fields.set_item(&dict, "merged", pr.merged)?;
+    fields.set_item(&dict, "merged_by", &pr.merged_by)?;
+    fields.set_item(&dict, "base_ref", &pr.base_ref)?;
+    fields.set_item(&dict, "base_sha", &pr.base_sha)?;
+    fields.set_item(&dict, "head_ref", &pr.head_ref)?;
+    fields.set_item(&dict, "head_sha", &pr.head_sha)?;
+    fields.set_item(&dict, "head_repo_full_name", &pr.head_repo_full_name)?;
+    fields.set_item(&dict, "is_cross_fork", pr.is_cross_fork)?;
+    fields.set_item(&dict, "author_association", &pr.author_association)?;
+    Ok(dict)
+}
+
+/// Replaces a selected "body" with its plain-text form, keeping the original as "raw_body".
+fn strip_body_markdown(
+    dict: &Bound<'_, PyDict>,
+    body: &Option<String>,
+    fields: &FieldSelection,
+) -> PyResult<()> {
+    if fields.includes("body") {
+        dict.set_item("raw_body", body)?;
+        dict.set_item("body", body.as_deref().map(text::markdown_to_text))?;
+    }
+    Ok(())
+}
+
+fn comment_to_dict<'py>(py: Python<'py>, comment: &CommentInfo) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("id", comment.id)?;
+
+    // Convert enum to string for Python
+    let comment_type = match comment.comment_type {
+        comments::CommentType::Issue => "issue",
+        comments::CommentType::Commit => "commit",
+        comments::CommentType::PullRequest => "pull_request",
+        comments::CommentType::ReviewComment => "review_comment",
+    };
+    dict.set_item("comment_type", comment_type)?;
+
+    dict.set_item("user_login", &comment.user_login)?;
+    dict.set_item("user_id", comment.user_id)?;
+    dict.set_item("body", &comment.body)?;
+    dict.set_item("created_at", &comment.created_at)?;
+    dict.set_item("updated_at", &comment.updated_at)?;
+    dict.set_item("html_url", &comment.html_url)?;
+
+    // Optional fields become None when absent
+    dict.set_item("issue_number", comment.issue_number)?;
+    dict.set_item("pull_request_number", comment.pull_request_number)?;
+    dict.set_item("commit_id", &comment.commit_id)?;
+    dict.set_item("path", &comment.path)?;
+    dict.set_item("position", comment.position)?;
+    dict.set_item("line", comment.line)?;
+    dict.set_item("commit_sha", &comment.commit_sha)?;
+    Ok(dict)
+}
+
+fn review_to_dict<'py>(py: Python<'py>, review: &ReviewInfo) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("id", review.id)?;
+    dict.set_item("pr_number", review.pr_number)?;
+    dict.set_item("user_login", &review.user_login)?;
+    dict.set_item("user_id", review.user_id)?;
+    dict.set_item("body", &review.body)?;
+    dict.set_item("state", &review.state)?;
+    dict.set_item("submitted_at", &review.submitted_at)?;
+    dict.set_item("commit_id", &review.commit_id)?;
+    dict.set_item("html_url", &review.html_url)?;
+    Ok(dict)
+}
+
+// --- Exposed Python Class: CloneProgressStream ---
+/// Async iterator over clone status transitions, returned by `watch_clone_progress`.
+/// Yields `(url, status_type, progress)` tuples and stops once every task has
+/// completed or failed.
+#[pyclass(name = "CloneProgressStream", module = "gradelib")]
+pub struct CloneProgressStream {
+    inner: Arc<InternalRepoManagerLogic>,
+    receiver: Arc<::tokio::sync::Mutex<broadcast::Receiver<CloneProgressEvent>>>,
+}
+
+#[pymethods]
+impl CloneProgressStream {
+    fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __anext__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let inner = Arc::clone(&self.inner);
+        let receiver = Arc::clone(&self.receiver);
+        tokio::future_into_py(py, async move {
+            let mut receiver = receiver.lock().await;
+            let event = loop {
+                // Drain buffered events before deciding the stream is over
+                match receiver.try_recv() {
+                    Ok(event) => break event,
+                    Err(broadcast::error::TryRecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::TryRecvError::Closed) => {
+                        return Err(pyo3::exceptions::PyStopAsyncIteration::new_err(()))
+                    }
+                    Err(broadcast::error::TryRecvError::Empty) => {}
+                }
+                if inner.all_clones_finished() {
+                    return Err(pyo3::exceptions::PyStopAsyncIteration::new_err(()));
+                }
+                match receiver.recv().await {
+                    Ok(event) => break event,
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => {
+                        return Err(pyo3::exceptions::PyStopAsyncIteration::new_err(()))
+                    }
+                }
+            };
+            let status = ExposedCloneStatus::from(event.status);
+            Ok((event.url, status.status_type, status.progress))
+        })
+    }
+}
+
+/// Converts a Python seconds argument into a Duration, rejecting zero, negative and NaN values.
+fn positive_duration(name: &str, secs: f64) -> PyResult<Duration> {
+    match Duration::try_from_secs_f64(secs) {
+        Ok(limit) if !limit.is_zero() => Ok(limit),
+        _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "{} must be a positive number of seconds, got {}",
+            name, secs
+        ))),
     }
 }
 
@@ -109,19 +576,80 @@ pub struct RepoManager {
 #[pymethods]
 impl RepoManager {
     #[new]
-    #[pyo3(signature = (urls, github_token, github_username=None))]
-    fn new(urls: Vec<String>, github_token: String, github_username: Option<String>) -> Self {
+    #[pyo3(signature = (urls, github_token, github_username=None, target_dir=None, clone_timeout=None, http_timeout=None, pool_max_idle_per_host=None, proxy_url=None, client_headers=None, max_concurrent_clones=None, clone_retries=None, token_type=None, clone_dir_scheme=None))]
+    #[allow(clippy::too_many_arguments)] // Each keyword argument maps to a Python parameter
+    fn new(
+        urls: Vec<String>,
+        github_token: String,
+        github_username: Option<String>,
+        target_dir: Option<PathBuf>,
+        clone_timeout: Option<f64>, // Seconds; None never times out
+        http_timeout: Option<f64>,  // Seconds; None uses the 30s default
+        pool_max_idle_per_host: Option<usize>,
+        proxy_url: Option<String>, // None falls back to HTTPS_PROXY
+        client_headers: Option<HashMap<String, String>>,
+        max_concurrent_clones: Option<usize>, // None uses the default of 8
+        clone_retries: Option<u32>,           // None uses the default of 2
+        token_type: Option<String>,           // "pat" (default) or "oauth"
+        clone_dir_scheme: Option<String>,     // None uses owner__repo
+    ) -> PyResult<Self> {
         let string_urls: Vec<&str> = urls.iter().map(|s| s.as_str()).collect();
         // Use an empty string if username is None
         let username = github_username.unwrap_or_default();
+        let clone_timeout = clone_timeout
+            .map(|secs| positive_duration("clone_timeout", secs))
+            .transpose()?;
+        let api_config = ApiClientConfig {
+            timeout: http_timeout
+                .map(|secs| positive_duration("http_timeout", secs))
+                .transpose()?
+                .unwrap_or(api_client::DEFAULT_TIMEOUT),
+            pool_max_idle_per_host,
+            proxy_url: proxy_url.or_else(api_client::proxy_from_env),
+            headers: api_client::parse_client_headers(client_headers.unwrap_or_default())
+                .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?,
+            token_kind: token_type
+                .as_deref()
+                .map(api_client::TokenKind::parse)
+                .transpose()
+                .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?
+                .unwrap_or_default(),
+        };
+        if let Some(proxy_url) = &api_config.proxy_url {
+            reqwest::Proxy::all(proxy_url).map_err(|e| {
+                PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Invalid proxy_url '{}': {}",
+                    proxy_url, e
+                ))
+            })?;
+        }
+        let clone_dir_scheme = clone_dir_scheme
+            .as_deref()
+            .map(repo::CloneDirScheme::parse)
+            .transpose()
+            .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?
+            .unwrap_or_default();
+        let max_concurrent_clones =
+            max_concurrent_clones.unwrap_or(repo::DEFAULT_MAX_CONCURRENT_CLONES);
+        if max_concurrent_clones == 0 {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "max_concurrent_clones must be at least 1",
+            ));
+        }
         // Create the internal logic handler with username and token
-        Self {
+        Ok(Self {
             inner: Arc::new(InternalRepoManagerLogic::new(
                 &string_urls,
                 &username,
                 &github_token,
+                target_dir,
+                clone_dir_scheme,
+                clone_timeout,
+                api_config,
+                max_concurrent_clones,
+                clone_retries.unwrap_or(repo::DEFAULT_CLONE_RETRIES),
             )),
-        }
+        })
     }
 
     /// Clones all repositories configured in this manager instance asynchronously.
@@ -144,9 +672,10 @@ impl RepoManager {
             // Get tasks in their internal representation
             let internal_tasks = inner.get_internal_tasks().await;
             // Convert internal tasks to the exposed task type
+            // Tasks are stored under canonical URLs but reported under the URL the caller gave
             let result: HashMap<String, ExposedCloneTask> = internal_tasks
-                .into_iter()
-                .map(|(k, v)| (k, v.into())) // Use From impl for conversion
+                .into_values()
+                .map(|v| (v.url.clone(), v.into())) // Use From impl for conversion
                 .collect();
 
             // Convert the Rust HashMap to a Python dictionary
@@ -160,31 +689,316 @@ impl RepoManager {
         })
     }
 
+    /// Returns an async iterator of `(url, status_type, progress)` tuples, one per clone
+    /// status change, ending once every task has completed or failed.
+    #[pyo3(name = "watch_clone_progress")]
+    fn watch_clone_progress(&self) -> CloneProgressStream {
+        CloneProgressStream {
+            receiver: Arc::new(::tokio::sync::Mutex::new(self.inner.subscribe_progress())),
+            inner: Arc::clone(&self.inner),
+        }
+    }
+
+    /// Creates a manager using credentials from the environment.
+    /// Reads `GITHUB_TOKEN` (required) and `GITHUB_USERNAME` (optional).
+    #[staticmethod]
+    #[pyo3(name = "from_env", signature = (urls, target_dir=None, clone_timeout=None, http_timeout=None, pool_max_idle_per_host=None, proxy_url=None, client_headers=None, max_concurrent_clones=None, clone_retries=None, token_type=None, clone_dir_scheme=None))]
+    #[allow(clippy::too_many_arguments)] // Each keyword argument maps to a Python parameter
+    fn from_env(
+        urls: Vec<String>,
+        target_dir: Option<PathBuf>,
+        clone_timeout: Option<f64>,
+        http_timeout: Option<f64>,
+        pool_max_idle_per_host: Option<usize>,
+        proxy_url: Option<String>,
+        client_headers: Option<HashMap<String, String>>,
+        max_concurrent_clones: Option<usize>,
+        clone_retries: Option<u32>,
+        token_type: Option<String>,
+        clone_dir_scheme: Option<String>,
+    ) -> PyResult<Self> {
+        let github_token = std::env::var("GITHUB_TOKEN").map_err(|_| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "GITHUB_TOKEN environment variable is not set",
+            )
+        })?;
+        let github_username = std::env::var("GITHUB_USERNAME").ok();
+        Self::new(
+            urls,
+            github_token,
+            github_username,
+            target_dir,
+            clone_timeout,
+            http_timeout,
+            pool_max_idle_per_host,
+            proxy_url,
+            client_headers,
+            max_concurrent_clones,
+            clone_retries,
+            token_type,
+            clone_dir_scheme,
+        )
+    }
+
+    /// Replaces the GitHub token for clones and API calls started after this returns,
+    /// keeping clone state. Calls already in flight finish with the old token.
+    #[pyo3(name = "update_token")]
+    fn update_token(&self, new_token: String) -> PyResult<()> {
+        if new_token.is_empty() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "new_token must not be empty",
+            ));
+        }
+        // Checked here so a bad token fails now rather than on every later API call
+        reqwest::header::HeaderValue::from_str(&new_token).map_err(|_| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "new_token is not a valid HTTP header value",
+            )
+        })?;
+        self.inner.set_github_token(&new_token);
+        Ok(())
+    }
+
+    /// Returns the canonical form of a repository URL as used for clone task keys.
+    /// SSH and HTTPS URLs for the same repository yield the same canonical URL.
+    #[pyo3(name = "canonical_url")]
+    fn canonical_url(&self, url: &str) -> String {
+        repo::canonicalize_url(url)
+    }
+
+    /// Tallies the clone tasks by status: `{queued, cloning, completed, failed, failures}`,
+    /// where `failures` lists `{url, error}` for each failed task.
+    #[pyo3(name = "clone_summary")]
+    fn clone_summary<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let summary = self.inner.clone_summary();
+        let failures = PyList::empty(py);
+        for (url, error) in &summary.failures {
+            let failure = PyDict::new(py);
+            failure.set_item("url", url)?;
+            failure.set_item("error", error)?;
+            failures.append(failure)?;
+        }
+        let dict = PyDict::new(py);
+        dict.set_item("queued", summary.queued)?;
+        dict.set_item("cloning", summary.cloning)?;
+        dict.set_item("completed", summary.completed)?;
+        dict.set_item("failed", summary.failed)?;
+        dict.set_item("failures", failures)?;
+        Ok(dict)
+    }
+
+    /// Checks every managed URL with the parser used for GitHub API calls.
+    /// Returns {url: None} for valid URLs and {url: error message} otherwise.
+    #[pyo3(name = "validate_urls")]
+    fn validate_urls(&self) -> HashMap<String, Option<String>> {
+        self.inner.validate_urls()
+    }
+
+    /// Registers a repository that is already checked out at `path` under `identifier`,
+    /// marking it completed so the analysis methods work without cloning it.
+    #[pyo3(name = "register_local_repo")]
+    fn register_local_repo(&self, identifier: &str, path: PathBuf) -> PyResult<()> {
+        self.inner
+            .register_local_repo(identifier, &path)
+            .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)
+    }
+
+    /// Drops all cached blame results and resets the cache counters.
+    #[pyo3(name = "clear_blame_cache")]
+    fn clear_blame_cache(&self) {
+        self.inner.blame_cache.lock().unwrap().clear();
+    }
+
+    /// Returns `{"hits", "misses", "entries"}` for the blame cache.
+    /// Every miss is a blame that was actually run.
+    #[pyo3(name = "blame_cache_info")]
+    fn blame_cache_info(&self) -> HashMap<&'static str, usize> {
+        let cache = self.inner.blame_cache.lock().unwrap();
+        HashMap::from([
+            ("hits", cache.hits),
+            ("misses", cache.misses),
+            ("entries", cache.len()),
+        ])
+    }
+
+    /// Cancels an in-flight clone; its task is marked failed with "cancelled".
+    /// Returns False if no clone is running for the URL.
+    #[pyo3(name = "cancel_clone")]
+    fn cancel_clone(&self, url: &str) -> bool {
+        self.inner.cancel_clone(url)
+    }
+
+    /// Cancels all in-flight clones and returns how many were signalled.
+    #[pyo3(name = "cancel_all")]
+    fn cancel_all(&self) -> usize {
+        self.inner.cancel_all()
+    }
+
+    /// Writes the most recent results of one kind for a repository to a JSON file.
+    /// `data_kind` is one of "commits", "blame", "branches", "collaborators", "issues"
+    /// or "pull_requests"; `repo_url` is the URL or path the results were fetched with.
+    #[pyo3(name = "export_json")]
+    fn export_json(&self, path: PathBuf, data_kind: &str, repo_url: &str) -> PyResult<()> {
+        self.inner
+            .export_json(&path, data_kind, repo_url)
+            .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)
+    }
+
     /// Clones a single repository specified by URL asynchronously.
-    #[pyo3(name = "clone")]
-    fn clone<'py>(&self, py: Python<'py>, url: String) -> PyResult<Bound<'py, PyAny>> {
+    /// `branch` optionally selects the branch to check out instead of the default.
+    /// `extra_args` takes the supported `git clone` flags (`--depth`, `--no-tags`, `--branch`).
+    /// `checkout` checks out a branch, tag or commit once the clone has finished.
+    #[pyo3(name = "clone", signature = (url, branch=None, extra_args=None, checkout=None))]
+    fn clone<'py>(
+        &self,
+        py: Python<'py>,
+        url: String,
+        branch: Option<String>,
+        extra_args: Option<Vec<String>>,
+        checkout: Option<String>,
+    ) -> PyResult<Bound<'py, PyAny>> {
         let inner = Arc::clone(&self.inner); // Clone Arc for the async block
         let url_clone = url.clone(); // Clone the URL for the closure
+        let mut options = CloneOptions {
+            branch,
+            checkout,
+            ..Default::default()
+        };
+        options
+            .apply_extra_args(&extra_args.unwrap_or_default())
+            .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?;
         tokio::future_into_py(py, async move {
             // Call the clone method on InternalRepoManagerLogic through deref()
-            let _ = inner.deref().clone(url_clone).await;
+            let _ = inner.deref().clone(url_clone, options).await;
             Python::with_gil(|py| Ok(py.None()))
         })
     }
 
+    /// Clones a single repository like `clone`, resolving to its final CloneStatus.
+    /// Raises ValueError with the task's error if the clone failed, or before cloning
+    /// if the URL is not managed, since there would be no task to report.
+    #[pyo3(name = "clone_and_wait", signature = (url, branch=None, extra_args=None, checkout=None))]
+    fn clone_and_wait<'py>(
+        &self,
+        py: Python<'py>,
+        url: String,
+        branch: Option<String>,
+        extra_args: Option<Vec<String>>,
+        checkout: Option<String>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let inner = Arc::clone(&self.inner);
+        let mut options = CloneOptions {
+            branch,
+            checkout,
+            ..Default::default()
+        };
+        options
+            .apply_extra_args(&extra_args.unwrap_or_default())
+            .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?;
+        if !inner.manages(&url) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Repository {} is not managed",
+                url
+            )));
+        }
+        tokio::future_into_py(py, async move {
+            let (result, _) = inner.deref().clone(url.clone(), options).await;
+            result.map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?;
+            let status = inner
+                .get_internal_tasks()
+                .await
+                .remove(&repo::canonicalize_url(&url))
+                .map(|task| ExposedCloneStatus::from(task.status))
+                .ok_or_else(|| {
+                    PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                        "No clone task for {}",
+                        url
+                    ))
+                })?;
+            Ok(status)
+        })
+    }
+
+    /// Clones every managed repository like `clone_all`, resolving to `{url: CloneStatus}`
+    /// once all have finished. Raises ValueError naming each failed clone if any failed.
+    #[pyo3(name = "clone_all_and_wait")]
+    fn clone_all_and_wait<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let inner = Arc::clone(&self.inner);
+        tokio::future_into_py(py, async move {
+            inner.clone_all().await;
+            let tasks = inner.get_internal_tasks().await;
+            let mut failures: Vec<String> = tasks
+                .values()
+                .filter_map(|task| match &task.status {
+                    InternalCloneStatus::Failed(err) => Some(format!("{}: {}", task.url, err)),
+                    _ => None,
+                })
+                .collect();
+            if !failures.is_empty() {
+                failures.sort();
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "{} of {} clones failed: {}",
+                    failures.len(),
+                    tasks.len(),
+                    failures.join("; ")
+                )));
+            }
+            // Reported under the URL the caller gave, like fetch_clone_tasks
+            let statuses: HashMap<String, ExposedCloneStatus> = tasks
+                .into_values()
+                .map(|task| (task.url, task.status.into()))
+                .collect();
+            Ok(statuses)
+        })
+    }
+
     /// Performs 'git blame' on multiple files within a cloned repository asynchronously.
-    #[pyo3(name = "bulk_blame")]
+    /// The optional flags mirror `git blame -w`, `-M` and `-C`.
+    /// `max_parallel` caps how many files are blamed at once (default: number of CPUs).
+    /// `rev` blames the files as of that revision instead of the working tree.
+    #[pyo3(
+        name = "bulk_blame",
+        signature = (repo_path, file_paths, ignore_whitespace=false, detect_moves=false, detect_copies=false, max_parallel=None, rev=None)
+    )]
+    #[allow(clippy::too_many_arguments)] // Each keyword argument maps to a Python parameter
     fn bulk_blame<'py>(
         &self,
         py: Python<'py>,
         repo_path: String,
         file_paths: Vec<String>,
+        ignore_whitespace: bool,
+        detect_moves: bool,
+        detect_copies: bool,
+        max_parallel: Option<usize>,
+        rev: Option<String>,
     ) -> PyResult<Bound<'py, PyAny>> {
         let inner = Arc::clone(&self.inner); // Clone Arc for the async block
+        let options = blame::BlameOptions {
+            ignore_whitespace,
+            detect_moves,
+            detect_copies,
+            rev,
+        };
         tokio::future_into_py(py, async move {
             let result_map = inner
-                .bulk_blame(&PathBuf::from(repo_path), file_paths)
+                .bulk_blame(
+                    &PathBuf::from(&repo_path),
+                    file_paths,
+                    options,
+                    max_parallel,
+                )
                 .await;
+            if let Ok(blame_results_map) = &result_map {
+                // Failed files are exported as their error string
+                let exported: HashMap<&String, serde_json::Value> = blame_results_map
+                    .iter()
+                    .map(|(file_path, result)| match result {
+                        Ok(lines) => (file_path, serde_json::json!(lines)),
+                        Err(e) => (file_path, serde_json::json!(e)),
+                    })
+                    .collect();
+                inner.record_result("blame", &repo_path, &exported);
+            }
             Python::with_gil(|py| -> PyResult<Py<PyAny>> {
                 match result_map {
                     Ok(blame_results_map) => {
@@ -200,6 +1014,9 @@ impl RepoManager {
                                             .set_item("author_name", &line_info.author_name)?;
                                         line_dict
                                             .set_item("author_email", &line_info.author_email)?;
+                                        line_dict.set_item("author_time", line_info.author_time)?;
+                                        line_dict
+                                            .set_item("author_offset", line_info.author_offset)?;
                                         line_dict
                                             .set_item("orig_line_no", line_info.orig_line_no)?;
                                         line_dict
@@ -225,39 +1042,159 @@ impl RepoManager {
         })
     }
 
+    /// Blames files in a cloned repository and counts the surviving lines per author email.
+    /// Returns `{files: {path: authorship}, total: authorship, errors: {path: error}}`, where
+    /// each authorship is `{lines: {email: count}, percentages: {email: pct}, total_lines}`.
+    /// `identity_map` maps alias emails to the email they are counted under.
+    #[pyo3(name = "blame_authorship", signature = (repo_url, file_paths, identity_map=None))]
+    fn blame_authorship<'py>(
+        &self,
+        py: Python<'py>,
+        repo_url: String,
+        file_paths: Vec<String>,
+        identity_map: Option<HashMap<String, String>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let inner = Arc::clone(&self.inner);
+        tokio::future_into_py(py, async move {
+            let result = inner
+                .blame_authorship(&repo_url, file_paths, identity_map.unwrap_or_default())
+                .await;
+            Python::with_gil(|py| -> PyResult<Py<PyAny>> {
+                match result {
+                    Ok(authorship) => {
+                        let files = PyDict::new(py);
+                        for (file_path, file) in &authorship.files {
+                            files.set_item(file_path, authorship_to_dict(py, file)?)?;
+                        }
+                        let dict = PyDict::new(py);
+                        dict.set_item("files", files)?;
+                        dict.set_item("total", authorship_to_dict(py, &authorship.total)?)?;
+                        dict.set_item("errors", &authorship.errors)?;
+                        Ok(dict.into_any().unbind())
+                    }
+                    Err(err_string) => {
+                        Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(err_string))
+                    }
+                }
+            })
+        })
+    }
+
+    /// Bins the commits of a cloned repository by author date into "day", "week" or "month"
+    /// bins. Returns `[(bin_start_date, commits, additions, deletions)]`, oldest first.
+    /// `utc_offset_minutes` bins in that timezone instead of each author's own.
+    #[pyo3(name = "commit_activity", signature = (repo_url, bucket="day", utc_offset_minutes=None))]
+    fn commit_activity<'py>(
+        &self,
+        py: Python<'py>,
+        repo_url: String,
+        bucket: &str,
+        utc_offset_minutes: Option<i32>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let bucket = commits::ActivityBucket::parse(bucket)
+            .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?;
+        let inner = Arc::clone(&self.inner);
+        tokio::future_into_py(py, async move {
+            let bins = inner
+                .commit_activity(&repo_url, bucket, utc_offset_minutes)
+                .await
+                .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?;
+            Ok(bins
+                .into_iter()
+                .map(|bin| (bin.start, bin.commits, bin.additions, bin.deletions))
+                .collect::<Vec<_>>())
+        })
+    }
+
+    /// Builds who-wrote-with-whom from the `Co-authored-by` trailers of a cloned repository.
+    /// Returns `{nodes: {email: commits}, edges: [{source, target, weight}]}`, heaviest edge
+    /// first. `identity_map` maps alias emails to the email they are counted under.
+    #[pyo3(name = "coauthorship_graph", signature = (repo_url, identity_map=None))]
+    fn coauthorship_graph<'py>(
+        &self,
+        py: Python<'py>,
+        repo_url: String,
+        identity_map: Option<HashMap<String, String>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let inner = Arc::clone(&self.inner);
+        tokio::future_into_py(py, async move {
+            let result = inner
+                .coauthorship_graph(&repo_url, identity_map.unwrap_or_default())
+                .await;
+            Python::with_gil(|py| -> PyResult<Py<PyAny>> {
+                match result {
+                    Ok(graph) => {
+                        let edges = PyList::empty(py);
+                        for edge in &graph.edges {
+                            let edge_dict = PyDict::new(py);
+                            edge_dict.set_item("source", &edge.source)?;
+                            edge_dict.set_item("target", &edge.target)?;
+                            edge_dict.set_item("weight", edge.weight)?;
+                            edges.append(edge_dict)?;
+                        }
+                        let dict = PyDict::new(py);
+                        dict.set_item("nodes", &graph.nodes)?;
+                        dict.set_item("edges", edges)?;
+                        Ok(dict.into_any().unbind())
+                    }
+                    Err(err_string) => {
+                        Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(err_string))
+                    }
+                }
+            })
+        })
+    }
+
     /// Analyzes the commit history of a cloned repository asynchronously.
-    #[pyo3(name = "analyze_commits")]
+    /// `fields` optionally restricts the keys emitted in each commit dict.
+    /// `author_email` keeps only commits by any of the given author emails.
+    /// `rev` walks history from that revision instead of HEAD; "--all" walks every ref.
+    /// `parse_trailers` fills each commit's `trailers` from its message.
+    /// `max_count` keeps only the newest commits, like `git log -n`.
+    /// `path` keeps only commits touching that file or directory, like `git log -- <path>`.
+    /// `file_stats` fills each commit's `files`, detecting renames.
+    #[pyo3(
+        name = "analyze_commits",
+        signature = (repo_path, fields=None, author_email=None, rev=None, parse_trailers=false, max_count=None, path=None, file_stats=false)
+    )]
+    #[allow(clippy::too_many_arguments)] // Each keyword argument maps to a Python parameter
     fn analyze_commits<'py>(
         &self,
         py: Python<'py>,
         repo_path: String,
+        fields: Option<Vec<String>>,
+        author_email: Option<Vec<String>>,
+        rev: Option<String>,
+        parse_trailers: bool,
+        max_count: Option<usize>,
+        path: Option<String>,
+        file_stats: bool,
     ) -> PyResult<Bound<'py, PyAny>> {
         let inner = Arc::clone(&self.inner);
         let repo_path_clone = repo_path.clone();
+        let fields = FieldSelection::new(fields, COMMIT_FIELDS)?;
+        let options = CommitAnalysisOptions {
+            author_emails: author_email,
+            rev,
+            since_sha: None,
+            parse_trailers,
+            max_count,
+            path,
+            file_stats,
+        };
         tokio::future_into_py(py, async move {
-            let result_vec = inner.get_commit_analysis(&PathBuf::from(repo_path_clone));
+            let result_vec = inner
+                .get_commit_analysis(&PathBuf::from(&repo_path_clone), options)
+                .await;
+            if let Ok(commit_infos) = &result_vec {
+                inner.record_result("commits", &repo_path_clone, commit_infos);
+            }
             Python::with_gil(|py| -> PyResult<Py<PyAny>> {
                 match result_vec {
                     Ok(commit_infos) => {
                         let py_commit_list = PyList::empty(py);
                         for info in commit_infos {
-                            let commit_dict = PyDict::new(py);
-                            commit_dict.set_item("sha", &info.sha)?;
-                            commit_dict.set_item("repo_name", &info.repo_name)?;
-                            commit_dict.set_item("message", &info.message)?;
-                            commit_dict.set_item("author_name", &info.author_name)?;
-                            commit_dict.set_item("author_email", &info.author_email)?;
-                            commit_dict.set_item("author_timestamp", info.author_timestamp)?;
-                            commit_dict.set_item("author_offset", info.author_offset)?;
-                            commit_dict.set_item("committer_name", &info.committer_name)?;
-                            commit_dict.set_item("committer_email", &info.committer_email)?;
-                            commit_dict
-                                .set_item("committer_timestamp", info.committer_timestamp)?;
-                            commit_dict.set_item("committer_offset", info.committer_offset)?;
-                            commit_dict.set_item("additions", info.additions)?;
-                            commit_dict.set_item("deletions", info.deletions)?;
-                            commit_dict.set_item("is_merge", info.is_merge)?;
-                            py_commit_list.append(commit_dict)?;
+                            py_commit_list.append(commit_to_dict(py, &info, &fields)?)?;
                         }
                         Ok(py_commit_list.into())
                     }
@@ -269,28 +1206,209 @@ impl RepoManager {
         })
     }
 
+    /// Writes the commit history of a cloned repository to `path` as JSON lines, one commit
+    /// per line, without building the whole history in memory. Returns the number written.
+    #[pyo3(name = "stream_commits_to_file")]
+    fn stream_commits_to_file<'py>(
+        &self,
+        py: Python<'py>,
+        repo_url: String,
+        path: String,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let inner = Arc::clone(&self.inner);
+        tokio::future_into_py(py, async move {
+            inner
+                .stream_commits_to_file(&repo_url, PathBuf::from(path), Default::default())
+                .await
+                .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)
+        })
+    }
+
+    /// Looks for force-pushes of the default branch in a cloned repository's reflog.
+    /// Returns a list of `{ref_name, old_sha, new_sha, timestamp, offset, message}` dicts,
+    /// oldest first. Needs a full, non-bare clone.
+    #[pyo3(name = "detect_force_pushes")]
+    fn detect_force_pushes<'py>(
+        &self,
+        py: Python<'py>,
+        repo_url: String,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let inner = Arc::clone(&self.inner);
+        tokio::future_into_py(py, async move {
+            let result = inner.detect_force_pushes(&repo_url).await;
+            Python::with_gil(|py| -> PyResult<Py<PyAny>> {
+                match result {
+                    Ok(force_pushes) => {
+                        let py_list = PyList::empty(py);
+                        for info in &force_pushes {
+                            let dict = PyDict::new(py);
+                            dict.set_item("ref_name", &info.ref_name)?;
+                            dict.set_item("old_sha", &info.old_sha)?;
+                            dict.set_item("new_sha", &info.new_sha)?;
+                            dict.set_item("timestamp", info.timestamp)?;
+                            dict.set_item("offset", info.offset)?;
+                            dict.set_item("message", &info.message)?;
+                            py_list.append(dict)?;
+                        }
+                        Ok(py_list.into_any().unbind())
+                    }
+                    Err(err_string) => {
+                        Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(err_string))
+                    }
+                }
+            })
+        })
+    }
+
+    /// Diffs `head` against `base` in a cloned repository, like `git diff --numstat`.
+    /// A `base` ending in "..." diffs from the merge base of the two revisions.
+    /// Returns `{base_sha, head_sha, additions, deletions, files}`.
+    #[pyo3(name = "diff_stats")]
+    fn diff_stats<'py>(
+        &self,
+        py: Python<'py>,
+        repo_url: String,
+        base: String,
+        head: String,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let inner = Arc::clone(&self.inner);
+        tokio::future_into_py(py, async move {
+            let result = inner.diff_stats(&repo_url, base, head).await;
+            Python::with_gil(|py| -> PyResult<Py<PyAny>> {
+                match result {
+                    Ok(stats) => {
+                        let dict = PyDict::new(py);
+                        dict.set_item("base_sha", &stats.base_sha)?;
+                        dict.set_item("head_sha", &stats.head_sha)?;
+                        dict.set_item("additions", stats.additions)?;
+                        dict.set_item("deletions", stats.deletions)?;
+                        let py_files = PyList::empty(py);
+                        for change in &stats.files {
+                            py_files.append(file_change_to_dict(py, change)?)?;
+                        }
+                        dict.set_item("files", py_files)?;
+                        Ok(dict.into_any().unbind())
+                    }
+                    Err(err_string) => {
+                        Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(err_string))
+                    }
+                }
+            })
+        })
+    }
+
+    /// Analyzes the commit history of several cloned repositories in parallel.
+    /// Returns a dict of URL to commit list, or to an error string for repositories
+    /// that are not managed or not completed. `file_stats` fills each commit's `files`.
+    #[pyo3(
+        name = "analyze_commits_bulk",
+        signature = (repo_urls, fields=None, file_stats=false)
+    )]
+    fn analyze_commits_bulk<'py>(
+        &self,
+        py: Python<'py>,
+        repo_urls: Vec<String>,
+        fields: Option<Vec<String>>,
+        file_stats: bool,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let inner = Arc::clone(&self.inner);
+        let fields = FieldSelection::new(fields, COMMIT_FIELDS)?;
+        let options = CommitAnalysisOptions {
+            file_stats,
+            ..Default::default()
+        };
+        tokio::future_into_py(py, async move {
+            let result_map = inner.get_commit_analysis_bulk(repo_urls, options).await;
+            inner.record_results("commits", &result_map);
+            Python::with_gil(|py| -> PyResult<Py<PyAny>> {
+                let py_result_dict = PyDict::new(py);
+                for (repo_url, result) in result_map {
+                    match result {
+                        Ok(commit_infos) => {
+                            let py_commit_list = PyList::empty(py);
+                            for info in &commit_infos {
+                                py_commit_list.append(commit_to_dict(py, info, &fields)?)?;
+                            }
+                            py_result_dict.set_item(repo_url, py_commit_list)?;
+                        }
+                        Err(error) => py_result_dict.set_item(repo_url, error)?,
+                    }
+                }
+                Ok(py_result_dict.into())
+            })
+        })
+    }
+
+    /// Analyzes only the commits added to HEAD since `last_sha` was seen.
+    /// Returns `(commits, head_sha, full_history)`; `full_history` is True when `last_sha`
+    /// is no longer in HEAD's history (e.g. after a force-push) and all commits were returned.
+    #[pyo3(name = "analyze_commits_since", signature = (repo_path, last_sha, fields=None))]
+    fn analyze_commits_since<'py>(
+        &self,
+        py: Python<'py>,
+        repo_path: String,
+        last_sha: String,
+        fields: Option<Vec<String>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let inner = Arc::clone(&self.inner);
+        let fields = FieldSelection::new(fields, COMMIT_FIELDS)?;
+        tokio::future_into_py(py, async move {
+            let result = inner
+                .get_commit_analysis_since(&PathBuf::from(&repo_path), last_sha)
+                .await;
+            Python::with_gil(|py| -> PyResult<Py<PyAny>> {
+                match result {
+                    Ok(incremental) => {
+                        let py_commit_list = PyList::empty(py);
+                        for info in &incremental.commits {
+                            py_commit_list.append(commit_to_dict(py, info, &fields)?)?;
+                        }
+                        let result = (
+                            py_commit_list,
+                            incremental.head_sha,
+                            incremental.full_history,
+                        );
+                        Ok(result.into_pyobject(py)?.into_any().unbind())
+                    }
+                    Err(err_string) => {
+                        Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(err_string))
+                    }
+                }
+            })
+        })
+    }
+
     /// Fetches collaborator information for multiple repositories.
     /// Returns a dictionary mapping each repo URL to either a list of collaborators (on success)
     /// or an error string (on failure for that repo). No exceptions are raised for individual failures.
-    #[pyo3(name = "fetch_collaborators")]
+    /// `affiliation` filters collaborators by "outside", "direct" or "all" (the default).
+    #[pyo3(name = "fetch_collaborators", signature = (repo_urls, max_pages=None, affiliation=None))]
     fn fetch_collaborators<'py>(
         &self,
         py: Python<'py>,
         repo_urls: Vec<String>,
         max_pages: Option<usize>,
+        affiliation: Option<String>,
     ) -> PyResult<Bound<'py, PyAny>> {
         // Use the existing credentials from the RepoManager
         let github_username = self.inner.github_username.clone();
-        let github_token = self.inner.github_token.clone();
+        let github_token = self.inner.github_token();
+        let api_config = self.inner.api_config.clone();
+        let inner = Arc::clone(&self.inner);
 
         tokio::future_into_py(py, async move {
             let result = collaborators::fetch_collaborators(
                 repo_urls,
                 &github_username, // Even though prefixed with underscore in the implementation,
                 &github_token,    // we still need to pass it here
+                &api_config,
                 max_pages,
+                affiliation.as_deref(),
             )
             .await;
+            if let Ok(collab_map) = &result {
+                inner.record_results("collaborators", collab_map);
+            }
 
             Python::with_gil(|py| -> PyResult<Py<PyAny>> {
                 match result {
@@ -302,30 +1420,8 @@ impl RepoManager {
                                 Ok(collaborators) => {
                                     let py_collab_list = PyList::empty(py);
 
-                                    for collab in collaborators {
-                                        let collab_dict = PyDict::new(py);
-                                        collab_dict.set_item("login", &collab.login)?;
-                                        collab_dict.set_item("github_id", collab.github_id)?;
-
-                                        if let Some(name) = &collab.full_name {
-                                            collab_dict.set_item("full_name", name)?;
-                                        } else {
-                                            collab_dict.set_item("full_name", py.None())?;
-                                        }
-
-                                        if let Some(email) = &collab.email {
-                                            collab_dict.set_item("email", email)?;
-                                        } else {
-                                            collab_dict.set_item("email", py.None())?;
-                                        }
-
-                                        if let Some(avatar) = &collab.avatar_url {
-                                            collab_dict.set_item("avatar_url", avatar)?;
-                                        } else {
-                                            collab_dict.set_item("avatar_url", py.None())?;
-                                        }
-
-                                        py_collab_list.append(collab_dict)?;
+                                    for collab in &collaborators {
+                                        py_collab_list.append(collaborator_to_dict(py, collab)?)?;
                                     }
 
                                     py_result_dict.set_item(repo_url, py_collab_list)?;
@@ -347,27 +1443,42 @@ impl RepoManager {
     }
 
     /// Fetches issue information for multiple repositories.
-    #[pyo3(name = "fetch_issues")]
+    /// `fields` optionally restricts the keys emitted in each issue dict.
+    /// `since` (ISO 8601) limits results to issues updated at or after that time.
+    /// `strip_markdown` turns bodies into plain text, keeping the original as "raw_body".
+    #[pyo3(name = "fetch_issues", signature = (repo_urls, state=None, max_pages=None, fields=None, since=None, strip_markdown=false))]
+    #[allow(clippy::too_many_arguments)] // Each keyword argument maps to a Python parameter
     fn fetch_issues<'py>(
         &self,
         py: Python<'py>,
         repo_urls: Vec<String>,
         state: Option<String>,
         max_pages: Option<usize>,
+        fields: Option<Vec<String>>,
+        since: Option<String>,
+        strip_markdown: bool,
     ) -> PyResult<Bound<'py, PyAny>> {
         // Use the existing credentials from the RepoManager
         let github_username = self.inner.github_username.clone();
-        let github_token = self.inner.github_token.clone();
+        let github_token = self.inner.github_token();
+        let api_config = self.inner.api_config.clone();
+        let fields = FieldSelection::new(fields, ISSUE_FIELDS)?;
+        let inner = Arc::clone(&self.inner);
 
         tokio::future_into_py(py, async move {
             let result = issues::fetch_issues(
                 repo_urls,
                 &github_username,
                 &github_token,
+                &api_config,
                 state.as_deref(),
                 max_pages,
+                since.as_deref(),
             )
             .await;
+            if let Ok(result_map) = &result {
+                inner.record_results("issues", result_map);
+            }
 
             Python::with_gil(|py| -> PyResult<Py<PyAny>> {
                 match result {
@@ -380,45 +1491,10 @@ impl RepoManager {
                                     let py_issue_list = PyList::empty(py);
 
                                     for issue in issues {
-                                        let issue_dict = PyDict::new(py);
-                                        issue_dict.set_item("id", issue.id)?;
-                                        issue_dict.set_item("number", issue.number)?;
-                                        issue_dict.set_item("title", &issue.title)?;
-                                        issue_dict.set_item("state", &issue.state)?;
-                                        issue_dict.set_item("created_at", &issue.created_at)?;
-                                        issue_dict.set_item("updated_at", &issue.updated_at)?;
-
-                                        if let Some(closed_at) = &issue.closed_at {
-                                            issue_dict.set_item("closed_at", closed_at)?;
-                                        } else {
-                                            issue_dict.set_item("closed_at", py.None())?;
-                                        }
-
-                                        issue_dict.set_item("user_login", &issue.user_login)?;
-                                        issue_dict.set_item("user_id", issue.user_id)?;
-
-                                        if let Some(body) = &issue.body {
-                                            issue_dict.set_item("body", body)?;
-                                        } else {
-                                            issue_dict.set_item("body", py.None())?;
+                                        let issue_dict = issue_to_dict(py, &issue, &fields)?;
+                                        if strip_markdown {
+                                            strip_body_markdown(&issue_dict, &issue.body, &fields)?;
                                         }
-
-                                        issue_dict
-                                            .set_item("comments_count", issue.comments_count)?;
-                                        issue_dict
-                                            .set_item("is_pull_request", issue.is_pull_request)?;
-                                        issue_dict.set_item("labels", &issue.labels)?;
-                                        issue_dict.set_item("assignees", &issue.assignees)?;
-
-                                        if let Some(milestone) = &issue.milestone {
-                                            issue_dict.set_item("milestone", milestone)?;
-                                        } else {
-                                            issue_dict.set_item("milestone", py.None())?;
-                                        }
-
-                                        issue_dict.set_item("locked", issue.locked)?;
-                                        issue_dict.set_item("html_url", &issue.html_url)?;
-
                                         py_issue_list.append(issue_dict)?;
                                     }
 
@@ -441,28 +1517,167 @@ impl RepoManager {
         })
     }
 
+    /// Fetches a single issue by number with one API request.
+    /// Resolves to None when GitHub reports no such issue (404).
+    #[pyo3(name = "fetch_issue", signature = (repo_url, number, fields=None))]
+    fn fetch_issue<'py>(
+        &self,
+        py: Python<'py>,
+        repo_url: String,
+        number: i32,
+        fields: Option<Vec<String>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let github_token = self.inner.github_token();
+        let api_config = self.inner.api_config.clone();
+        let fields = FieldSelection::new(fields, ISSUE_FIELDS)?;
+
+        tokio::future_into_py(py, async move {
+            let result = issues::fetch_issue(&repo_url, &github_token, &api_config, number).await;
+
+            Python::with_gil(|py| -> PyResult<Py<PyAny>> {
+                match result {
+                    Ok(Some(info)) => Ok(issue_to_dict(py, &info, &fields)?.into_any().unbind()),
+                    Ok(None) => Ok(py.None()),
+                    Err(err_string) => {
+                        Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(err_string))
+                    }
+                }
+            })
+        })
+    }
+
+    /// Fetches the timeline events (labeled, closed, assigned, ...) of one issue or pull
+    /// request. Returns a list of `{event, actor_login, created_at, label, assignee}` dicts.
+    #[pyo3(name = "fetch_issue_events")]
+    fn fetch_issue_events<'py>(
+        &self,
+        py: Python<'py>,
+        repo_url: String,
+        number: i32,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let github_token = self.inner.github_token();
+        let api_config = self.inner.api_config.clone();
+
+        tokio::future_into_py(py, async move {
+            let result =
+                issues::fetch_issue_events(&repo_url, &github_token, &api_config, number).await;
+
+            Python::with_gil(|py| -> PyResult<Py<PyAny>> {
+                match result {
+                    Ok(events) => {
+                        let py_events = PyList::empty(py);
+                        for event in &events {
+                            let dict = PyDict::new(py);
+                            dict.set_item("event", &event.event)?;
+                            dict.set_item("actor_login", &event.actor_login)?;
+                            dict.set_item("created_at", &event.created_at)?;
+                            dict.set_item("label", &event.label)?;
+                            dict.set_item("assignee", &event.assignee)?;
+                            py_events.append(dict)?;
+                        }
+                        Ok(py_events.into_any().unbind())
+                    }
+                    Err(err_string) => {
+                        Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(err_string))
+                    }
+                }
+            })
+        })
+    }
+
+    /// Searches issues and pull requests across repositories with GitHub's search syntax.
+    /// Returns `{"items", "total_count", "truncated"}`; search returns at most 1000 results.
+    #[pyo3(name = "search_issues", signature = (query, max_pages=None, fields=None))]
+    fn search_issues<'py>(
+        &self,
+        py: Python<'py>,
+        query: String,
+        max_pages: Option<usize>,
+        fields: Option<Vec<String>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let github_token = self.inner.github_token();
+        let api_config = self.inner.api_config.clone();
+        let fields = FieldSelection::new(fields, ISSUE_FIELDS)?;
+
+        tokio::future_into_py(py, async move {
+            let result = issues::search_issues(&query, &github_token, &api_config, max_pages).await;
+
+            Python::with_gil(|py| -> PyResult<Py<PyAny>> {
+                match result {
+                    Ok(search) => {
+                        let py_items = PyList::empty(py);
+                        for issue in &search.items {
+                            py_items.append(issue_to_dict(py, issue, &fields)?)?;
+                        }
+                        let dict = PyDict::new(py);
+                        dict.set_item("items", py_items)?;
+                        dict.set_item("total_count", search.total_count)?;
+                        dict.set_item("truncated", search.truncated)?;
+                        Ok(dict.into())
+                    }
+                    Err(err_string) => {
+                        Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(err_string))
+                    }
+                }
+            })
+        })
+    }
+
     /// Fetches pull request information for multiple repositories.
-    #[pyo3(name = "fetch_pull_requests")]
+    /// `fields` optionally restricts the keys emitted in each pull request dict.
+    /// `use_graphql` fetches each page in one GraphQL query instead of a REST call per PR.
+    /// `strip_markdown` turns bodies into plain text, keeping the original as "raw_body".
+    /// `fetch_details=false` skips the per-PR detail request (REST only); see the Python docs
+    /// for which fields are then left empty.
+    /// `max_concurrent_details` caps detail requests in flight across all repositories.
+    #[pyo3(name = "fetch_pull_requests", signature = (repo_urls, state=None, max_pages=None, fields=None, use_graphql=false, strip_markdown=false, fetch_details=true, max_concurrent_details=None))]
+    #[allow(clippy::too_many_arguments)] // Each keyword argument maps to a Python parameter
     fn fetch_pull_requests<'py>(
         &self,
         py: Python<'py>,
         repo_urls: Vec<String>,
         state: Option<String>,
         max_pages: Option<usize>,
+        fields: Option<Vec<String>>,
+        use_graphql: bool,
+        strip_markdown: bool,
+        fetch_details: bool,
+        max_concurrent_details: Option<usize>, // None uses the default of 8
     ) -> PyResult<Bound<'py, PyAny>> {
         // Use the existing credentials from the RepoManager
         let github_username = self.inner.github_username.clone();
-        let github_token = self.inner.github_token.clone();
+        let github_token = self.inner.github_token();
+        let api_config = self.inner.api_config.clone();
+        let fields = FieldSelection::new(fields, PULL_REQUEST_FIELDS)?;
+        let max_concurrent_details =
+            max_concurrent_details.unwrap_or(pull_requests::DEFAULT_MAX_CONCURRENT_DETAILS);
+        if max_concurrent_details == 0 {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "max_concurrent_details must be at least 1",
+            ));
+        }
+        let options = pull_requests::PullRequestFetchOptions {
+            use_graphql,
+            // No need for a request per PR when none of the fields it fills are wanted
+            fetch_details: fetch_details && fields.includes_any(PULL_REQUEST_DETAIL_FIELDS),
+            max_concurrent_details,
+        };
+        let inner = Arc::clone(&self.inner);
 
         tokio::future_into_py(py, async move {
             let result = pull_requests::fetch_pull_requests(
                 repo_urls,
                 &github_username,
                 &github_token,
+                &api_config,
                 state.as_deref(),
                 max_pages,
+                &options,
             )
             .await;
+            if let Ok(result_map) = &result {
+                inner.record_results("pull_requests", result_map);
+            }
 
             Python::with_gil(|py| -> PyResult<Py<PyAny>> {
                 match result {
@@ -475,61 +1690,110 @@ impl RepoManager {
                                     let py_pr_list = PyList::empty(py);
 
                                     for pr in prs {
-                                        let pr_dict = PyDict::new(py);
-                                        pr_dict.set_item("id", pr.id)?;
-                                        pr_dict.set_item("number", pr.number)?;
-                                        pr_dict.set_item("title", &pr.title)?;
-                                        pr_dict.set_item("state", &pr.state)?;
-                                        pr_dict.set_item("created_at", &pr.created_at)?;
-                                        pr_dict.set_item("updated_at", &pr.updated_at)?;
-
-                                        if let Some(closed_at) = &pr.closed_at {
-                                            pr_dict.set_item("closed_at", closed_at)?;
-                                        } else {
-                                            pr_dict.set_item("closed_at", py.None())?;
+                                        let pr_dict = pull_request_to_dict(py, &pr, &fields)?;
+                                        if strip_markdown {
+                                            strip_body_markdown(&pr_dict, &pr.body, &fields)?;
                                         }
+                                        py_pr_list.append(pr_dict)?;
+                                    }
 
-                                        if let Some(merged_at) = &pr.merged_at {
-                                            pr_dict.set_item("merged_at", merged_at)?;
-                                        } else {
-                                            pr_dict.set_item("merged_at", py.None())?;
-                                        }
+                                    py_result_dict.set_item(repo_url, py_pr_list)?;
+                                }
+                                Err(error) => {
+                                    // Store error message
+                                    py_result_dict.set_item(repo_url, error)?;
+                                }
+                            }
+                        }
 
-                                        pr_dict.set_item("user_login", &pr.user_login)?;
-                                        pr_dict.set_item("user_id", pr.user_id)?;
+                        Ok(py_result_dict.into())
+                    }
+                    Err(err_string) => {
+                        Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(err_string))
+                    }
+                }
+            })
+        })
+    }
 
-                                        if let Some(body) = &pr.body {
-                                            pr_dict.set_item("body", body)?;
-                                        } else {
-                                            pr_dict.set_item("body", py.None())?;
-                                        }
+    /// Fetches a single pull request by number with one API request.
+    /// Resolves to None when GitHub reports no such pull request (404).
+    #[pyo3(name = "fetch_pull_request", signature = (repo_url, number, fields=None))]
+    fn fetch_pull_request<'py>(
+        &self,
+        py: Python<'py>,
+        repo_url: String,
+        number: i32,
+        fields: Option<Vec<String>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let github_token = self.inner.github_token();
+        let api_config = self.inner.api_config.clone();
+        let fields = FieldSelection::new(fields, PULL_REQUEST_FIELDS)?;
 
-                                        pr_dict.set_item("comments", pr.comments)?;
-                                        pr_dict.set_item("commits", pr.commits)?;
-                                        pr_dict.set_item("additions", pr.additions)?;
-                                        pr_dict.set_item("deletions", pr.deletions)?;
-                                        pr_dict.set_item("changed_files", pr.changed_files)?;
+        tokio::future_into_py(py, async move {
+            let result =
+                pull_requests::fetch_pull_request(&repo_url, &github_token, &api_config, number)
+                    .await;
 
-                                        if let Some(mergeable) = pr.mergeable {
-                                            pr_dict.set_item("mergeable", mergeable)?;
-                                        } else {
-                                            pr_dict.set_item("mergeable", py.None())?;
-                                        }
+            Python::with_gil(|py| -> PyResult<Py<PyAny>> {
+                match result {
+                    Ok(Some(info)) => Ok(pull_request_to_dict(py, &info, &fields)?
+                        .into_any()
+                        .unbind()),
+                    Ok(None) => Ok(py.None()),
+                    Err(err_string) => {
+                        Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(err_string))
+                    }
+                }
+            })
+        })
+    }
+
+    /// Fetches code review information for multiple repositories.
+    #[pyo3(name = "fetch_code_reviews")]
+    fn fetch_code_reviews<'py>(
+        &self,
+        py: Python<'py>,
+        repo_urls: Vec<String>,
+        max_pages: Option<usize>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        // Use the existing credentials from the RepoManager
+        let github_username = self.inner.github_username.clone();
+        let github_token = self.inner.github_token();
+        let api_config = self.inner.api_config.clone();
 
-                                        pr_dict.set_item("labels", &pr.labels)?;
-                                        pr_dict.set_item("is_draft", pr.draft)?;
-                                        pr_dict.set_item("merged", pr.merged)?;
+        tokio::future_into_py(py, async move {
+            let result = code_review::fetch_code_reviews(
+                repo_urls,
+                &github_username,
+                &github_token,
+                &api_config,
+                max_pages,
+            )
+            .await;
 
-                                        if let Some(merged_by) = &pr.merged_by {
-                                            pr_dict.set_item("merged_by", merged_by)?;
-                                        } else {
-                                            pr_dict.set_item("merged_by", py.None())?;
+            Python::with_gil(|py| -> PyResult<Py<PyAny>> {
+                match result {
+                    Ok(reviews_map) => {
+                        let py_result_dict = PyDict::new(py);
+
+                        for (repo_url, result) in reviews_map {
+                            match result {
+                                Ok(pr_reviews) => {
+                                    let py_pr_reviews_dict = PyDict::new(py);
+
+                                    for (pr_number, reviews) in pr_reviews {
+                                        let py_reviews_list = PyList::empty(py);
+
+                                        for review in reviews {
+                                            py_reviews_list.append(review_to_dict(py, &review)?)?;
                                         }
 
-                                        py_pr_list.append(pr_dict)?;
+                                        py_pr_reviews_dict
+                                            .set_item(pr_number.to_string(), py_reviews_list)?;
                                     }
 
-                                    py_result_dict.set_item(repo_url, py_pr_list)?;
+                                    py_result_dict.set_item(repo_url, py_pr_reviews_dict)?;
                                 }
                                 Err(error) => {
                                     // Store error message
@@ -538,7 +1802,137 @@ impl RepoManager {
                             }
                         }
 
-                        Ok(py_result_dict.into())
+                        Ok(py_result_dict.into())
+                    }
+                    Err(err_string) => {
+                        Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(err_string))
+                    }
+                }
+            })
+        })
+    }
+
+    /// Fetches the reviews and pending review requests for a single pull request.
+    /// Returns a dict with `reviews`, `requested_reviewers` and `requested_teams`.
+    #[pyo3(name = "fetch_pull_request_reviews")]
+    fn fetch_pull_request_reviews<'py>(
+        &self,
+        py: Python<'py>,
+        repo_url: String,
+        number: i32,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let github_token = self.inner.github_token();
+        let api_config = self.inner.api_config.clone();
+
+        tokio::future_into_py(py, async move {
+            let result = pull_requests::fetch_pull_request_reviews(
+                &repo_url,
+                &github_token,
+                &api_config,
+                number,
+            )
+            .await;
+
+            Python::with_gil(|py| -> PyResult<Py<PyAny>> {
+                match result {
+                    Ok(pr_reviews) => {
+                        let py_reviews_list = PyList::empty(py);
+                        for review in &pr_reviews.reviews {
+                            py_reviews_list.append(review_to_dict(py, review)?)?;
+                        }
+
+                        let dict = PyDict::new(py);
+                        dict.set_item("reviews", py_reviews_list)?;
+                        dict.set_item("requested_reviewers", &pr_reviews.requested_reviewers)?;
+                        dict.set_item("requested_teams", &pr_reviews.requested_teams)?;
+                        Ok(dict.into())
+                    }
+                    Err(err_string) => {
+                        Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(err_string))
+                    }
+                }
+            })
+        })
+    }
+
+    /// Fetches the files changed by a single pull request.
+    #[pyo3(name = "fetch_pull_request_files")]
+    fn fetch_pull_request_files<'py>(
+        &self,
+        py: Python<'py>,
+        repo_url: String,
+        number: i32,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let github_token = self.inner.github_token();
+        let api_config = self.inner.api_config.clone();
+
+        tokio::future_into_py(py, async move {
+            let result =
+                pull_requests::fetch_pr_files(&repo_url, &github_token, &api_config, number).await;
+
+            Python::with_gil(|py| -> PyResult<Py<PyAny>> {
+                match result {
+                    Ok(files) => {
+                        let py_files_list = PyList::empty(py);
+                        for file in &files {
+                            let file_dict = PyDict::new(py);
+                            file_dict.set_item("filename", &file.filename)?;
+                            file_dict.set_item("status", &file.status)?;
+                            file_dict.set_item("additions", file.additions)?;
+                            file_dict.set_item("deletions", file.deletions)?;
+                            file_dict.set_item("changes", file.changes)?;
+                            file_dict.set_item("patch", &file.patch)?;
+                            py_files_list.append(file_dict)?;
+                        }
+                        Ok(py_files_list.into())
+                    }
+                    Err(err_string) => {
+                        Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(err_string))
+                    }
+                }
+            })
+        })
+    }
+
+    /// Fetches the inline review comments (comments on lines of the diff) of a pull request.
+    /// `line` is None for outdated comments; replies carry the id they answer in `in_reply_to_id`.
+    #[pyo3(name = "fetch_pull_request_review_comments")]
+    fn fetch_pull_request_review_comments<'py>(
+        &self,
+        py: Python<'py>,
+        repo_url: String,
+        number: i32,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let github_token = self.inner.github_token();
+        let api_config = self.inner.api_config.clone();
+
+        tokio::future_into_py(py, async move {
+            let result = pull_requests::fetch_pr_review_comments(
+                &repo_url,
+                &github_token,
+                &api_config,
+                number,
+            )
+            .await;
+
+            Python::with_gil(|py| -> PyResult<Py<PyAny>> {
+                match result {
+                    Ok(comments) => {
+                        let py_comments_list = PyList::empty(py);
+                        for comment in &comments {
+                            let comment_dict = PyDict::new(py);
+                            comment_dict.set_item("id", comment.id)?;
+                            comment_dict.set_item("user_login", &comment.user_login)?;
+                            comment_dict.set_item("body", &comment.body)?;
+                            comment_dict.set_item("path", &comment.path)?;
+                            comment_dict.set_item("line", comment.line)?;
+                            comment_dict.set_item("original_line", comment.original_line)?;
+                            comment_dict.set_item("commit_id", &comment.commit_id)?;
+                            comment_dict.set_item("created_at", &comment.created_at)?;
+                            comment_dict.set_item("in_reply_to_id", comment.in_reply_to_id)?;
+                            py_comments_list.append(comment_dict)?;
+                        }
+                        Ok(py_comments_list.into())
                     }
                     Err(err_string) => {
                         Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(err_string))
@@ -548,77 +1942,41 @@ impl RepoManager {
         })
     }
 
-    /// Fetches code review information for multiple repositories.
-    #[pyo3(name = "fetch_code_reviews")]
-    fn fetch_code_reviews<'py>(
+    /// Fetches the commits on a single pull request.
+    /// Returns {"commits": [...], "truncated": bool}; GitHub lists at most 250 commits.
+    #[pyo3(name = "fetch_pull_request_commits")]
+    fn fetch_pull_request_commits<'py>(
         &self,
         py: Python<'py>,
-        repo_urls: Vec<String>,
-        max_pages: Option<usize>,
+        repo_url: String,
+        number: i32,
     ) -> PyResult<Bound<'py, PyAny>> {
-        // Use the existing credentials from the RepoManager
-        let github_username = self.inner.github_username.clone();
-        let github_token = self.inner.github_token.clone();
+        let github_token = self.inner.github_token();
+        let api_config = self.inner.api_config.clone();
 
         tokio::future_into_py(py, async move {
-            let result = code_review::fetch_code_reviews(
-                repo_urls,
-                &github_username,
-                &github_token,
-                max_pages,
-            )
-            .await;
+            let result =
+                pull_requests::fetch_pr_commits(&repo_url, &github_token, &api_config, number)
+                    .await;
 
             Python::with_gil(|py| -> PyResult<Py<PyAny>> {
                 match result {
-                    Ok(reviews_map) => {
-                        let py_result_dict = PyDict::new(py);
-
-                        for (repo_url, result) in reviews_map {
-                            match result {
-                                Ok(pr_reviews) => {
-                                    let py_pr_reviews_dict = PyDict::new(py);
-
-                                    for (pr_number, reviews) in pr_reviews {
-                                        let py_reviews_list = PyList::empty(py);
-
-                                        for review in reviews {
-                                            let review_dict = PyDict::new(py);
-                                            review_dict.set_item("id", review.id)?;
-                                            review_dict.set_item("pr_number", review.pr_number)?;
-                                            review_dict
-                                                .set_item("user_login", &review.user_login)?;
-                                            review_dict.set_item("user_id", review.user_id)?;
-
-                                            if let Some(body) = &review.body {
-                                                review_dict.set_item("body", body)?;
-                                            } else {
-                                                review_dict.set_item("body", py.None())?;
-                                            }
-
-                                            review_dict.set_item("state", &review.state)?;
-                                            review_dict
-                                                .set_item("submitted_at", &review.submitted_at)?;
-                                            review_dict.set_item("commit_id", &review.commit_id)?;
-                                            review_dict.set_item("html_url", &review.html_url)?;
-
-                                            py_reviews_list.append(review_dict)?;
-                                        }
-
-                                        py_pr_reviews_dict
-                                            .set_item(pr_number.to_string(), py_reviews_list)?;
-                                    }
-
-                                    py_result_dict.set_item(repo_url, py_pr_reviews_dict)?;
-                                }
-                                Err(error) => {
-                                    // Store error message
-                                    py_result_dict.set_item(repo_url, error)?;
-                                }
-                            }
+                    Ok(pr_commits) => {
+                        let py_commits_list = PyList::empty(py);
+                        for commit in &pr_commits.commits {
+                            let commit_dict = PyDict::new(py);
+                            commit_dict.set_item("sha", &commit.sha)?;
+                            commit_dict.set_item("message", &commit.message)?;
+                            commit_dict.set_item("author_login", &commit.author_login)?;
+                            commit_dict.set_item("author_email", &commit.author_email)?;
+                            commit_dict.set_item("authored_at", &commit.authored_at)?;
+                            py_commits_list.append(commit_dict)?;
                         }
 
-                        Ok(py_result_dict.into())
+                        let dict = PyDict::new(py);
+                        dict.set_item("commits", py_commits_list)?;
+                        dict.set_item("truncated", pr_commits.truncated)?;
+                        Ok(dict.into())
                     }
                     Err(err_string) => {
                         Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(err_string))
@@ -639,7 +1997,8 @@ impl RepoManager {
     ) -> PyResult<Bound<'py, PyAny>> {
         // Use the existing credentials from the RepoManager
         let github_username = self.inner.github_username.clone();
-        let github_token = self.inner.github_token.clone();
+        let github_token = self.inner.github_token();
+        let api_config = self.inner.api_config.clone();
 
         // Convert string comment types to CommentType enum if provided
         let types_enum = match comment_types {
@@ -672,6 +2031,7 @@ impl RepoManager {
                 repo_urls,
                 &github_username,
                 &github_token,
+                &api_config,
                 types_enum,
                 max_pages,
             )
@@ -688,73 +2048,7 @@ impl RepoManager {
                                     let py_comments_list = PyList::empty(py);
 
                                     for comment in comments {
-                                        let comment_dict = PyDict::new(py);
-                                        comment_dict.set_item("id", comment.id)?;
-
-                                        // Convert enum to string for Python
-                                        let comment_type = match comment.comment_type {
-                                            comments::CommentType::Issue => "issue",
-                                            comments::CommentType::Commit => "commit",
-                                            comments::CommentType::PullRequest => "pull_request",
-                                            comments::CommentType::ReviewComment => {
-                                                "review_comment"
-                                            }
-                                        };
-                                        comment_dict.set_item("comment_type", comment_type)?;
-
-                                        comment_dict.set_item("user_login", &comment.user_login)?;
-                                        comment_dict.set_item("user_id", comment.user_id)?;
-                                        comment_dict.set_item("body", &comment.body)?;
-                                        comment_dict.set_item("created_at", &comment.created_at)?;
-                                        comment_dict.set_item("updated_at", &comment.updated_at)?;
-                                        comment_dict.set_item("html_url", &comment.html_url)?;
-
-                                        // Handle optional fields
-                                        if let Some(issue_number) = comment.issue_number {
-                                            comment_dict.set_item("issue_number", issue_number)?;
-                                        } else {
-                                            comment_dict.set_item("issue_number", py.None())?;
-                                        }
-
-                                        if let Some(pr_number) = comment.pull_request_number {
-                                            comment_dict
-                                                .set_item("pull_request_number", pr_number)?;
-                                        } else {
-                                            comment_dict
-                                                .set_item("pull_request_number", py.None())?;
-                                        }
-
-                                        if let Some(commit_id) = &comment.commit_id {
-                                            comment_dict.set_item("commit_id", commit_id)?;
-                                        } else {
-                                            comment_dict.set_item("commit_id", py.None())?;
-                                        }
-
-                                        if let Some(path) = &comment.path {
-                                            comment_dict.set_item("path", path)?;
-                                        } else {
-                                            comment_dict.set_item("path", py.None())?;
-                                        }
-
-                                        if let Some(position) = comment.position {
-                                            comment_dict.set_item("position", position)?;
-                                        } else {
-                                            comment_dict.set_item("position", py.None())?;
-                                        }
-
-                                        if let Some(line) = comment.line {
-                                            comment_dict.set_item("line", line)?;
-                                        } else {
-                                            comment_dict.set_item("line", py.None())?;
-                                        }
-
-                                        if let Some(commit_sha) = &comment.commit_sha {
-                                            comment_dict.set_item("commit_sha", commit_sha)?;
-                                        } else {
-                                            comment_dict.set_item("commit_sha", py.None())?;
-                                        }
-
-                                        py_comments_list.append(comment_dict)?;
+                                        py_comments_list.append(comment_to_dict(py, &comment)?)?;
                                     }
 
                                     py_result_dict.set_item(repo_url, py_comments_list)?;
@@ -776,6 +2070,403 @@ impl RepoManager {
         })
     }
 
+    /// Fetches the comments on a single issue.
+    /// Returns a list of comment dicts (empty if the issue has no comments).
+    #[pyo3(name = "fetch_issue_comments", signature = (repo_url, issue_number, max_pages=None))]
+    fn fetch_issue_comments<'py>(
+        &self,
+        py: Python<'py>,
+        repo_url: String,
+        issue_number: i32,
+        max_pages: Option<usize>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let github_token = self.inner.github_token();
+        let api_config = self.inner.api_config.clone();
+
+        tokio::future_into_py(py, async move {
+            let result = comments::fetch_issue_comments(
+                &repo_url,
+                &github_token,
+                &api_config,
+                issue_number,
+                max_pages,
+            )
+            .await;
+
+            Python::with_gil(|py| -> PyResult<Py<PyAny>> {
+                match result {
+                    Ok(comments) => {
+                        let py_comments_list = PyList::empty(py);
+                        for comment in comments {
+                            py_comments_list.append(comment_to_dict(py, &comment)?)?;
+                        }
+                        Ok(py_comments_list.into())
+                    }
+                    Err(err_string) => {
+                        Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(err_string))
+                    }
+                }
+            })
+        })
+    }
+
+    /// Returns headline numbers for a cloned repository.
+    /// API-derived values are None when the GitHub API is unavailable for the URL.
+    #[pyo3(name = "summary")]
+    fn summary<'py>(&self, py: Python<'py>, repo_url: String) -> PyResult<Bound<'py, PyAny>> {
+        let inner = Arc::clone(&self.inner);
+
+        tokio::future_into_py(py, async move {
+            let result = match inner.get_repo_path(&repo_url) {
+                Ok(path) => {
+                    summary::build_summary(
+                        &repo_url,
+                        path,
+                        &inner.github_token(),
+                        &inner.api_config,
+                    )
+                    .await
+                }
+                Err(e) => Err(e),
+            };
+
+            Python::with_gil(|py| -> PyResult<Py<PyAny>> {
+                match result {
+                    Ok(summary) => {
+                        let dict = PyDict::new(py);
+                        dict.set_item("commit_count", summary.commit_count)?;
+                        dict.set_item("contributor_count", summary.contributor_count)?;
+                        dict.set_item("branch_count", summary.branch_count)?;
+                        dict.set_item("open_issues", summary.open_issues)?;
+                        dict.set_item("open_prs", summary.open_prs)?;
+                        dict.set_item("default_branch", &summary.default_branch)?;
+                        dict.set_item("last_commit_date", summary.last_commit_date)?;
+                        dict.set_item("primary_language", &summary.primary_language)?;
+                        Ok(dict.into())
+                    }
+                    Err(err_string) => {
+                        Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(err_string))
+                    }
+                }
+            })
+        })
+    }
+
+    /// Gathers commits, branches, collaborators, issues and pull requests of a cloned
+    /// repository concurrently. Returns a dict keyed by section; a section that failed holds
+    /// its error string instead of a list.
+    #[pyo3(name = "fetch_repo_report")]
+    fn fetch_repo_report<'py>(
+        &self,
+        py: Python<'py>,
+        repo_url: String,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let inner = Arc::clone(&self.inner);
+
+        tokio::future_into_py(py, async move {
+            let result = inner.fetch_repo_report(&repo_url).await;
+
+            Python::with_gil(|py| -> PyResult<Py<PyAny>> {
+                let report = match result {
+                    Ok(report) => report,
+                    Err(err_string) => {
+                        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(err_string))
+                    }
+                };
+                let all = FieldSelection(None);
+                let dict = PyDict::new(py);
+                dict.set_item(
+                    "commits",
+                    report_section(py, &report.commits, |c| commit_to_dict(py, c, &all))?,
+                )?;
+                dict.set_item(
+                    "branches",
+                    report_section(py, &report.branches, |b| branch_to_dict(py, b))?,
+                )?;
+                dict.set_item(
+                    "collaborators",
+                    report_section(py, &report.collaborators, |c| collaborator_to_dict(py, c))?,
+                )?;
+                dict.set_item(
+                    "issues",
+                    report_section(py, &report.issues, |i| issue_to_dict(py, i, &all))?,
+                )?;
+                dict.set_item(
+                    "pull_requests",
+                    report_section(py, &report.pull_requests, |p| {
+                        pull_request_to_dict(py, p, &all)
+                    })?,
+                )?;
+                Ok(dict.into())
+            })
+        })
+    }
+
+    /// Lists the tracked files of a cloned repository, like `git ls-files`.
+    /// `glob` (e.g. "src/**/*.py") keeps only matching paths.
+    #[pyo3(name = "list_files", signature = (repo_url, glob=None))]
+    fn list_files<'py>(
+        &self,
+        py: Python<'py>,
+        repo_url: String,
+        glob: Option<String>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let inner = Arc::clone(&self.inner);
+
+        tokio::future_into_py(py, async move {
+            let result = match inner.get_repo_path(&repo_url) {
+                Ok(path) => {
+                    ::tokio::task::spawn_blocking(move || files::list_files(&path, glob.as_deref()))
+                        .await
+                        .unwrap_or_else(|e| Err(format!("Task execution failed: {}", e)))
+                }
+                Err(e) => Err(e),
+            };
+
+            Python::with_gil(|py| -> PyResult<Py<PyAny>> {
+                match result {
+                    Ok(paths) => Ok(PyList::new(py, paths)?.into()),
+                    Err(err_string) => {
+                        Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(err_string))
+                    }
+                }
+            })
+        })
+    }
+
+    /// Scans committed files of a cloned repository for merge conflict markers.
+    /// Returns {path: [line_numbers]} for files containing markers; `paths` limits the scan.
+    #[pyo3(name = "find_conflict_markers", signature = (repo_url, paths=None))]
+    fn find_conflict_markers<'py>(
+        &self,
+        py: Python<'py>,
+        repo_url: String,
+        paths: Option<Vec<String>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let inner = Arc::clone(&self.inner);
+
+        tokio::future_into_py(py, async move {
+            let result = match inner.get_repo_path(&repo_url) {
+                Ok(path) => ::tokio::task::spawn_blocking(move || {
+                    files::find_conflict_markers(&path, paths)
+                })
+                .await
+                .unwrap_or_else(|e| Err(format!("Task execution failed: {}", e))),
+                Err(e) => Err(e),
+            };
+
+            Python::with_gil(|py| -> PyResult<Py<PyAny>> {
+                match result {
+                    Ok(markers) => {
+                        let py_result_dict = PyDict::new(py);
+                        for (path, lines) in markers {
+                            py_result_dict.set_item(path, lines)?;
+                        }
+                        Ok(py_result_dict.into())
+                    }
+                    Err(err_string) => {
+                        Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(err_string))
+                    }
+                }
+            })
+        })
+    }
+
+    /// Reports line endings and encoding of committed files in a cloned repository.
+    /// Returns {path: {has_crlf, is_utf8, has_bom, byte_count}}.
+    #[pyo3(name = "file_encoding_report")]
+    fn file_encoding_report<'py>(
+        &self,
+        py: Python<'py>,
+        repo_url: String,
+        file_paths: Vec<String>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let inner = Arc::clone(&self.inner);
+
+        tokio::future_into_py(py, async move {
+            let result = match inner.get_repo_path(&repo_url) {
+                Ok(path) => ::tokio::task::spawn_blocking(move || {
+                    files::file_encoding_report(&path, &file_paths)
+                })
+                .await
+                .unwrap_or_else(|e| Err(format!("Task execution failed: {}", e))),
+                Err(e) => Err(e),
+            };
+
+            Python::with_gil(|py| -> PyResult<Py<PyAny>> {
+                match result {
+                    Ok(report) => {
+                        let py_result_dict = PyDict::new(py);
+                        for (path, encoding) in report {
+                            let dict = PyDict::new(py);
+                            dict.set_item("has_crlf", encoding.has_crlf)?;
+                            dict.set_item("is_utf8", encoding.is_utf8)?;
+                            dict.set_item("has_bom", encoding.has_bom)?;
+                            dict.set_item("byte_count", encoding.byte_count)?;
+                            py_result_dict.set_item(path, dict)?;
+                        }
+                        Ok(py_result_dict.into())
+                    }
+                    Err(err_string) => {
+                        Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(err_string))
+                    }
+                }
+            })
+        })
+    }
+
+    /// Checks that each repository exists and is accessible before cloning it.
+    /// Returns a dict of URL to `{exists, private, permission, default_branch}`,
+    /// or to an error string (e.g. when access is forbidden).
+    #[pyo3(name = "check_access")]
+    fn check_access<'py>(
+        &self,
+        py: Python<'py>,
+        repo_urls: Vec<String>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let github_token = self.inner.github_token();
+        let api_config = self.inner.api_config.clone();
+
+        tokio::future_into_py(py, async move {
+            let result_map = access::check_access(repo_urls, &github_token, &api_config).await;
+
+            Python::with_gil(|py| -> PyResult<Py<PyAny>> {
+                let py_result_dict = PyDict::new(py);
+                for (repo_url, result) in result_map {
+                    match result {
+                        Ok(info) => {
+                            let dict = PyDict::new(py);
+                            dict.set_item("exists", info.exists)?;
+                            dict.set_item("private", info.private)?;
+                            dict.set_item("permission", &info.permission)?;
+                            dict.set_item("default_branch", &info.default_branch)?;
+                            py_result_dict.set_item(repo_url, dict)?;
+                        }
+                        Err(error) => py_result_dict.set_item(repo_url, error)?,
+                    }
+                }
+                Ok(py_result_dict.into())
+            })
+        })
+    }
+
+    /// Lists the repositories of an organization (or user), optionally only those whose
+    /// name starts with `name_prefix`. Returns `{name, full_name, clone_url, private}` dicts.
+    #[pyo3(name = "list_org_repos", signature = (org, name_prefix=None))]
+    fn list_org_repos<'py>(
+        &self,
+        py: Python<'py>,
+        org: String,
+        name_prefix: Option<String>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let github_token = self.inner.github_token();
+        let api_config = self.inner.api_config.clone();
+
+        tokio::future_into_py(py, async move {
+            let result = org_repos::list_owner_repos(
+                &org,
+                name_prefix.as_deref(),
+                &github_token,
+                &api_config,
+            )
+            .await;
+
+            Python::with_gil(|py| -> PyResult<Py<PyAny>> {
+                match result {
+                    Ok(repos) => {
+                        let py_repos = PyList::empty(py);
+                        for repo in &repos {
+                            let dict = PyDict::new(py);
+                            dict.set_item("name", &repo.name)?;
+                            dict.set_item("full_name", &repo.full_name)?;
+                            dict.set_item("clone_url", &repo.clone_url)?;
+                            dict.set_item("private", repo.private)?;
+                            py_repos.append(dict)?;
+                        }
+                        Ok(py_repos.into_any().unbind())
+                    }
+                    Err(err_string) => {
+                        Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(err_string))
+                    }
+                }
+            })
+        })
+    }
+
+    /// Returns the core GitHub API rate limit status for the manager's token.
+    /// Returns None instead of raising when the status can't be retrieved.
+    #[pyo3(name = "get_rate_limit_status")]
+    fn get_rate_limit_status<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let github_token = self.inner.github_token();
+        let api_config = self.inner.api_config.clone();
+
+        tokio::future_into_py(py, async move {
+            let result = rate_limit::fetch_rate_limit(&github_token, &api_config).await;
+
+            Python::with_gil(|py| -> PyResult<Py<PyAny>> {
+                match result {
+                    Ok(info) => {
+                        let dict = PyDict::new(py);
+                        dict.set_item("limit", info.limit)?;
+                        dict.set_item("remaining", info.remaining)?;
+                        dict.set_item("reset_time", info.reset)?;
+                        dict.set_item("resource", &info.resource)?;
+                        Ok(dict.into())
+                    }
+                    Err(err_string) => {
+                        eprintln!("Warning: Failed to fetch rate limit status: {}", err_string);
+                        Ok(py.None())
+                    }
+                }
+            })
+        })
+    }
+
+    /// Returns aggregate commit statistics for a cloned repository.
+    /// `path` limits the summary to commits and lines under that file or directory.
+    /// Raises ValueError if the repository hasn't finished cloning.
+    #[pyo3(name = "commit_summary", signature = (repo_url, path=None))]
+    fn commit_summary<'py>(
+        &self,
+        py: Python<'py>,
+        repo_url: String,
+        path: Option<String>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let inner = Arc::clone(&self.inner);
+        let options = CommitAnalysisOptions {
+            path,
+            ..Default::default()
+        };
+
+        tokio::future_into_py(py, async move {
+            let result = match inner.get_repo_path(&repo_url) {
+                Ok(repo_path) => inner
+                    .get_commit_analysis(&repo_path, options)
+                    .await
+                    .map(|commits| commits::summarize_commits(&commits)),
+                Err(e) => Err(e),
+            };
+
+            Python::with_gil(|py| -> PyResult<Py<PyAny>> {
+                match result {
+                    Ok(summary) => {
+                        let dict = PyDict::new(py);
+                        dict.set_item("total_commits", summary.total_commits)?;
+                        dict.set_item("total_additions", summary.total_additions)?;
+                        dict.set_item("total_deletions", summary.total_deletions)?;
+                        dict.set_item("first_commit_timestamp", summary.first_commit_timestamp)?;
+                        dict.set_item("last_commit_timestamp", summary.last_commit_timestamp)?;
+                        dict.set_item("unique_authors", summary.unique_authors)?;
+                        Ok(dict.into())
+                    }
+                    Err(err_string) => {
+                        Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(err_string))
+                    }
+                }
+            })
+        })
+    }
+
     /// Analyzes branches in cloned repositories.
     #[pyo3(name = "analyze_branches")]
     fn analyze_branches<'py>(
@@ -793,7 +2484,7 @@ impl RepoManager {
                 let tasks = inner.tasks.lock().unwrap();
 
                 for url in &repo_urls {
-                    if let Some(task) = tasks.get(url) {
+                    if let Some(task) = tasks.get(&repo::canonicalize_url(url)) {
                         match &task.status {
                             InternalCloneStatus::Completed => {
                                 if let Some(path) = &task.temp_dir {
@@ -825,6 +2516,7 @@ impl RepoManager {
                 }
                 error_map
             });
+            inner.record_results("branches", &result_map);
 
             // Convert results to Python objects
             Python::with_gil(|py| -> PyResult<Py<PyAny>> {
@@ -835,24 +2527,8 @@ impl RepoManager {
                         Ok(branch_infos) => {
                             let py_branch_list = PyList::empty(py);
 
-                            for info in branch_infos {
-                                let branch_dict = PyDict::new(py);
-                                branch_dict.set_item("name", &info.name)?;
-                                branch_dict.set_item("is_remote", info.is_remote)?;
-                                branch_dict.set_item("commit_id", &info.commit_id)?;
-                                branch_dict.set_item("commit_message", &info.commit_message)?;
-                                branch_dict.set_item("author_name", &info.author_name)?;
-                                branch_dict.set_item("author_email", &info.author_email)?;
-                                branch_dict.set_item("author_time", info.author_time)?;
-                                branch_dict.set_item("is_head", info.is_head)?;
-
-                                if let Some(remote) = &info.remote_name {
-                                    branch_dict.set_item("remote_name", remote)?;
-                                } else {
-                                    branch_dict.set_item("remote_name", py.None())?;
-                                }
-
-                                py_branch_list.append(branch_dict)?;
+                            for info in &branch_infos {
+                                py_branch_list.append(branch_to_dict(py, info)?)?;
                             }
 
                             py_result_dict.set_item(repo_url, py_branch_list)?;
@@ -881,6 +2557,13 @@ fn setup_async(_py: Python) -> PyResult<()> {
     Ok(())
 }
 
+// --- Exposed Python Function: markdown_to_text ---
+/// Converts a GitHub-flavoured Markdown body to plain text.
+#[pyfunction]
+fn markdown_to_text(markdown: &str) -> String {
+    text::markdown_to_text(markdown)
+}
+
 // --- Exposed Python Class: TaigaClient ---
 #[pyclass(name = "TaigaClient", module = "gradelib")]
 #[derive(Debug, Clone)]
@@ -1082,10 +2765,12 @@ fn register_taiga_module(py: Python<'_>, parent_module: &Bound<'_, PyModule>) ->
 #[pymodule]
 fn gradelib(_py: Python, m: &Bound<PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(setup_async, m)?)?;
+    m.add_function(wrap_pyfunction!(markdown_to_text, m)?)?;
     m.add_class::<RepoManager>()?; // Exposes RepoManager
     m.add_class::<ExposedCloneTask>()?; // Exposes CloneTask
     m.add_class::<ExposedCloneStatus>()?; // Exposes CloneStatus
-                                          // BlameLineInfo is not exposed as a class, only as dicts within bulk_blame result
+    m.add_class::<CloneProgressStream>()?;
+    // BlameLineInfo is not exposed as a class, only as dicts within bulk_blame result
 
     // Also expose TaigaClient directly in the root module
     m.add_class::<TaigaClient>()?;
@@ -0,0 +1,98 @@
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::api_client::{api_error, create_github_client, request_error, ApiClientConfig};
+use crate::collaborators::Permissions;
+use crate::repo::parse_repo_parts;
+
+/// Whether a repository exists and what the token may do with it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepoAccess {
+    pub exists: bool,
+    // The remaining fields are None when the repository doesn't exist
+    pub private: Option<bool>,
+    pub permission: Option<String>, // "admin", "maintain", "push", "triage" or "pull"
+    pub default_branch: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RepoResponse {
+    private: bool,
+    default_branch: String,
+    // Only present for authenticated requests
+    permissions: Option<Permissions>,
+}
+
+/// Checks that each repository exists and is accessible, with one request per URL
+///
+/// A 404 yields `exists: false`; GitHub also answers 404 for private repositories the
+/// token can't see. A 403 (token lacks access, e.g. SSO or rate limiting) is an error.
+pub async fn check_access(
+    repo_urls: Vec<String>,
+    github_token: &str,
+    api_config: &ApiClientConfig,
+) -> HashMap<String, Result<RepoAccess, String>> {
+    let client = match create_github_client(github_token, api_config) {
+        Ok(c) => c,
+        Err(e) => {
+            let err_msg = format!("Failed to create GitHub client: {}", e);
+            return repo_urls
+                .into_iter()
+                .map(|url| (url, Err(err_msg.clone())))
+                .collect();
+        }
+    };
+
+    let checks = repo_urls.into_iter().map(|url| {
+        let client = client.clone();
+        async move {
+            let result = check_repo_access(&client, &url).await;
+            (url, result)
+        }
+    });
+    join_all(checks).await.into_iter().collect()
+}
+
+/// Looks up a single repository via `GET /repos/{owner}/{repo}`
+async fn check_repo_access(client: &reqwest::Client, repo_url: &str) -> Result<RepoAccess, String> {
+    let (owner, repo) = parse_repo_parts(repo_url)?;
+    let api_url = format!("https://api.github.com/repos/{}/{}", owner, repo);
+
+    let response = client
+        .get(&api_url)
+        .send()
+        .await
+        .map_err(|e| request_error("Failed to fetch repository", e))?;
+    match response.status() {
+        reqwest::StatusCode::NOT_FOUND => {
+            return Ok(RepoAccess {
+                exists: false,
+                private: None,
+                permission: None,
+                default_branch: None,
+            })
+        }
+        reqwest::StatusCode::FORBIDDEN => {
+            return Err(format!(
+                "Access denied to {}/{} (403 Forbidden)",
+                owner, repo
+            ))
+        }
+        status if !status.is_success() => {
+            return Err(api_error("GitHub API error", response).await);
+        }
+        _ => {}
+    }
+
+    let repo_response: RepoResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse repository response: {}", e))?;
+    Ok(RepoAccess {
+        exists: true,
+        private: Some(repo_response.private),
+        permission: repo_response.permissions.map(|p| p.level().to_string()),
+        default_branch: Some(repo_response.default_branch),
+    })
+}
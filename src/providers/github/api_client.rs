@@ -0,0 +1,200 @@
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, ACCEPT, AUTHORIZATION, USER_AGENT};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Default limit for connecting to and completing a single API request
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Longest stretch of a non-JSON error body quoted in an error message
+const MAX_ERROR_BODY_CHARS: usize = 500;
+
+/// How the token is presented in the `Authorization` header
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TokenKind {
+    // Personal access token: `Authorization: token <pat>`
+    #[default]
+    Pat,
+    // OAuth or GitHub App token: `Authorization: Bearer <token>`
+    OAuth,
+}
+
+impl TokenKind {
+    /// Parses the `token_type` accepted by `RepoManager`: "pat" or "oauth"
+    pub fn parse(token_type: &str) -> Result<Self, String> {
+        match token_type.to_ascii_lowercase().as_str() {
+            "pat" => Ok(TokenKind::Pat),
+            "oauth" => Ok(TokenKind::OAuth),
+            _ => Err(format!(
+                "Invalid token_type '{}': expected 'pat' or 'oauth'",
+                token_type
+            )),
+        }
+    }
+
+    fn scheme(self) -> &'static str {
+        match self {
+            TokenKind::Pat => "token",
+            TokenKind::OAuth => "Bearer",
+        }
+    }
+}
+
+/// Settings applied to every GitHub API client
+#[derive(Debug, Clone)]
+pub struct ApiClientConfig {
+    // Applies to connecting and, separately, to each whole request
+    pub timeout: Duration,
+    // Idle connections kept per host; None uses reqwest's default
+    pub pool_max_idle_per_host: Option<usize>,
+    // HTTP(S) proxy for API requests and clones; None connects directly
+    pub proxy_url: Option<String>,
+    // Extra default headers sent with every request; a User-Agent here replaces ours
+    pub headers: HeaderMap,
+    // Authorization scheme for the token
+    pub token_kind: TokenKind,
+}
+
+impl Default for ApiClientConfig {
+    fn default() -> Self {
+        Self {
+            timeout: DEFAULT_TIMEOUT,
+            pool_max_idle_per_host: None,
+            proxy_url: None,
+            headers: HeaderMap::new(),
+            token_kind: TokenKind::default(),
+        }
+    }
+}
+
+/// Reads the proxy from `HTTPS_PROXY` (or `https_proxy`), the usual default for proxy settings
+pub fn proxy_from_env() -> Option<String> {
+    ["HTTPS_PROXY", "https_proxy"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty())
+}
+
+/// Validates user-supplied headers for `ApiClientConfig::headers`.
+/// Authorization is rejected since it would replace the token.
+pub fn parse_client_headers(headers: HashMap<String, String>) -> Result<HeaderMap, String> {
+    let mut header_map = HeaderMap::new();
+    for (name, value) in headers {
+        let header_name = HeaderName::from_bytes(name.as_bytes())
+            .map_err(|e| format!("Invalid header name '{}': {}", name, e))?;
+        if header_name == AUTHORIZATION {
+            return Err(
+                "The Authorization header is set from the token and can't be overridden"
+                    .to_string(),
+            );
+        }
+        let header_value = HeaderValue::from_str(&value)
+            .map_err(|e| format!("Invalid value for header '{}': {}", name, e))?;
+        header_map.insert(header_name, header_value);
+    }
+    Ok(header_map)
+}
+
+/// Creates a GitHub API client with proper authentication
+pub fn create_github_client(
+    token: &str,
+    config: &ApiClientConfig,
+) -> Result<reqwest::Client, String> {
+    let mut headers = HeaderMap::new();
+    // Standard GitHub API headers
+    headers.insert(
+        ACCEPT,
+        HeaderValue::from_static("application/vnd.github.v3+json"),
+    );
+    let authorization = HeaderValue::from_str(&format!("{} {}", config.token_kind.scheme(), token))
+        .map_err(|_| "GitHub token is not a valid HTTP header value".to_string())?;
+    headers.insert(AUTHORIZATION, authorization);
+    headers.insert(
+        USER_AGENT,
+        HeaderValue::from_static("gradelib-github-client/0.1.0"),
+    );
+    headers.extend(config.headers.clone());
+
+    let mut builder = reqwest::Client::builder()
+        .default_headers(headers)
+        .connect_timeout(config.timeout)
+        .timeout(config.timeout);
+    if let Some(max_idle) = config.pool_max_idle_per_host {
+        builder = builder.pool_max_idle_per_host(max_idle);
+    }
+    if let Some(proxy_url) = &config.proxy_url {
+        builder = builder.proxy(reqwest::Proxy::all(proxy_url).map_err(|e| e.to_string())?);
+    }
+    builder.build().map_err(|e| e.to_string())
+}
+
+/// Describes a failed request, calling out timeouts so callers know a retry may succeed
+pub fn request_error(context: &str, error: reqwest::Error) -> String {
+    if error.is_timeout() {
+        format!("{}: request timed out (retryable): {}", context, error)
+    } else {
+        format!("{}: {}", context, error)
+    }
+}
+
+/// GitHub's JSON error body, e.g. `{"message": "Validation Failed", "documentation_url": ...}`
+#[derive(Debug, Deserialize)]
+struct ApiErrorBody {
+    message: String,
+    documentation_url: Option<String>,
+}
+
+/// Describes a non-success response, e.g. "GitHub API error: 422 Unprocessable Entity:
+/// Validation Failed (see https://docs.github.com/...)". GitHub's `message` is quoted when the
+/// body is its usual JSON, otherwise the body itself, truncated. Consumes the response.
+pub async fn api_error(context: &str, response: reqwest::Response) -> String {
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    let detail = match serde_json::from_str::<ApiErrorBody>(&body) {
+        Ok(ApiErrorBody {
+            message,
+            documentation_url: Some(url),
+        }) => format!("{} (see {})", message, url),
+        Ok(error_body) => error_body.message,
+        Err(_) => {
+            let body = body.trim();
+            match body.char_indices().nth(MAX_ERROR_BODY_CHARS) {
+                Some((cut, _)) => format!("{}...", &body[..cut]),
+                None => body.to_string(),
+            }
+        }
+    };
+    if detail.is_empty() {
+        format!("{}: {}", context, status)
+    } else {
+        format!("{}: {}: {}", context, status, detail)
+    }
+}
+
+/// The `rel="next"` URL of a paginated response's `Link` header, e.g.
+/// `<https://api.github.com/...&page=2>; rel="next", <...&page=5>; rel="last"`.
+/// None on the last page, where GitHub omits it.
+pub fn next_page_url(headers: &HeaderMap) -> Option<String> {
+    let link = headers.get(reqwest::header::LINK)?.to_str().ok()?;
+    let mut rest = link;
+    while let Some(start) = rest.find('<') {
+        let end = start + rest[start..].find('>')?;
+        let url = &rest[start + 1..end];
+        rest = &rest[end + 1..];
+        // Parameters run up to the next link; URLs may themselves contain commas
+        let params = rest[..rest.find('<').unwrap_or(rest.len())].trim_end();
+        let params = params.strip_suffix(',').unwrap_or(params);
+        let is_next = params.split(';').any(|param| {
+            param.trim().strip_prefix("rel=").is_some_and(|rel| {
+                rel.trim()
+                    .trim_matches('"')
+                    .split_whitespace()
+                    .any(|r| r.eq_ignore_ascii_case("next"))
+            })
+        });
+        if is_next {
+            return Some(url.to_string());
+        }
+    }
+    None
+}
@@ -1,55 +1,274 @@
-use git2::{BlameOptions, Repository};
-use std::path::Path;
+use git2::{AttrCheckFlags, AttrValue, Repository};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::{
-    fs,                  // For reading file content
-    io::{self, BufRead}, // For reading file content efficiently
+    fs, // For reading file content
+    io,
 };
 
+use crate::clone::EMPTY_REPOSITORY_ERROR;
+
+/// Per-file blame result for binary files, which have no lines to attribute.
+/// The `BinaryFile:` prefix lets callers filter them out of `bulk_blame` results.
+pub const BINARY_FILE_ERROR: &str = "BinaryFile: binary files can't be blamed line by line";
+
+/// How much of a file git itself scans for a NUL byte when deciding whether it is binary
+const BINARY_SCAN_LEN: usize = 8000;
+
+/// Whether `content` is binary, by git's rules: the path is marked `binary` or `-diff` in
+/// `.gitattributes`, or a NUL byte appears near the start of the content.
+fn is_binary_file(repo: &Repository, path: &Path, content: &[u8]) -> bool {
+    let diff_attr = repo.get_attr(path, "diff", AttrCheckFlags::default());
+    if matches!(diff_attr.map(AttrValue::from_string), Ok(AttrValue::False)) {
+        return true;
+    }
+    content.iter().take(BINARY_SCAN_LEN).any(|&byte| byte == 0)
+}
+
 /// Represents information about a single line from a git blame operation.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct BlameLineInfo {
-    pub commit_id: String,    // Full commit hash
+    pub commit_id: String, // Full commit hash
     pub author_name: String,
     pub author_email: String,
-    pub orig_line_no: usize,  // 1-based original line number in the commit
+    pub author_time: i64, // Seconds since epoch when the line was last authored
+    pub author_offset: i32, // Author's timezone offset in minutes
+    pub orig_line_no: usize, // 1-based original line number in the commit
     pub final_line_no: usize, // 1-based final line number in the file
     pub line_content: String,
 }
 
-/// Performs git blame on a single file within a repository.
-/// Designed to be run synchronously, intended for use with `tokio::task::spawn_blocking`.
-pub fn get_blame_for_file(
+/// Options controlling how blame attributes lines, mirroring `git blame -w/-M/-C`.
+#[derive(Clone, Debug, Default)]
+pub struct BlameOptions {
+    pub ignore_whitespace: bool, // -w: skip whitespace-only changes
+    pub detect_moves: bool,      // -M: follow lines moved within the file
+    pub detect_copies: bool,     // -C: follow lines copied from other files in the same commit
+    pub rev: Option<String>,     // Blame as of this revision instead of the working tree
+}
+
+/// Identifies a cached blame: the commit blamed at, the file and the attribution options.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BlameCacheKey {
+    pub commit_sha: String, // HEAD, or the commit `rev` resolves to
+    pub path: String,
+    pub ignore_whitespace: bool,
+    pub detect_moves: bool,
+    pub detect_copies: bool,
+}
+
+impl BlameCacheKey {
+    pub fn new(commit_sha: &str, path: &str, options: &BlameOptions) -> Self {
+        Self {
+            commit_sha: commit_sha.to_string(),
+            path: path.to_string(),
+            ignore_whitespace: options.ignore_whitespace,
+            detect_moves: options.detect_moves,
+            detect_copies: options.detect_copies,
+        }
+    }
+}
+
+/// In-memory blame results per repository.
+/// A repository's entries are dropped as soon as its HEAD is seen to move.
+#[derive(Debug, Default)]
+pub struct BlameCache {
+    // Repository path -> (HEAD the entries were computed at, cached results)
+    repos: HashMap<PathBuf, (String, HashMap<BlameCacheKey, Vec<BlameLineInfo>>)>,
+    pub hits: usize,
+    pub misses: usize, // Each miss is one blame actually run
+}
+
+impl BlameCache {
+    /// Records the repository's current HEAD, invalidating its entries if HEAD changed.
+    pub fn sync_head(&mut self, repo_path: &Path, head_sha: &str) {
+        let entry = self
+            .repos
+            .entry(repo_path.to_path_buf())
+            .or_insert_with(|| (head_sha.to_string(), HashMap::new()));
+        if entry.0 != head_sha {
+            *entry = (head_sha.to_string(), HashMap::new());
+        }
+    }
+
+    /// Looks up a cached blame, counting the hit or miss.
+    pub fn get(&mut self, repo_path: &Path, key: &BlameCacheKey) -> Option<Vec<BlameLineInfo>> {
+        let cached = self
+            .repos
+            .get(repo_path)
+            .and_then(|(_, entries)| entries.get(key))
+            .cloned();
+        match cached {
+            Some(_) => self.hits += 1,
+            None => self.misses += 1,
+        }
+        cached
+    }
+
+    pub fn insert(&mut self, repo_path: &Path, key: BlameCacheKey, lines: Vec<BlameLineInfo>) {
+        if let Some((_, entries)) = self.repos.get_mut(repo_path) {
+            entries.insert(key, lines);
+        }
+    }
+
+    /// Number of cached file blames across all repositories.
+    pub fn len(&self) -> usize {
+        self.repos.values().map(|(_, entries)| entries.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drops every cached result and resets the counters.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Resolves the SHAs of HEAD and of the commit a blame is attributed at (`rev`, or HEAD).
+pub fn resolve_blame_commit(
     repo_path: &Path,
-    file_path_relative: &str,
-) -> Result<Vec<BlameLineInfo>, String> {
-    // 1. Open the repository
+    rev: Option<&str>,
+) -> Result<(String, String), String> {
+    let repo = Repository::open(repo_path)
+        .map_err(|e| format!("Failed to open repository at {:?}: {}", repo_path, e))?;
+    if repo.is_empty().unwrap_or(false) {
+        return Err(EMPTY_REPOSITORY_ERROR.to_string());
+    }
+    let head = repo
+        .head()
+        .and_then(|h| h.peel_to_commit())
+        .map_err(|e| format!("Failed to resolve HEAD: {}", e))?
+        .id()
+        .to_string();
+    let commit = match rev {
+        Some(rev) => repo
+            .revparse_single(rev)
+            .and_then(|obj| obj.peel_to_commit())
+            .map_err(|e| format!("Revision '{}' not found: {}", rev, e))?
+            .id()
+            .to_string(),
+        None => head.clone(),
+    };
+    Ok((head, commit))
+}
+
+/// Performs git blame on files of one repository, one after another.
+/// The repository is opened and `options.rev` resolved once for all of them, which is most of
+/// the cost of blaming a small file. `next_file` hands out the paths, so several workers can
+/// share one queue. Designed to be run synchronously, e.g. with `tokio::task::spawn_blocking`.
+pub fn blame_files(
+    repo_path: &Path,
+    mut next_file: impl FnMut() -> Option<String>,
+    options: &BlameOptions,
+) -> Vec<(String, Result<Vec<BlameLineInfo>, String>)> {
+    let opened = open_for_blame(repo_path, options);
+    let mut results = Vec::new();
+    while let Some(file_path) = next_file() {
+        let result = match &opened {
+            Ok((repo, rev_commit)) => {
+                get_blame_for_file(repo, repo_path, *rev_commit, &file_path, options)
+            }
+            Err(e) => Err(e.clone()),
+        };
+        results.push((file_path, result));
+    }
+    results
+}
+
+/// Opens a repository for blaming and resolves the commit of `options.rev`, if given.
+fn open_for_blame(
+    repo_path: &Path,
+    options: &BlameOptions,
+) -> Result<(Repository, Option<git2::Oid>), String> {
     let repo = Repository::open(repo_path)
         .map_err(|e| format!("Failed to open repository at {:?}: {}", repo_path, e))?;
+    // Nothing has been committed yet, so no line has an author
+    if repo.is_empty().unwrap_or(false) {
+        return Err(EMPTY_REPOSITORY_ERROR.to_string());
+    }
+    // Resolve the requested revision up front so a bad rev gets a clear error
+    let rev_commit = match &options.rev {
+        Some(rev) => Some(
+            repo.revparse_single(rev)
+                .and_then(|obj| obj.peel_to_commit())
+                .map_err(|e| format!("Revision '{}' not found: {}", rev, e))?
+                .id(),
+        ),
+        None => None,
+    };
+    Ok((repo, rev_commit))
+}
 
+/// Performs git blame on a single file of an opened repository.
+fn get_blame_for_file(
+    repo: &Repository,
+    repo_path: &Path,
+    rev_commit: Option<git2::Oid>,
+    file_path_relative: &str,
+    options: &BlameOptions,
+) -> Result<Vec<BlameLineInfo>, String> {
     let file_path_repo = Path::new(file_path_relative);
+    let rev_commit = match rev_commit {
+        Some(oid) => Some(
+            repo.find_commit(oid)
+                .map_err(|e| format!("Failed to find commit {}: {}", oid, e))?,
+        ),
+        None => None,
+    };
 
-    // 2. Read the actual file content for context
+    // 2. Read the file content for context, from the revision if one was given
     let full_file_path = repo_path.join(file_path_repo);
-    let file_lines = match fs::File::open(&full_file_path) {
-        Ok(file) => io::BufReader::new(file)
-            .lines()
-            .collect::<Result<Vec<String>, _>>(),
-        // Handle file not found specifically
-        Err(ref e) if e.kind() == io::ErrorKind::NotFound => {
-            return Err(format!("File not found at path: {:?}", full_file_path));
-        }
-        Err(e) => {
-            return Err(format!(
-                "Failed to open/read file {:?}: {}",
-                full_file_path, e
-            ))
+    let content = if let Some(commit) = &rev_commit {
+        let blob = commit
+            .tree()
+            .and_then(|tree| tree.get_path(file_path_repo))
+            .and_then(|entry| entry.to_object(repo))
+            .and_then(|obj| obj.peel_to_blob())
+            .map_err(|e| {
+                format!(
+                    "File {:?} not found at revision {}: {}",
+                    file_path_relative,
+                    commit.id(),
+                    e
+                )
+            })?;
+        blob.content().to_vec()
+    } else {
+        match fs::read(&full_file_path) {
+            Ok(content) => content,
+            // Handle file not found specifically
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => {
+                return Err(format!("File not found at path: {:?}", full_file_path));
+            }
+            Err(e) => {
+                return Err(format!(
+                    "Failed to open/read file {:?}: {}",
+                    full_file_path, e
+                ))
+            }
         }
+    };
+    // Blaming binary content would only attribute meaningless "lines"
+    if is_binary_file(repo, file_path_repo, &content) {
+        return Err(BINARY_FILE_ERROR.to_string());
     }
-    .map_err(|e| format!("Failed to read lines from file {:?}: {}", full_file_path, e))?;
+    let file_lines: Vec<String> = String::from_utf8_lossy(&content)
+        .lines()
+        .map(|line| line.to_string())
+        .collect();
 
     // 3. Perform git blame using git2-rs
-    let mut blame_opts = BlameOptions::new();
-    // Configure options if needed (e.g., track copies, specific commit)
+    let mut blame_opts = git2::BlameOptions::new();
+    blame_opts
+        .ignore_whitespace(options.ignore_whitespace)
+        .track_copies_same_file(options.detect_moves)
+        .track_copies_same_commit_copies(options.detect_copies);
+    if let Some(commit) = &rev_commit {
+        blame_opts.newest_commit(commit.id());
+    }
 
     let blame = match repo.blame_file(file_path_repo, Some(&mut blame_opts)) {
         Ok(b) => b,
@@ -76,6 +295,8 @@ pub fn get_blame_for_file(
         // Use empty strings as fallback for potentially missing signature info
         let author_name = signature.name().unwrap_or("").to_string();
         let author_email = signature.email().unwrap_or("").to_string();
+        let author_time = signature.when().seconds();
+        let author_offset = signature.when().offset_minutes();
         let start_line_no = hunk.final_start_line(); // 1-based line number in final file
         let orig_start_line_no = hunk.orig_start_line(); // 1-based line number in original commit
 
@@ -94,6 +315,8 @@ pub fn get_blame_for_file(
                 commit_id: commit_id.clone(), // Clone commit_id for each line
                 author_name: author_name.clone(),
                 author_email: author_email.clone(),
+                author_time,
+                author_offset,
                 orig_line_no,
                 final_line_no,
                 line_content,
@@ -102,4 +325,77 @@ pub fn get_blame_for_file(
     }
 
     Ok(blame_results)
-} 
\ No newline at end of file
+}
+
+/// Surviving line counts per author email.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct Authorship {
+    pub lines: HashMap<String, usize>, // Lowercased (or identity-mapped) email -> line count
+    pub total_lines: usize,
+}
+
+impl Authorship {
+    fn add(&mut self, email: String, count: usize) {
+        *self.lines.entry(email).or_insert(0) += count;
+        self.total_lines += count;
+    }
+
+    /// Each author's share of the lines, in percent; empty when there are no lines.
+    pub fn percentages(&self) -> HashMap<String, f64> {
+        if self.total_lines == 0 {
+            return HashMap::new();
+        }
+        self.lines
+            .iter()
+            .map(|(email, &count)| {
+                (
+                    email.clone(),
+                    count as f64 * 100.0 / self.total_lines as f64,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Blame authorship per file and over all files, plus the files that couldn't be blamed.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct BlameAuthorship {
+    pub files: HashMap<String, Authorship>,
+    pub total: Authorship,
+    pub errors: HashMap<String, String>,
+}
+
+/// Counts blamed lines per author email.
+///
+/// Emails are compared case-insensitively. `identity_map` maps an alias email to the email it
+/// should be counted under, e.g. a student's personal address to their university one.
+pub fn summarize_authorship(
+    blame_results: HashMap<String, Result<Vec<BlameLineInfo>, String>>,
+    identity_map: &HashMap<String, String>,
+) -> BlameAuthorship {
+    let identities: HashMap<String, String> = identity_map
+        .iter()
+        .map(|(alias, email)| (alias.to_lowercase(), email.to_lowercase()))
+        .collect();
+    let mut authorship = BlameAuthorship::default();
+    for (file_path, result) in blame_results {
+        let lines = match result {
+            Ok(lines) => lines,
+            Err(e) => {
+                authorship.errors.insert(file_path, e);
+                continue;
+            }
+        };
+        let mut file = Authorship::default();
+        for line in &lines {
+            let email = line.author_email.to_lowercase();
+            let email = identities.get(&email).cloned().unwrap_or(email);
+            file.add(email, 1);
+        }
+        for (email, &count) in &file.lines {
+            authorship.total.add(email.clone(), count);
+        }
+        authorship.files.insert(file_path, file);
+    }
+    authorship
+}
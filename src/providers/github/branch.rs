@@ -1,9 +1,10 @@
-use git2::{Branch, BranchType, Repository};
-use std::path::Path;
-use std::collections::HashMap;
+use git2::{Branch, BranchType, Oid, Repository};
 use rayon::prelude::*;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct BranchInfo {
     pub name: String,
     pub remote_name: Option<String>,
@@ -14,6 +15,12 @@ pub struct BranchInfo {
     pub author_email: String,
     pub author_time: i64,
     pub is_head: bool,
+    // Commits ahead of/behind the default branch (local branches only);
+    // None when the default branch can't be determined
+    pub ahead: Option<usize>,
+    pub behind: Option<usize>,
+    // Whether the tip is reachable from the default branch (false when it can't be determined)
+    pub is_merged: bool,
 }
 
 /// Extracts branch information from a cloned repository.
@@ -25,9 +32,11 @@ pub fn extract_branches(repo_path: &Path) -> Result<Vec<BranchInfo>, String> {
     // Fetch all remote branches to ensure we have the latest information
     // This is equivalent to 'git fetch --all'
     let mut remote_names = Vec::new();
-    
+
     // Get remote names - StringArray isn't directly iterable, need to use indices
-    let remotes = repo.remotes().map_err(|e| format!("Failed to get remotes: {}", e))?;
+    let remotes = repo
+        .remotes()
+        .map_err(|e| format!("Failed to get remotes: {}", e))?;
     for i in 0..remotes.len() {
         if let Some(remote_name) = remotes.get(i) {
             remote_names.push(remote_name.to_string());
@@ -40,7 +49,10 @@ pub fn extract_branches(repo_path: &Path) -> Result<Vec<BranchInfo>, String> {
             Ok(mut remote) => {
                 let fetch_result = remote.fetch(&[] as &[&str], None, None);
                 if let Err(e) = fetch_result {
-                    eprintln!("Warning: Failed to fetch from remote '{}': {}", remote_name, e);
+                    eprintln!(
+                        "Warning: Failed to fetch from remote '{}': {}",
+                        remote_name, e
+                    );
                     // Continue with other remotes even if one fails
                 }
             }
@@ -52,30 +64,27 @@ pub fn extract_branches(repo_path: &Path) -> Result<Vec<BranchInfo>, String> {
     }
 
     // Get HEAD reference to identify the current branch
-    let head = match repo.head() {
-        Ok(head) => Some(head),
-        Err(_) => None, // Repository might be empty or HEAD might be detached
-    };
+    // Repository might be empty or HEAD might be detached
+    let head = repo.head().ok();
+
+    // Branches are compared against the default branch origin/HEAD points at
+    let default_oid = default_branch_oid(&repo);
 
     // Process local branches
     let mut branch_infos = Vec::new();
     if let Ok(branches) = repo.branches(Some(BranchType::Local)) {
-        for branch_result in branches {
-            if let Ok((branch, _)) = branch_result {
-                if let Some(branch_info) = process_branch(&repo, branch, &head, false) {
-                    branch_infos.push(branch_info);
-                }
+        for (branch, _) in branches.flatten() {
+            if let Some(branch_info) = process_branch(&repo, branch, &head, false, default_oid) {
+                branch_infos.push(branch_info);
             }
         }
     }
 
     // Process remote branches
     if let Ok(branches) = repo.branches(Some(BranchType::Remote)) {
-        for branch_result in branches {
-            if let Ok((branch, _)) = branch_result {
-                if let Some(branch_info) = process_branch(&repo, branch, &head, true) {
-                    branch_infos.push(branch_info);
-                }
+        for (branch, _) in branches.flatten() {
+            if let Some(branch_info) = process_branch(&repo, branch, &head, true, default_oid) {
+                branch_infos.push(branch_info);
             }
         }
     }
@@ -83,12 +92,23 @@ pub fn extract_branches(repo_path: &Path) -> Result<Vec<BranchInfo>, String> {
     Ok(branch_infos)
 }
 
+/// Resolves the commit of the default branch via `refs/remotes/origin/HEAD`.
+fn default_branch_oid(repo: &Repository) -> Option<Oid> {
+    repo.find_reference("refs/remotes/origin/HEAD")
+        .and_then(|r| r.resolve())
+        .ok()
+        .and_then(|r| r.target())
+}
+
 /// Processes a single branch to extract its information.
+/// `default_oid`, when given, is used to compute merged status and, for local branches,
+/// ahead/behind counts.
 fn process_branch(
     repo: &Repository,
     branch: Branch,
     head: &Option<git2::Reference>,
     is_remote: bool,
+    default_oid: Option<Oid>,
 ) -> Option<BranchInfo> {
     // Get branch name
     let branch_name = match branch.name() {
@@ -107,10 +127,7 @@ fn process_branch(
     };
 
     // Get the target commit
-    let oid = match branch.get().target() {
-        Some(oid) => oid,
-        None => return None, // Skip if no target
-    };
+    let oid = branch.get().target()?;
 
     // Find the commit
     let commit = match repo.find_commit(oid) {
@@ -132,6 +149,18 @@ fn process_branch(
     let author_email = author.email().unwrap_or("").to_string();
     let author_time = author.when().seconds();
 
+    let ahead_behind = default_oid
+        .filter(|_| !is_remote)
+        .map(|base| repo.graph_ahead_behind(oid, base));
+    let (ahead, behind) = match ahead_behind {
+        Some(Ok((ahead, behind))) => (Some(ahead), Some(behind)),
+        _ => (None, None),
+    };
+
+    // Like `git branch --merged <default>`: the tip is the default commit or one of its ancestors
+    let is_merged = default_oid
+        .is_some_and(|base| base == oid || repo.graph_descendant_of(base, oid).unwrap_or(false));
+
     Some(BranchInfo {
         name: branch_name,
         remote_name,
@@ -142,6 +171,9 @@ fn process_branch(
         author_email,
         author_time,
         is_head,
+        ahead,
+        behind,
+        is_merged,
     })
 }
 
@@ -156,4 +188,4 @@ pub fn extract_branches_parallel(
             (repo_url.clone(), result)
         })
         .collect()
-}
\ No newline at end of file
+}
@@ -1,3 +1,4 @@
+use git2::{build::CheckoutBuilder, BranchType, Repository};
 use std::path::PathBuf;
 
 /// Internal representation of the status of a cloning operation.
@@ -15,4 +16,217 @@ pub struct InternalRepoCloneTask {
     pub url: String,
     pub status: InternalCloneStatus,
     pub temp_dir: Option<PathBuf>, // Stores the path to the temporary directory if clone is successful
-} 
\ No newline at end of file
+    pub attempts: u32,             // Clone attempts made so far, including retries
+}
+
+/// A clone task's status transition, published to progress watchers.
+#[derive(Debug, Clone)]
+pub struct CloneProgressEvent {
+    pub url: String, // The URL the task was created with
+    pub status: InternalCloneStatus,
+}
+
+impl InternalCloneStatus {
+    /// Whether the task has finished, successfully or not.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            InternalCloneStatus::Completed | InternalCloneStatus::Failed(_)
+        )
+    }
+}
+
+/// Error messages of failures worth retrying: name resolution and dropped connections
+const TRANSIENT_CLONE_ERRORS: &[&str] = &[
+    "could not resolve host",
+    "failed to resolve address",
+    "connection reset",
+    "early eof",
+    "unexpected eof",
+    "rpc failed",
+    "timed out",
+];
+
+/// Whether a failed clone may succeed if simply tried again.
+/// Authentication failures are never transient, whatever their message says.
+pub fn is_transient_clone_error(error: &git2::Error) -> bool {
+    if error.code() == git2::ErrorCode::Auth {
+        return false;
+    }
+    let message = error.message().to_lowercase();
+    if message.contains("authentication") || message.contains("401") || message.contains("403") {
+        return false;
+    }
+    // The remote hung up mid-conversation
+    if error.code() == git2::ErrorCode::Eof {
+        return true;
+    }
+    TRANSIENT_CLONE_ERRORS
+        .iter()
+        .any(|pattern| message.contains(pattern))
+}
+
+/// Options for a single clone.
+#[derive(Debug, Clone, Default)]
+pub struct CloneOptions {
+    pub branch: Option<String>, // Check out this branch instead of the default one
+    pub depth: Option<i32>,     // Shallow clone with this many commits
+    pub no_tags: bool,          // Don't fetch tags
+    // After cloning, check out this branch, tag or commit (commits and tags detach HEAD)
+    pub checkout: Option<String>,
+}
+
+impl CloneOptions {
+    /// Applies `git clone`-style flags that map onto the libgit2 clone.
+    ///
+    /// Supported: `--depth <n>` (or `--depth=<n>`), `--no-tags` and `--branch <name>`
+    /// (or `-b <name>`). Anything else is rejected, including flags that would change the
+    /// URL, destination or transport such as `--upload-pack`, `--template` or `--config`.
+    /// `--filter` gets its own error: libgit2 has no partial clone support, so a
+    /// `--filter=blob:none` clone can't be made here at all.
+    pub fn apply_extra_args(&mut self, args: &[String]) -> Result<(), String> {
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            let mut value = |flag: &str| {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next().cloned())
+                    .ok_or_else(|| format!("Clone argument {} requires a value", flag))
+            };
+            match flag {
+                "--depth" => {
+                    let depth = value(flag)?;
+                    self.depth = match depth.parse::<i32>() {
+                        Ok(n) if n > 0 => Some(n),
+                        _ => return Err(format!("Invalid --depth value: {}", depth)),
+                    };
+                }
+                "--no-tags" => self.no_tags = true,
+                "--branch" | "-b" => self.branch = Some(value(flag)?),
+                "--filter" => return Err(
+                    "Clone argument --filter is not supported: libgit2 cannot make partial clones"
+                        .to_string(),
+                ),
+                _ => return Err(format!("Unsupported clone argument: {}", arg)),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Checks out `rev` in a fresh or fetched clone, like `git checkout <rev>`.
+///
+/// A local branch, or a remote branch of `origin` (which gets a local tracking branch), is
+/// checked out as a branch. Anything else that names a commit, such as a tag or SHA, is
+/// checked out with a detached HEAD. Local changes are never overwritten.
+pub fn checkout_ref(repo: &Repository, rev: &str) -> Result<(), String> {
+    let fail = |e: git2::Error| format!("Failed to check out '{}': {}", rev, e);
+
+    let branch = match repo.find_branch(rev, BranchType::Local) {
+        Ok(branch) => Some(branch),
+        Err(_) => match repo.find_branch(&format!("origin/{}", rev), BranchType::Remote) {
+            Ok(remote) => {
+                let commit = remote.get().peel_to_commit().map_err(fail)?;
+                let mut branch = repo.branch(rev, &commit, false).map_err(fail)?;
+                branch
+                    .set_upstream(Some(&format!("origin/{}", rev)))
+                    .map_err(fail)?;
+                Some(branch)
+            }
+            Err(_) => None,
+        },
+    };
+
+    let mut checkout = CheckoutBuilder::new();
+    checkout.safe();
+    match branch {
+        Some(branch) => {
+            let reference = branch.into_reference();
+            let commit = reference.peel_to_commit().map_err(fail)?;
+            repo.checkout_tree(commit.as_object(), Some(&mut checkout))
+                .map_err(fail)?;
+            let name = reference
+                .name()
+                .ok_or_else(|| format!("Branch '{}' has a non-UTF-8 name", rev))?;
+            repo.set_head(name).map_err(fail)
+        }
+        None => {
+            let commit = repo
+                .revparse_single(rev)
+                .and_then(|obj| obj.peel_to_commit())
+                .map_err(|e| {
+                    format!(
+                        "Cannot check out '{}': no such branch or commit: {}",
+                        rev, e
+                    )
+                })?;
+            repo.checkout_tree(commit.as_object(), Some(&mut checkout))
+                .map_err(fail)?;
+            repo.set_head_detached(commit.id()).map_err(fail)
+        }
+    }
+}
+
+/// Deletes every tag, for `--no-tags` clones: libgit2 always fetches tags on clone.
+pub fn delete_tags(repo: &Repository) -> Result<(), String> {
+    for tag in repo.tag_names(None).map_err(|e| e.to_string())?.iter() {
+        repo.tag_delete(tag.unwrap_or_default())
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Moves a reused clone onto the freshly fetched `origin/<branch>`, like
+/// `git checkout -B <branch> origin/<branch>`. `branch` defaults to the checked-out branch,
+/// or to origin's default branch when HEAD is detached. The working tree is reset to match,
+/// since analyses must see exactly what was fetched; untracked files are left alone.
+pub fn reset_to_fetched(repo: &Repository, branch: Option<&str>) -> Result<(), String> {
+    let branch = match branch {
+        Some(branch) => branch.to_string(),
+        None => checked_out_branch(repo)?,
+    };
+    let fail = |e: git2::Error| format!("Failed to update branch '{}': {}", branch, e);
+
+    let remote = repo
+        .find_branch(&format!("origin/{}", branch), BranchType::Remote)
+        .map_err(|e| format!("Branch '{}' not found: {}", branch, e))?;
+    let commit = remote.get().peel_to_commit().map_err(fail)?;
+    let local_ref = format!("refs/heads/{}", branch);
+    repo.reference(&local_ref, commit.id(), true, "reset to fetched origin")
+        .map_err(fail)?;
+    repo.find_branch(&branch, BranchType::Local)
+        .and_then(|mut local| local.set_upstream(Some(&format!("origin/{}", branch))))
+        .map_err(fail)?;
+    repo.set_head(&local_ref).map_err(fail)?;
+    let mut checkout = CheckoutBuilder::new();
+    checkout.force();
+    repo.checkout_head(Some(&mut checkout)).map_err(fail)
+}
+
+/// The branch HEAD is on, or origin's default branch if HEAD is detached
+fn checked_out_branch(repo: &Repository) -> Result<String, String> {
+    if let Ok(head) = repo.head() {
+        if head.is_branch() {
+            if let Some(name) = head.shorthand() {
+                return Ok(name.to_string());
+            }
+        }
+    }
+    repo.find_reference("refs/remotes/origin/HEAD")
+        .ok()
+        .and_then(|origin_head| origin_head.symbolic_target().map(str::to_string))
+        .and_then(|target| {
+            target
+                .strip_prefix("refs/remotes/origin/")
+                .map(str::to_string)
+        })
+        .ok_or_else(|| "Cannot tell which branch to update: HEAD is detached".to_string())
+}
+
+/// Error for analyses that need a commit in a clone of an empty repository (unborn HEAD).
+/// The `EmptyRepository:` prefix lets callers tell it apart from other git errors.
+pub const EMPTY_REPOSITORY_ERROR: &str = "EmptyRepository: the repository has no commits yet";
@@ -1,8 +1,10 @@
-use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, USER_AGENT};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tokio::task;
 
+use crate::api_client::{api_error, create_github_client, request_error, ApiClientConfig};
+use crate::repo::parse_repo_parts;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReviewInfo {
     pub id: i64,
@@ -24,10 +26,11 @@ pub async fn fetch_code_reviews(
     repo_urls: Vec<String>,
     _github_username: &str, // Prefix with underscore to indicate intentional non-use
     github_token: &str,
+    api_config: &ApiClientConfig,
     max_pages: Option<usize>,
 ) -> Result<HashMap<String, Result<HashMap<i32, Vec<ReviewInfo>>, String>>, String> {
     // Create a GitHub client
-    let client = match create_github_client(github_token) {
+    let client = match create_github_client(github_token, api_config) {
         Ok(c) => c,
         Err(e) => {
             let err_msg = format!("Failed to create GitHub client: {}", e);
@@ -72,41 +75,6 @@ pub async fn fetch_code_reviews(
     Ok(results)
 }
 
-/// Creates a GitHub API client with proper authentication
-fn create_github_client(token: &str) -> Result<reqwest::Client, reqwest::Error> {
-    let mut headers = HeaderMap::new();
-    // Standard GitHub API headers
-    headers.insert(
-        ACCEPT,
-        HeaderValue::from_static("application/vnd.github.v3+json"),
-    );
-    headers.insert(
-        AUTHORIZATION,
-        HeaderValue::from_str(&format!("token {}", token)).unwrap(),
-    );
-    headers.insert(
-        USER_AGENT,
-        HeaderValue::from_static("gradelib-github-client/0.1.0"),
-    );
-
-    reqwest::Client::builder().default_headers(headers).build()
-}
-
-/// Parses owner and repo name from GitHub URL
-fn parse_repo_parts(repo_url: &str) -> Result<(String, String), String> {
-    use crate::repo::parse_slug_from_url;
-
-    let slug = parse_slug_from_url(repo_url)
-        .ok_or_else(|| format!("Invalid repository URL format: {}", repo_url))?;
-
-    let parts: Vec<&str> = slug.split('/').collect();
-    if parts.len() != 2 {
-        return Err(format!("Invalid repository slug format: {}", slug));
-    }
-
-    Ok((parts[0].to_string(), parts[1].to_string()))
-}
-
 /// Fetches code reviews for a single repository
 async fn fetch_repo_code_reviews(
     client: &reqwest::Client,
@@ -132,9 +100,9 @@ async fn fetch_repo_code_reviews(
             .get(&pr_url)
             .send()
             .await
-            .map_err(|e| format!("Failed to fetch pull requests: {}", e))?;
+            .map_err(|e| request_error("Failed to fetch pull requests", e))?;
         if !prs_response.status().is_success() {
-            return Err(format!("GitHub API error: {}", prs_response.status()));
+            return Err(api_error("GitHub API error", prs_response).await);
         }
         let pull_requests: Vec<PullRequestBasic> = prs_response
             .json()
@@ -179,19 +147,14 @@ async fn fetch_repo_code_reviews(
     Ok(result_map)
 }
 
-/// Fetches review information for a single pull request
-async fn fetch_pr_reviews(
+/// Fetches review information for a single pull request, following pagination
+pub(crate) async fn fetch_pr_reviews(
     client: &reqwest::Client,
     owner: &str,
     repo: &str,
     pr_number: i32,
     _pr_html_url: &str,
 ) -> Result<Vec<ReviewInfo>, String> {
-    let reviews_url = format!(
-        "https://api.github.com/repos/{}/{}/pulls/{}/reviews",
-        owner, repo, pr_number
-    );
-
     #[derive(Deserialize)]
     struct ReviewResponse {
         id: i64,
@@ -209,34 +172,48 @@ async fn fetch_pr_reviews(
         id: i64,
     }
 
-    let reviews_response = client
-        .get(&reviews_url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch reviews: {}", e))?;
+    let mut result = Vec::new();
+    let mut page = 1;
+    loop {
+        let reviews_url = format!(
+            "https://api.github.com/repos/{}/{}/pulls/{}/reviews?per_page=100&page={}",
+            owner, repo, pr_number, page
+        );
+
+        let reviews_response = client
+            .get(&reviews_url)
+            .send()
+            .await
+            .map_err(|e| request_error("Failed to fetch reviews", e))?;
 
-    if !reviews_response.status().is_success() {
-        return Err(format!("GitHub API error: {}", reviews_response.status()));
-    }
+        if !reviews_response.status().is_success() {
+            return Err(api_error("GitHub API error", reviews_response).await);
+        }
 
-    let reviews: Vec<ReviewResponse> = reviews_response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse reviews response: {}", e))?;
+        let reviews: Vec<ReviewResponse> = reviews_response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse reviews response: {}", e))?;
+
+        let len = reviews.len();
+        for review in reviews {
+            result.push(ReviewInfo {
+                id: review.id,
+                pr_number,
+                user_login: review.user.login,
+                user_id: review.user.id,
+                body: review.body,
+                state: review.state,
+                submitted_at: review.submitted_at,
+                commit_id: review.commit_id,
+                html_url: review.html_url,
+            });
+        }
 
-    let mut result = Vec::new();
-    for review in reviews {
-        result.push(ReviewInfo {
-            id: review.id,
-            pr_number,
-            user_login: review.user.login,
-            user_id: review.user.id,
-            body: review.body,
-            state: review.state,
-            submitted_at: review.submitted_at,
-            commit_id: review.commit_id,
-            html_url: review.html_url,
-        });
+        if len < 100 {
+            break;
+        }
+        page += 1;
     }
 
     Ok(result)
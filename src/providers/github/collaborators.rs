@@ -1,9 +1,11 @@
-use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, USER_AGENT};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use tokio::task;
 
-use crate::repo::parse_slug_from_url;
+use crate::api_client::{
+    api_error, create_github_client, next_page_url, request_error, ApiClientConfig,
+};
+use crate::repo::parse_repo_parts;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CollaboratorInfo {
@@ -12,8 +14,47 @@ pub struct CollaboratorInfo {
     pub full_name: Option<String>,
     pub email: Option<String>,
     pub avatar_url: Option<String>,
+    pub permission: String, // Highest granted level: "admin", "maintain", "push", "triage" or "pull"
+    pub affiliation: String, // "outside", "direct", "organization" or "unknown"; see `fetch_repo_collaborators`
 }
 
+/// Permission flags returned with each collaborator in the list response
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct Permissions {
+    #[serde(default)]
+    admin: bool,
+    #[serde(default)]
+    maintain: bool,
+    #[serde(default)]
+    push: bool,
+    #[serde(default)]
+    triage: bool,
+    #[serde(default)]
+    pull: bool,
+}
+
+impl Permissions {
+    /// Collapses the flags into the highest permission level granted
+    pub(crate) fn level(&self) -> &'static str {
+        if self.admin {
+            "admin"
+        } else if self.maintain {
+            "maintain"
+        } else if self.push {
+            "push"
+        } else if self.triage {
+            "triage"
+        } else if self.pull {
+            "pull"
+        } else {
+            "none"
+        }
+    }
+}
+
+/// Affiliation filters accepted by the collaborators endpoint
+const AFFILIATIONS: &[&str] = &["outside", "direct", "all"];
+
 /// Fetches collaborator information for multiple repositories concurrently
 ///
 /// For each input repo URL, returns either a list of collaborators or an error string.
@@ -22,10 +63,23 @@ pub async fn fetch_collaborators(
     repo_urls: Vec<String>,
     _github_username: &str, // Prefix with underscore to indicate intentional non-use
     github_token: &str,
+    api_config: &ApiClientConfig,
     max_pages: Option<usize>,
+    affiliation: Option<&str>, // "outside", "direct", "all"
 ) -> Result<HashMap<String, Result<Vec<CollaboratorInfo>, String>>, String> {
+    if let Some(affiliation) = affiliation {
+        if !AFFILIATIONS.contains(&affiliation) {
+            return Err(format!(
+                "Invalid affiliation '{}'. Valid values are: {}",
+                affiliation,
+                AFFILIATIONS.join(", ")
+            ));
+        }
+    }
+    let affiliation = affiliation.map(|a| a.to_string());
+
     // Create a GitHub client
-    let client = match create_github_client(github_token) {
+    let client = match create_github_client(github_token, api_config) {
         Ok(c) => c,
         Err(e) => {
             let err_msg = format!("Failed to create GitHub client: {}", e);
@@ -44,9 +98,12 @@ pub async fn fetch_collaborators(
         let client = client.clone();
         let token = github_token.to_string();
         let url = repo_url.clone();
+        let affiliation = affiliation.clone();
 
         let task = task::spawn(async move {
-            let result = fetch_repo_collaborators(&client, &url, &token, max_pages).await;
+            let result =
+                fetch_repo_collaborators(&client, &url, &token, max_pages, affiliation.as_deref())
+                    .await;
             (url, result)
         });
 
@@ -76,92 +133,60 @@ pub async fn fetch_collaborators(
     Ok(results)
 }
 
-/// Creates a GitHub API client with proper authentication
-fn create_github_client(token: &str) -> Result<reqwest::Client, reqwest::Error> {
-    let mut headers = HeaderMap::new();
-    // Standard GitHub API headers
-    headers.insert(
-        ACCEPT,
-        HeaderValue::from_static("application/vnd.github.v3+json"),
-    );
-    headers.insert(
-        AUTHORIZATION,
-        HeaderValue::from_str(&format!("token {}", token)).unwrap(),
-    );
-    headers.insert(
-        USER_AGENT,
-        HeaderValue::from_static("gradelib-github-client/0.1.0"),
-    );
-
-    reqwest::Client::builder().default_headers(headers).build()
+/// Collaborator as returned by the list endpoint
+#[derive(Deserialize)]
+struct CollaboratorBasic {
+    login: String,
+    #[serde(default)]
+    permissions: Permissions,
 }
 
-/// Fetches collaborators for a single repository
+/// Fetches collaborators for a single repository, tagging each with how it has access:
+/// - "outside": an outside collaborator, i.e. not a member of the owning organization
+/// - "direct": added to the repository itself, as an organization member or on a
+///   personal repository
+/// - "organization": access only through organization membership or a team
+/// - "unknown": a lookup needed to tell these apart failed
+///
+/// GitHub's `direct` listing includes outside collaborators, so "outside" wins the overlap.
 async fn fetch_repo_collaborators(
     client: &reqwest::Client,
     repo_url: &str,
     _token: &str, // Prefix with underscore to indicate intentional non-use
     max_pages: Option<usize>,
+    affiliation: Option<&str>,
 ) -> Result<Vec<CollaboratorInfo>, String> {
-    // Parse owner/repo from URL using existing function
-    let slug = parse_slug_from_url(repo_url)
-        .ok_or_else(|| format!("Invalid repository URL format: {}", repo_url))?;
-    let parts: Vec<&str> = slug.split('/').collect();
-    if parts.len() != 2 {
-        return Err(format!("Invalid repository slug format: {}", slug));
-    }
-    let owner = parts[0];
-    let repo = parts[1];
-    let mut page = 1;
-    let mut all_collaborators = Vec::new();
-    loop {
-        let collaborators_url = format!(
-            "https://api.github.com/repos/{}/{}/collaborators?per_page=100&page={}",
-            owner, repo, page
-        );
-        #[derive(Deserialize)]
-        struct CollaboratorBasic {
-            login: String,
-        }
-        let collaborators_response = client
-            .get(&collaborators_url)
-            .send()
-            .await
-            .map_err(|e| format!("Failed to fetch collaborators: {}", e))?;
-        if !collaborators_response.status().is_success() {
-            return Err(format!(
-                "GitHub API error: {}",
-                collaborators_response.status()
-            ));
-        }
-        let collaborators: Vec<CollaboratorBasic> = collaborators_response
-            .json()
-            .await
-            .map_err(|e| format!("Failed to parse collaborators response: {}", e))?;
-        let len = collaborators.len();
-        if len == 0 {
-            break;
-        }
-        let mut should_break = false;
-        if let Some(max) = max_pages {
-            if page >= max {
-                should_break = true;
-            }
-        }
-        if len < 100 {
-            should_break = true;
-        }
-        all_collaborators.extend(collaborators);
-        if should_break {
-            break;
-        }
-        page += 1;
-    }
+    let (owner, repo) = parse_repo_parts(repo_url)?;
+    let requested = affiliation.unwrap_or("all");
+    let all_collaborators =
+        fetch_collaborator_list(client, &owner, &repo, max_pages, requested).await?;
+    // Only the logins are needed to tell the groups apart, so these lists are not detailed
+    let outside = match requested {
+        "outside" => Membership::Everyone,
+        _ => collaborator_logins(client, &owner, &repo, max_pages, "outside").await,
+    };
+    let direct = match requested {
+        "all" => collaborator_logins(client, &owner, &repo, max_pages, "direct").await,
+        _ => Membership::Everyone,
+    };
+    // Outside collaborators are also direct ones, so missing from `direct` settles it
+    let affiliation_of = |login: &str| match (outside.contains(login), direct.contains(login)) {
+        (Some(true), _) => "outside",
+        (Some(false), Some(true)) => "direct",
+        (_, Some(false)) => "organization",
+        _ => "unknown",
+    };
     // Now fetch detailed information for each collaborator
     let mut detailed_collaborators = Vec::new();
     for collab in all_collaborators {
+        let permission = collab.permissions.level().to_string();
+        let affiliation = affiliation_of(&collab.login).to_string();
         match fetch_user_details(client, &collab.login).await {
-            Ok(user_info) => detailed_collaborators.push(user_info),
+            Ok(user_info) => detailed_collaborators.push(CollaboratorInfo {
+                permission,
+                affiliation,
+                ..user_info
+            }),
             Err(e) => {
                 eprintln!(
                     "Warning: Failed to fetch details for {}: {}",
@@ -174,6 +199,8 @@ async fn fetch_repo_collaborators(
                     full_name: None,
                     email: None,
                     avatar_url: None,
+                    permission,
+                    affiliation,
                 });
             }
         }
@@ -181,6 +208,87 @@ async fn fetch_repo_collaborators(
     Ok(detailed_collaborators)
 }
 
+/// Which collaborators belong to one affiliation group, as far as is known
+enum Membership {
+    /// The requested affiliation already implies the group
+    Everyone,
+    Logins(HashSet<String>),
+    /// The lookup failed
+    Unknown,
+}
+
+impl Membership {
+    fn contains(&self, login: &str) -> Option<bool> {
+        match self {
+            Membership::Everyone => Some(true),
+            Membership::Logins(logins) => Some(logins.contains(login)),
+            Membership::Unknown => None,
+        }
+    }
+}
+
+/// Logins of every collaborator with the given affiliation. A failed lookup only costs the
+/// affiliation tags, so it is reported as a warning rather than failing the repository.
+async fn collaborator_logins(
+    client: &reqwest::Client,
+    owner: &str,
+    repo: &str,
+    max_pages: Option<usize>,
+    affiliation: &str,
+) -> Membership {
+    match fetch_collaborator_list(client, owner, repo, max_pages, affiliation).await {
+        Ok(collaborators) => {
+            Membership::Logins(collaborators.into_iter().map(|c| c.login).collect())
+        }
+        Err(e) => {
+            eprintln!(
+                "Warning: Failed to list {} collaborators of {}/{}, affiliations may be unknown: {}",
+                affiliation, owner, repo, e
+            );
+            Membership::Unknown
+        }
+    }
+}
+
+/// Lists the collaborators of a repository page by page, without user details
+async fn fetch_collaborator_list(
+    client: &reqwest::Client,
+    owner: &str,
+    repo: &str,
+    max_pages: Option<usize>,
+    affiliation: &str,
+) -> Result<Vec<CollaboratorBasic>, String> {
+    let mut page = 1;
+    let mut all_collaborators = Vec::new();
+    let mut collaborators_url = format!(
+        "https://api.github.com/repos/{}/{}/collaborators?affiliation={}&per_page=100",
+        owner, repo, affiliation
+    );
+    loop {
+        let collaborators_response = client
+            .get(&collaborators_url)
+            .send()
+            .await
+            .map_err(|e| request_error("Failed to fetch collaborators", e))?;
+        if !collaborators_response.status().is_success() {
+            return Err(api_error("GitHub API error", collaborators_response).await);
+        }
+        let next_url = next_page_url(collaborators_response.headers());
+        let collaborators: Vec<CollaboratorBasic> = collaborators_response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse collaborators response: {}", e))?;
+        all_collaborators.extend(collaborators);
+        // The Link header names the next page; the last page has none
+        match next_url {
+            Some(url) if max_pages.is_none_or(|max| page < max) => collaborators_url = url,
+            _ => break,
+        }
+        page += 1;
+    }
+    Ok(all_collaborators)
+}
+
 /// Fetches detailed information for a single user
 async fn fetch_user_details(
     client: &reqwest::Client,
@@ -201,10 +309,10 @@ async fn fetch_user_details(
         .get(&user_url)
         .send()
         .await
-        .map_err(|e| format!("Failed to fetch user details: {}", e))?;
+        .map_err(|e| request_error("Failed to fetch user details", e))?;
 
     if !user_response.status().is_success() {
-        return Err(format!("GitHub API error: {}", user_response.status()));
+        return Err(api_error("GitHub API error", user_response).await);
     }
 
     let user: UserResponse = user_response
@@ -218,5 +326,7 @@ async fn fetch_user_details(
         full_name: user.name,
         email: user.email,
         avatar_url: user.avatar_url,
+        permission: String::new(),  // Filled in from the collaborators list
+        affiliation: String::new(), // Likewise
     })
 }
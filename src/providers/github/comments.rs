@@ -1,8 +1,10 @@
-use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, USER_AGENT};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tokio::task;
 
+use crate::api_client::{api_error, create_github_client, request_error, ApiClientConfig};
+use crate::repo::parse_repo_parts;
+
 /// Enum to represent different types of GitHub comments
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum CommentType {
@@ -45,11 +47,12 @@ pub async fn fetch_comments(
     repo_urls: Vec<String>,
     _github_username: &str, // Prefix with underscore to indicate intentional non-use
     github_token: &str,
+    api_config: &ApiClientConfig,
     comment_types: Option<Vec<CommentType>>, // Optional filter for comment types
     max_pages: Option<usize>,
 ) -> Result<HashMap<String, Result<Vec<CommentInfo>, String>>, String> {
     // Create a GitHub client
-    let client = match create_github_client(github_token) {
+    let client = match create_github_client(github_token, api_config) {
         Ok(c) => c,
         Err(e) => {
             let err_msg = format!("Failed to create GitHub client: {}", e);
@@ -69,7 +72,6 @@ pub async fn fetch_comments(
         let token = github_token.to_string();
         let url = repo_url.clone();
         let types = comment_types.clone();
-        let max_pages = max_pages.clone();
         let task = task::spawn(async move {
             let result = fetch_repo_comments(&client, &url, &token, types, max_pages).await;
             (url, result)
@@ -93,41 +95,6 @@ pub async fn fetch_comments(
     Ok(results)
 }
 
-/// Creates a GitHub API client with proper authentication
-fn create_github_client(token: &str) -> Result<reqwest::Client, reqwest::Error> {
-    let mut headers = HeaderMap::new();
-    // Standard GitHub API headers
-    headers.insert(
-        ACCEPT,
-        HeaderValue::from_static("application/vnd.github.v3+json"),
-    );
-    headers.insert(
-        AUTHORIZATION,
-        HeaderValue::from_str(&format!("token {}", token)).unwrap(),
-    );
-    headers.insert(
-        USER_AGENT,
-        HeaderValue::from_static("gradelib-github-client/0.1.0"),
-    );
-
-    reqwest::Client::builder().default_headers(headers).build()
-}
-
-/// Parses owner and repo name from GitHub URL
-fn parse_repo_parts(repo_url: &str) -> Result<(String, String), String> {
-    use crate::repo::parse_slug_from_url;
-
-    let slug = parse_slug_from_url(repo_url)
-        .ok_or_else(|| format!("Invalid repository URL format: {}", repo_url))?;
-
-    let parts: Vec<&str> = slug.split('/').collect();
-    if parts.len() != 2 {
-        return Err(format!("Invalid repository slug format: {}", slug));
-    }
-
-    Ok((parts[0].to_string(), parts[1].to_string()))
-}
-
 /// Fetches comments for a single repository
 async fn fetch_repo_comments(
     client: &reqwest::Client,
@@ -156,9 +123,8 @@ async fn fetch_repo_comments(
         let owner_clone = owner.clone();
         let repo_clone = repo.clone();
         let client_clone = client.clone();
-        let max_pages = max_pages.clone();
         tasks.push(task::spawn(async move {
-            fetch_issue_comments(&client_clone, &owner_clone, &repo_clone, max_pages).await
+            fetch_repo_issue_comments(&client_clone, &owner_clone, &repo_clone, max_pages).await
         }));
     }
 
@@ -166,7 +132,6 @@ async fn fetch_repo_comments(
         let owner_clone = owner.clone();
         let repo_clone = repo.clone();
         let client_clone = client.clone();
-        let max_pages = max_pages.clone();
         tasks.push(task::spawn(async move {
             fetch_commit_comments(&client_clone, &owner_clone, &repo_clone, max_pages).await
         }));
@@ -176,7 +141,6 @@ async fn fetch_repo_comments(
         let owner_clone = owner.clone();
         let repo_clone = repo.clone();
         let client_clone = client.clone();
-        let max_pages = max_pages.clone();
         tasks.push(task::spawn(async move {
             fetch_pr_comments(&client_clone, &owner_clone, &repo_clone, max_pages).await
         }));
@@ -189,7 +153,6 @@ async fn fetch_repo_comments(
         let owner_clone = owner.clone();
         let repo_clone = repo.clone();
         let client_clone = client.clone();
-        let max_pages = max_pages.clone();
         tasks.push(task::spawn(async move {
             fetch_review_comments(&client_clone, &owner_clone, &repo_clone, max_pages).await
         }));
@@ -210,8 +173,28 @@ async fn fetch_repo_comments(
     Ok(combined_comments)
 }
 
+/// Fetches the comments on a single issue
+///
+/// Returns an empty list if the issue has no comments.
+pub async fn fetch_issue_comments(
+    repo_url: &str,
+    github_token: &str,
+    api_config: &ApiClientConfig,
+    issue_number: i32,
+    max_pages: Option<usize>,
+) -> Result<Vec<CommentInfo>, String> {
+    let client = create_github_client(github_token, api_config)
+        .map_err(|e| format!("Failed to create GitHub client: {}", e))?;
+    let (owner, repo) = parse_repo_parts(repo_url)?;
+    let comments_url = format!(
+        "https://api.github.com/repos/{}/{}/issues/{}/comments",
+        owner, repo, issue_number
+    );
+    fetch_issue_comments_for_number(&client, &comments_url, issue_number, max_pages).await
+}
+
 /// Fetches issue comments for a repository
-async fn fetch_issue_comments(
+async fn fetch_repo_issue_comments(
     client: &reqwest::Client,
     owner: &str,
     repo: &str,
@@ -232,12 +215,9 @@ async fn fetch_issue_comments(
             .get(&issues_url)
             .send()
             .await
-            .map_err(|e| format!("Failed to fetch issues: {}", e))?;
+            .map_err(|e| request_error("Failed to fetch issues", e))?;
         if !issues_response.status().is_success() {
-            return Err(format!(
-                "GitHub API error for issues: {}",
-                issues_response.status()
-            ));
+            return Err(api_error("GitHub API error for issues", issues_response).await);
         }
         let issues: Vec<IssueBasic> = issues_response
             .json()
@@ -302,12 +282,9 @@ async fn fetch_issue_comments_for_number(
             .get(&paged_url)
             .send()
             .await
-            .map_err(|e| format!("Failed to fetch issue comments: {}", e))?;
+            .map_err(|e| request_error("Failed to fetch issue comments", e))?;
         if !response.status().is_success() {
-            return Err(format!(
-                "GitHub API error for issue comments: {}",
-                response.status()
-            ));
+            return Err(api_error("GitHub API error for issue comments", response).await);
         }
         let comments: Vec<IssueComment> = response
             .json()
@@ -367,12 +344,9 @@ async fn fetch_pr_comments(
             .get(&prs_url)
             .send()
             .await
-            .map_err(|e| format!("Failed to fetch pull requests: {}", e))?;
+            .map_err(|e| request_error("Failed to fetch pull requests", e))?;
         if !prs_response.status().is_success() {
-            return Err(format!(
-                "GitHub API error for PRs: {}",
-                prs_response.status()
-            ));
+            return Err(api_error("GitHub API error for PRs", prs_response).await);
         }
         let pull_requests: Vec<PullRequestBasic> = prs_response
             .json()
@@ -435,12 +409,9 @@ async fn fetch_pr_comments_for_number(
             .get(&paged_url)
             .send()
             .await
-            .map_err(|e| format!("Failed to fetch PR comments: {}", e))?;
+            .map_err(|e| request_error("Failed to fetch PR comments", e))?;
         if !response.status().is_success() {
-            return Err(format!(
-                "GitHub API error for PR comments: {}",
-                response.status()
-            ));
+            return Err(api_error("GitHub API error for PR comments", response).await);
         }
         let comments: Vec<PullRequestComment> = response
             .json()
@@ -517,12 +488,9 @@ async fn fetch_review_comments(
             .get(&review_comments_url)
             .send()
             .await
-            .map_err(|e| format!("Failed to fetch review comments: {}", e))?;
+            .map_err(|e| request_error("Failed to fetch review comments", e))?;
         if !response.status().is_success() {
-            return Err(format!(
-                "GitHub API error for review comments: {}",
-                response.status()
-            ));
+            return Err(api_error("GitHub API error for review comments", response).await);
         }
         let comments: Vec<ReviewComment> = response
             .json()
@@ -598,12 +566,9 @@ async fn fetch_commit_comments(
             .get(&commit_comments_url)
             .send()
             .await
-            .map_err(|e| format!("Failed to fetch commit comments: {}", e))?;
+            .map_err(|e| request_error("Failed to fetch commit comments", e))?;
         if !response.status().is_success() {
-            return Err(format!(
-                "GitHub API error for commit comments: {}",
-                response.status()
-            ));
+            return Err(api_error("GitHub API error for commit comments", response).await);
         }
         let comments: Vec<CommitComment> = response
             .json()
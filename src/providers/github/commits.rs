@@ -1,34 +1,363 @@
-use git2::{Commit, DiffOptions, Oid, Repository, Sort};
-use std::path::{Path, PathBuf};
+use git2::{
+    Commit, Delta, DiffFindOptions, DiffOptions, ErrorCode, Oid, Patch, Repository, Sort, Tree,
+};
 use rayon::prelude::*; // Import Rayon traits
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::clone::EMPTY_REPOSITORY_ERROR;
+use crate::common::time::{civil_from_days, iso_date, local_days, local_iso8601};
 
 /// Represents information extracted for a single commit.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct CommitInfo {
     pub sha: String,
     pub repo_name: String, // Name/slug of the repository (e.g., "owner/repo")
     pub message: String,
     pub author_name: String,
     pub author_email: String,
-    pub author_timestamp: i64, // Seconds since epoch
-    pub author_offset: i32,    // Timezone offset in minutes
+    pub author_timestamp: i64,    // Seconds since epoch
+    pub author_offset: i32,       // Timezone offset in minutes
+    pub author_local_iso: String, // Author time in the author's timezone, ISO 8601
     pub committer_name: String,
     pub committer_email: String,
     pub committer_timestamp: i64,
     pub committer_offset: i32,
+    pub committer_local_iso: String,
     pub additions: usize,
     pub deletions: usize,
     pub is_merge: bool,
+    // Heuristic: committed by someone else or well after it was authored (see `looks_rewritten`)
+    pub amended_or_rebased: bool,
+    pub parents: Vec<String>, // Parent SHAs, first parent first
+    // Per-file changes against the first parent; empty unless `file_stats`
+    pub files: Vec<FileChange>,
+    // Trailer key (lowercased) -> values, e.g. "co-authored-by"; empty unless `parse_trailers`
+    pub trailers: HashMap<String, Vec<String>>,
+    // "signed" or "unsigned" (see `signature_status`)
+    pub signature_status: String,
     // pub branch: Option<String>, // Omitted for complexity/performance reasons
     // pub url: String, // URL construction moved to process_single_commit
 }
 
-/// Calculates additions and deletions for a commit by diffing against its first parent.
-/// Handles the initial commit case (no parents).
+/// How a file was changed by a commit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Deleted,
+    Modified,
+    Renamed,
+    Copied,
+    TypeChanged,
+}
+
+impl ChangeKind {
+    fn from_delta(delta: Delta) -> Self {
+        match delta {
+            Delta::Added => ChangeKind::Added,
+            Delta::Deleted => ChangeKind::Deleted,
+            Delta::Renamed => ChangeKind::Renamed,
+            Delta::Copied => ChangeKind::Copied,
+            Delta::Typechange => ChangeKind::TypeChanged,
+            _ => ChangeKind::Modified,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeKind::Added => "added",
+            ChangeKind::Deleted => "deleted",
+            ChangeKind::Modified => "modified",
+            ChangeKind::Renamed => "renamed",
+            ChangeKind::Copied => "copied",
+            ChangeKind::TypeChanged => "type_changed",
+        }
+    }
+}
+
+/// A single file touched by a commit.
+#[derive(Clone, Debug, Serialize)]
+pub struct FileChange {
+    pub path: String,
+    pub old_path: Option<String>, // Set for renames and copies
+    pub status: ChangeKind,
+    pub additions: usize,
+    pub deletions: usize,
+}
+
+/// Options controlling which commits `extract_commits_parallel` returns.
+#[derive(Clone, Debug, Default)]
+pub struct CommitAnalysisOptions {
+    /// Keep only commits whose author email matches one of these (case-insensitive).
+    /// Like multiple `git log --author` flags, the entries are ORed together.
+    pub author_emails: Option<Vec<String>>,
+    /// Revision to walk history from instead of HEAD. `ALL_REFS` walks every ref,
+    /// like `git log --all`.
+    pub rev: Option<String>,
+    /// Skip commits reachable from this SHA, like `git log <since_sha>..HEAD`.
+    pub since_sha: Option<String>,
+    /// Parse `Key: value` trailers (`Co-authored-by:`, `Signed-off-by:`, ...) from each
+    /// message into `CommitInfo::trailers`. Off by default to keep large histories lean.
+    pub parse_trailers: bool,
+    /// Stop after this many commits, newest first, like `git log -n`. Counted after the
+    /// other filters, so it is the number of commits returned.
+    pub max_count: Option<usize>,
+    /// Keep only commits that change something under this repository-relative file or
+    /// directory, like `git log -- <path>`; their stats and files are limited to it too.
+    /// The path must exist in the walked revision (HEAD for `ALL_REFS`).
+    pub path: Option<String>,
+    /// Fill `CommitInfo::files` with per-file changes, detecting renames like `git log -M`.
+    /// Off by default since it diffs every file separately; the totals then count a
+    /// renamed file as deleted and re-added.
+    pub file_stats: bool,
+}
+
+/// Sentinel `rev` value selecting the history reachable from any ref.
+pub const ALL_REFS: &str = "--all";
+
+impl CommitAnalysisOptions {
+    fn matches_author(&self, email: &str) -> bool {
+        self.author_emails
+            .as_ref()
+            .is_none_or(|emails| emails.iter().any(|e| e.eq_ignore_ascii_case(email)))
+    }
+
+    /// The `path` filter without leading or trailing slashes.
+    fn scoped_path(&self) -> Option<&str> {
+        self.path.as_deref().map(|path| path.trim_matches('/'))
+    }
+}
+
+/// "signed" for a GPG- or SSH-signed commit, "unsigned" otherwise. libgit2 can extract a
+/// signature but not check it against the signer's key, so "signed" says nothing about
+/// whether the signature is valid.
+fn signature_status(repo: &Repository, oid: Oid) -> Result<&'static str, git2::Error> {
+    match repo.extract_signature(&oid, None) {
+        Ok(_) => Ok("signed"),
+        Err(e) if e.code() == ErrorCode::NotFound => Ok("unsigned"),
+        Err(e) => Err(e),
+    }
+}
+
+/// Whether `commit` changes anything under `path`. Like `git log -- <path>`, a merge only
+/// counts when it differs from every parent there, so merging in a branch that touched the
+/// path reports the branch's commits rather than the merge.
+fn touches_path(commit: &Commit, path: &Path) -> Result<bool, git2::Error> {
+    let entry_at = |tree: Tree| tree.get_path(path).ok().map(|entry| entry.id());
+    let here = entry_at(commit.tree()?);
+    if commit.parent_count() == 0 {
+        return Ok(here.is_some());
+    }
+    for parent in commit.parents() {
+        if entry_at(parent.tree()?) == here {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Aggregate statistics over a repository's commit history.
+#[derive(Clone, Debug, Default)]
+pub struct CommitSummary {
+    pub total_commits: usize,
+    pub total_additions: usize,
+    pub total_deletions: usize,
+    pub first_commit_timestamp: Option<i64>, // Seconds since epoch, by author time
+    pub last_commit_timestamp: Option<i64>,
+    pub unique_authors: usize, // Distinct author emails, case-insensitive
+}
+
+/// Folds extracted commits into a single summary.
+pub fn summarize_commits(commits: &[CommitInfo]) -> CommitSummary {
+    let mut authors = HashSet::new();
+    let mut summary = CommitSummary::default();
+
+    for commit in commits {
+        summary.total_commits += 1;
+        summary.total_additions += commit.additions;
+        summary.total_deletions += commit.deletions;
+        let ts = commit.author_timestamp;
+        summary.first_commit_timestamp =
+            Some(summary.first_commit_timestamp.map_or(ts, |t| t.min(ts)));
+        summary.last_commit_timestamp =
+            Some(summary.last_commit_timestamp.map_or(ts, |t| t.max(ts)));
+        authors.insert(commit.author_email.to_lowercase());
+    }
+
+    summary.unique_authors = authors.len();
+    summary
+}
+
+/// Trailer key that credits an additional author of a commit.
+pub const CO_AUTHORED_BY: &str = "co-authored-by";
+
+/// An undirected co-authorship edge; `source` sorts before `target`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct CoauthorEdge {
+    pub source: String,
+    pub target: String,
+    pub weight: usize, // Commits the two wrote together
+}
+
+/// Who wrote commits with whom, from `Co-authored-by` trailers.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct CoauthorshipGraph {
+    // Identity (lowercased email) -> commits it authored or co-authored, for every identity
+    // on at least one edge
+    pub nodes: HashMap<String, usize>,
+    pub edges: Vec<CoauthorEdge>, // Heaviest first
+}
+
+/// Builds the co-authorship graph of commits whose trailers were parsed.
+///
+/// Each commit connects its author with every `Co-authored-by` identity. Identities are
+/// emails compared case-insensitively; `identity_map` maps an alias email to the email it
+/// should be counted under, as for blame authorship.
+pub fn coauthorship_graph(
+    commits: &[CommitInfo],
+    identity_map: &HashMap<String, String>,
+) -> CoauthorshipGraph {
+    let identities: HashMap<String, String> = identity_map
+        .iter()
+        .map(|(alias, email)| (alias.to_lowercase(), email.to_lowercase()))
+        .collect();
+    let identity = |email: &str| {
+        let email = email.trim().to_lowercase();
+        identities.get(&email).cloned().unwrap_or(email)
+    };
+
+    let mut commit_counts: HashMap<String, usize> = HashMap::new();
+    let mut weights: HashMap<(String, String), usize> = HashMap::new();
+    for commit in commits {
+        let author = identity(&commit.author_email);
+        let mut people: HashSet<String> = HashSet::from([author.clone()]);
+        for value in commit.trailers.get(CO_AUTHORED_BY).into_iter().flatten() {
+            // "Name <email>"; a bare value is taken as the email itself
+            let email = match (value.rfind('<'), value.rfind('>')) {
+                (Some(start), Some(end)) if start < end => &value[start + 1..end],
+                _ => value.as_str(),
+            };
+            let coauthor = identity(email);
+            if coauthor.is_empty() || !people.insert(coauthor.clone()) {
+                continue; // Listed twice, or the author crediting themselves
+            }
+            let pair = if author < coauthor {
+                (author.clone(), coauthor)
+            } else {
+                (coauthor, author.clone())
+            };
+            *weights.entry(pair).or_default() += 1;
+        }
+        for person in people {
+            *commit_counts.entry(person).or_default() += 1;
+        }
+    }
+
+    let mut edges: Vec<CoauthorEdge> = weights
+        .into_iter()
+        .map(|((source, target), weight)| CoauthorEdge {
+            source,
+            target,
+            weight,
+        })
+        .collect();
+    edges.sort_by(|a, b| {
+        b.weight
+            .cmp(&a.weight)
+            .then_with(|| (&a.source, &a.target).cmp(&(&b.source, &b.target)))
+    });
+    let connected: HashSet<&String> = edges.iter().flat_map(|e| [&e.source, &e.target]).collect();
+    commit_counts.retain(|person, _| connected.contains(person));
+    CoauthorshipGraph {
+        nodes: commit_counts,
+        edges,
+    }
+}
+
+/// Bin width for `commit_activity`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ActivityBucket {
+    Day,
+    Week, // Starting on Monday, as in ISO 8601
+    Month,
+}
+
+impl ActivityBucket {
+    /// Parses the `bucket` accepted by `RepoManager.commit_activity`
+    pub fn parse(bucket: &str) -> Result<Self, String> {
+        match bucket.to_ascii_lowercase().as_str() {
+            "day" => Ok(ActivityBucket::Day),
+            "week" => Ok(ActivityBucket::Week),
+            "month" => Ok(ActivityBucket::Month),
+            _ => Err(format!(
+                "Invalid bucket '{}': expected 'day', 'week' or 'month'",
+                bucket
+            )),
+        }
+    }
+
+    /// ISO 8601 date of the first day of the bin holding `days` (days since 1970-01-01)
+    fn start(self, days: i64) -> String {
+        match self {
+            ActivityBucket::Day => iso_date(days),
+            // 1970-01-01 was a Thursday, three days after a Monday
+            ActivityBucket::Week => iso_date(days - (days + 3).rem_euclid(7)),
+            ActivityBucket::Month => {
+                let (year, month, _) = civil_from_days(days);
+                format!("{:04}-{:02}-01", year, month)
+            }
+        }
+    }
+}
+
+/// Commits and changed lines in one bin of `commit_activity`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ActivityBin {
+    pub start: String, // First day of the bin, e.g. "2024-01-29"
+    pub commits: usize,
+    pub additions: usize,
+    pub deletions: usize,
+}
+
+/// Bins commits by author date, oldest bin first; bins without commits are left out.
+///
+/// Dates are local to each author's own timezone offset, so a commit made late in the
+/// evening counts for that evening. `utc_offset` (minutes) bins every commit in that one
+/// timezone instead, e.g. the course's.
+pub fn commit_activity(
+    commits: &[CommitInfo],
+    bucket: ActivityBucket,
+    utc_offset: Option<i32>,
+) -> Vec<ActivityBin> {
+    let mut bins: BTreeMap<String, ActivityBin> = BTreeMap::new();
+    for commit in commits {
+        let offset = utc_offset.unwrap_or(commit.author_offset);
+        let start = bucket.start(local_days(commit.author_timestamp, offset));
+        let bin = bins.entry(start.clone()).or_insert_with(|| ActivityBin {
+            start,
+            commits: 0,
+            additions: 0,
+            deletions: 0,
+        });
+        bin.commits += 1;
+        bin.additions += commit.additions;
+        bin.deletions += commit.deletions;
+    }
+    bins.into_values().collect()
+}
+
+/// Calculates additions, deletions and, with `file_stats`, per-file changes for a commit
+/// by diffing against its first parent, limited to the `path` filter. Handles the initial
+/// commit case (no parents).
 fn calculate_diff_stats(
     repo: &Repository,
     commit: &Commit,
-) -> Result<(usize, usize), git2::Error> {
+    options: &CommitAnalysisOptions,
+) -> Result<(usize, usize, Vec<FileChange>), git2::Error> {
     let commit_tree = commit.tree()?;
     let parent_tree = if commit.parent_count() > 0 {
         let parent = commit.parent(0)?;
@@ -36,15 +365,170 @@ fn calculate_diff_stats(
     } else {
         None // Initial commit
     };
+    diff_trees(
+        repo,
+        parent_tree.as_ref(),
+        &commit_tree,
+        true,
+        options.file_stats,
+        options.scoped_path(),
+    )
+}
 
+/// Diffs two trees, returning total additions and deletions. With `per_file`, renames are
+/// detected like `git log -M` and the per-file changes are returned too, so a moved file is
+/// reported once with its old path and only its edited lines are counted. `old_tree` of None
+/// diffs against the empty tree; `path` limits the diff to that file or directory.
+fn diff_trees(
+    repo: &Repository,
+    old_tree: Option<&Tree>,
+    new_tree: &Tree,
+    ignore_whitespace: bool,
+    per_file: bool,
+    path: Option<&str>,
+) -> Result<(usize, usize, Vec<FileChange>), git2::Error> {
     let mut diff_opts = DiffOptions::new();
     diff_opts.ignore_submodules(true);
-    diff_opts.ignore_whitespace(true);
+    diff_opts.ignore_whitespace(ignore_whitespace);
+    if let Some(path) = path {
+        // A literal path rather than a glob; a directory matches everything below it
+        diff_opts.pathspec(path).disable_pathspec_match(true);
+    }
+
+    let mut diff = repo.diff_tree_to_tree(old_tree, Some(new_tree), Some(&mut diff_opts))?;
+    if !per_file {
+        let stats = diff.stats()?;
+        return Ok((stats.insertions(), stats.deletions(), Vec::new()));
+    }
+    let mut find_opts = DiffFindOptions::new();
+    find_opts.renames(true);
+    diff.find_similar(Some(&mut find_opts))?;
+
+    let mut files = Vec::with_capacity(diff.deltas().len());
+    for (idx, delta) in diff.deltas().enumerate() {
+        let status = ChangeKind::from_delta(delta.status());
+        let new_path = delta
+            .new_file()
+            .path()
+            .map(|p| p.to_string_lossy().into_owned());
+        let old_path = delta
+            .old_file()
+            .path()
+            .map(|p| p.to_string_lossy().into_owned());
+        // Binary files have no patch and so no line counts
+        let (additions, deletions) = match Patch::from_diff(&diff, idx)? {
+            Some(patch) => {
+                let (_, additions, deletions) = patch.line_stats()?;
+                (additions, deletions)
+            }
+            None => (0, 0),
+        };
+        files.push(FileChange {
+            path: new_path
+                .clone()
+                .or_else(|| old_path.clone())
+                .unwrap_or_default(),
+            old_path: match status {
+                ChangeKind::Renamed | ChangeKind::Copied => old_path,
+                _ => None,
+            },
+            status,
+            additions,
+            deletions,
+        });
+    }
 
-    let diff =
-        repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&commit_tree), Some(&mut diff_opts))?;
-    let stats = diff.stats()?;
-    Ok((stats.insertions(), stats.deletions()))
+    // The per-file patches already hold the line counts, so don't diff again for totals
+    let additions = files.iter().map(|file| file.additions).sum();
+    let deletions = files.iter().map(|file| file.deletions).sum();
+    Ok((additions, deletions, files))
+}
+
+/// Line changes between two revisions, like `git diff --numstat base..head`.
+#[derive(Clone, Debug, Serialize)]
+pub struct DiffStats {
+    pub base_sha: String, // Commit diffed from; the merge base for a `base...` spec
+    pub head_sha: String,
+    pub additions: usize,
+    pub deletions: usize,
+    pub files: Vec<FileChange>,
+}
+
+/// Suffix on a diff base that selects its merge base with head, like `git diff base...head`.
+pub const MERGE_BASE_SUFFIX: &str = "...";
+
+/// Diffs `head` against `base` without walking history. A `base` ending in `...` (e.g.
+/// `main...`) diffs from the merge base of the two, showing only what `head` changed.
+/// Whitespace changes are counted, as `git diff --numstat` does.
+pub fn diff_revisions(repo_path: &Path, base: &str, head: &str) -> Result<DiffStats, String> {
+    let repo = Repository::open(repo_path)
+        .map_err(|e| format!("Failed to open repository at {:?}: {}", repo_path, e))?;
+    let resolve = |rev: &str| {
+        repo.revparse_single(rev)
+            .and_then(|obj| obj.peel_to_commit())
+            .map_err(|e| format!("Revision '{}' not found: {}", rev, e))
+    };
+
+    let head_commit = resolve(head)?;
+    let base_commit = match base.strip_suffix(MERGE_BASE_SUFFIX) {
+        Some(base) => {
+            let base_commit = resolve(base)?;
+            let merge_base = repo
+                .merge_base(base_commit.id(), head_commit.id())
+                .map_err(|e| format!("No merge base of '{}' and '{}': {}", base, head, e))?;
+            repo.find_commit(merge_base)
+                .map_err(|e| format!("Failed to find merge base {}: {}", merge_base, e))?
+        }
+        None => resolve(base)?,
+    };
+
+    let base_tree = base_commit
+        .tree()
+        .map_err(|e| format!("Failed to read tree: {}", e))?;
+    let head_tree = head_commit
+        .tree()
+        .map_err(|e| format!("Failed to read tree: {}", e))?;
+    let (additions, deletions, files) =
+        diff_trees(&repo, Some(&base_tree), &head_tree, false, true, None)
+            .map_err(|e| format!("Failed to diff {}..{}: {}", base, head, e))?;
+    Ok(DiffStats {
+        base_sha: base_commit.id().to_string(),
+        head_sha: head_commit.id().to_string(),
+        additions,
+        deletions,
+        files,
+    })
+}
+
+/// Committer time may trail author time by this much before a commit counts as rewritten
+const REWRITE_THRESHOLD_SECS: i64 = 60;
+
+/// Flags commits whose committer differs from the author, or that were committed well after
+/// they were authored, as happens when history is amended, rebased or cherry-picked.
+///
+/// This is a heuristic, not proof: patches applied by a maintainer or merged through a web UI
+/// are flagged too, and a rewrite that resets the committer date goes unnoticed.
+fn looks_rewritten(author: &git2::Signature, committer: &git2::Signature) -> bool {
+    let same_person = author.name() == committer.name()
+        && author.email().map(str::to_lowercase) == committer.email().map(str::to_lowercase);
+    let delay = committer.when().seconds() - author.when().seconds();
+    !same_person || delay.abs() > REWRITE_THRESHOLD_SECS
+}
+
+/// Collects a commit message's trailers, as `git interpret-trailers` finds them, keyed by
+/// lowercased key since git compares trailer keys case-insensitively.
+fn parse_trailers(message: &str) -> HashMap<String, Vec<String>> {
+    let mut trailers: HashMap<String, Vec<String>> = HashMap::new();
+    // A message with no trailer block (or an interior NUL) simply has none
+    if let Ok(parsed) = git2::message_trailers_strs(message) {
+        for (key, value) in parsed.iter() {
+            trailers
+                .entry(key.to_lowercase())
+                .or_default()
+                .push(value.to_string());
+        }
+    }
+    trailers
 }
 
 /// Extracts information for a single commit OID.
@@ -54,6 +538,7 @@ fn process_single_commit(
     repo_path: &Path,
     oid: Oid,
     repo_name: &str,
+    options: &CommitAnalysisOptions,
 ) -> Result<CommitInfo, String> {
     // Open repo handle specific to this thread/task
     let repo = Repository::open(repo_path)
@@ -63,7 +548,7 @@ fn process_single_commit(
         .find_commit(oid)
         .map_err(|e| format!("Failed to find commit {}: {}", oid, e))?;
 
-    let (additions, deletions) = calculate_diff_stats(&repo, &commit)
+    let (additions, deletions, files) = calculate_diff_stats(&repo, &commit, options)
         .map_err(|e| format!("Failed to calculate stats for commit {}: {}", oid, e))?;
 
     let author = commit.author();
@@ -71,21 +556,41 @@ fn process_single_commit(
     let author_time = author.when();
     let committer_time = committer.when();
 
+    let amended_or_rebased = looks_rewritten(&author, &committer);
+    let signature_status = signature_status(&repo, oid)
+        .map_err(|e| format!("Failed to read signature of commit {}: {}", oid, e))?;
+    let message = commit.message().unwrap_or("");
+    let trailers = if options.parse_trailers {
+        parse_trailers(message)
+    } else {
+        HashMap::new()
+    };
+
     let commit_info = CommitInfo {
         sha: oid.to_string(),
         repo_name: repo_name.to_string(), // Include the repo name
-        message: commit.message().unwrap_or("").trim().to_string(),
+        message: message.trim().to_string(),
         author_name: author.name().unwrap_or("").to_string(),
         author_email: author.email().unwrap_or("").to_string(),
         author_timestamp: author_time.seconds(),
         author_offset: author_time.offset_minutes(),
+        author_local_iso: local_iso8601(author_time.seconds(), author_time.offset_minutes()),
         committer_name: committer.name().unwrap_or("").to_string(),
         committer_email: committer.email().unwrap_or("").to_string(),
         committer_timestamp: committer_time.seconds(),
         committer_offset: committer_time.offset_minutes(),
+        committer_local_iso: local_iso8601(
+            committer_time.seconds(),
+            committer_time.offset_minutes(),
+        ),
         additions,
         deletions,
         is_merge: commit.parent_count() > 1,
+        amended_or_rebased,
+        parents: commit.parent_ids().map(|id| id.to_string()).collect(),
+        files,
+        trailers,
+        signature_status: signature_status.to_string(),
         // url: format!("https://github.com/{}/commit/{}", repo_name, oid), // Example URL
     };
 
@@ -95,28 +600,19 @@ fn process_single_commit(
 /// Extracts commit history information from a cloned repository using parallel processing.
 /// This function is synchronous but performs work in parallel using Rayon.
 pub fn extract_commits_parallel(
-    repo_path: PathBuf,      // Take ownership of path
-    repo_name: String, // Take ownership of name
+    repo_path: PathBuf, // Take ownership of path
+    repo_name: String,  // Take ownership of name
+    options: &CommitAnalysisOptions,
 ) -> Result<Vec<CommitInfo>, String> {
     // --- Step 1: Get all commit OIDs (Sequential) ---
-    let oids = {
-        let repo = Repository::open(&repo_path)
-            .map_err(|e| format!("Failed to open repository at {:?}: {}", repo_path, e))?;
-        let mut revwalk = repo.revwalk().map_err(|e| format!("Failed to create revwalk: {}", e))?;
-        revwalk.push_head().map_err(|e| format!("Failed to push HEAD: {}", e))?;
-        // Consider adding other refs like all branches if needed: revwalk.push_glob("refs/heads/*")?;
-        revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME).map_err(|e| format!("Failed to set revwalk sorting: {}", e))?;
-
-        let oids: Result<Vec<Oid>, _> = revwalk.collect();
-        oids.map_err(|e| format!("Failed during revwalk iteration: {}", e))?
-    };
+    let oids = collect_commit_oids(&repo_path, options)?;
 
     // --- Step 2: Process commits in parallel using Rayon ---
     let results: Vec<Result<CommitInfo, String>> = oids
         .into_par_iter()
         .map(|oid| {
             // Clone repo_path and repo_name for the closure
-            process_single_commit(&repo_path, oid, &repo_name)
+            process_single_commit(&repo_path, oid, &repo_name, options)
         })
         .collect();
 
@@ -134,8 +630,222 @@ pub fn extract_commits_parallel(
     if !errors.is_empty() {
         // If any errors occurred, return a combined error message
         // You might want more sophisticated error reporting
-        Err(format!("Errors encountered during commit processing: {}", errors.join("; ")))
+        Err(format!(
+            "Errors encountered during commit processing: {}",
+            errors.join("; ")
+        ))
     } else {
         Ok(commit_infos)
     }
 }
+
+/// Walks the history selected by `options` and returns the matching commit OIDs in log order.
+fn collect_commit_oids(
+    repo_path: &Path,
+    options: &CommitAnalysisOptions,
+) -> Result<Vec<Oid>, String> {
+    let repo = Repository::open(repo_path)
+        .map_err(|e| format!("Failed to open repository at {:?}: {}", repo_path, e))?;
+    // A new repository has no history to walk; only an explicit revision is an error
+    if repo.is_empty().unwrap_or(false) {
+        return match options.rev.as_deref() {
+            None | Some(ALL_REFS) => Ok(Vec::new()),
+            Some(_) => Err(EMPTY_REPOSITORY_ERROR.to_string()),
+        };
+    }
+    let mut revwalk = repo
+        .revwalk()
+        .map_err(|e| format!("Failed to create revwalk: {}", e))?;
+    // The commit the path filter is validated against
+    let tip = match options.rev.as_deref() {
+        None => {
+            revwalk
+                .push_head()
+                .map_err(|e| format!("Failed to push HEAD: {}", e))?;
+            repo.head().and_then(|head| head.peel_to_commit()).ok()
+        }
+        Some(ALL_REFS) => {
+            // The revwalk yields each commit once, however many refs reach it
+            revwalk
+                .push_glob("*")
+                .map_err(|e| format!("Failed to push refs: {}", e))?;
+            if repo.head().is_ok() {
+                revwalk
+                    .push_head()
+                    .map_err(|e| format!("Failed to push HEAD: {}", e))?;
+            }
+            repo.head().and_then(|head| head.peel_to_commit()).ok()
+        }
+        Some(rev) => {
+            let commit = repo
+                .revparse_single(rev)
+                .and_then(|obj| obj.peel_to_commit())
+                .map_err(|e| format!("Revision '{}' not found: {}", rev, e))?;
+            revwalk
+                .push(commit.id())
+                .map_err(|e| format!("Failed to push {}: {}", rev, e))?;
+            Some(commit)
+        }
+    };
+    let scope = options.scoped_path().map(Path::new);
+    if let (Some(scope), Some(tip)) = (scope, &tip) {
+        let tree = tip
+            .tree()
+            .map_err(|e| format!("Failed to read tree of {}: {}", tip.id(), e))?;
+        if tree.get_path(scope).is_err() {
+            return Err(format!(
+                "Path '{}' not found in {}",
+                scope.display(),
+                tip.id()
+            ));
+        }
+    }
+    if let Some(since) = &options.since_sha {
+        let oid = Oid::from_str(since).map_err(|e| format!("Invalid SHA {}: {}", since, e))?;
+        revwalk
+            .hide(oid)
+            .map_err(|e| format!("Failed to hide {}: {}", since, e))?;
+    }
+    revwalk
+        .set_sorting(Sort::TOPOLOGICAL | Sort::TIME)
+        .map_err(|e| format!("Failed to set revwalk sorting: {}", e))?;
+
+    // Filter by author and path, and stop at max_count, before the (expensive) diff stats
+    // are computed
+    let mut kept = Vec::new();
+    for oid in revwalk {
+        if options.max_count.is_some_and(|max| kept.len() >= max) {
+            break;
+        }
+        let oid = oid.map_err(|e| format!("Failed during revwalk iteration: {}", e))?;
+        if options.author_emails.is_some() || scope.is_some() {
+            let commit = repo
+                .find_commit(oid)
+                .map_err(|e| format!("Failed to find commit {}: {}", oid, e))?;
+            if !options.matches_author(commit.author().email().unwrap_or("")) {
+                continue;
+            }
+            if let Some(scope) = scope {
+                let touched = touches_path(&commit, scope)
+                    .map_err(|e| format!("Failed to compare trees of {}: {}", oid, e))?;
+                if !touched {
+                    continue;
+                }
+            }
+        }
+        kept.push(oid);
+    }
+    Ok(kept)
+}
+
+/// Commits processed in parallel per batch when streaming
+const STREAM_BATCH_SIZE: usize = 1024;
+
+/// Extracts commits like `extract_commits_parallel`, but hands each one to `sink` in log order
+/// instead of collecting them, so only one batch of `CommitInfo`s is in memory at a time.
+/// Stops at the first processing or sink error and returns the number of commits handed over.
+pub fn for_each_commit<F>(
+    repo_path: &Path,
+    repo_name: &str,
+    options: &CommitAnalysisOptions,
+    mut sink: F,
+) -> Result<usize, String>
+where
+    F: FnMut(CommitInfo) -> Result<(), String>,
+{
+    let oids = collect_commit_oids(repo_path, options)?;
+    let mut count = 0;
+    for batch in oids.chunks(STREAM_BATCH_SIZE) {
+        let results: Vec<Result<CommitInfo, String>> = batch
+            .par_iter()
+            .map(|oid| process_single_commit(repo_path, *oid, repo_name, options))
+            .collect();
+        for result in results {
+            sink(result?)?;
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// Writes the commit history of a repository to `output` as JSON lines, one commit per line,
+/// without holding the whole history in memory. Returns the number of commits written.
+pub fn stream_commits_to_file(
+    repo_path: &Path,
+    output: &Path,
+    options: &CommitAnalysisOptions,
+) -> Result<usize, String> {
+    let file = File::create(output)
+        .map_err(|e| format!("Failed to create {}: {}", output.display(), e))?;
+    let mut writer = BufWriter::new(file);
+    let write_error =
+        |e: &dyn std::fmt::Display| format!("Failed to write {}: {}", output.display(), e);
+    let count = for_each_commit(repo_path, "", options, |commit| {
+        serde_json::to_writer(&mut writer, &commit).map_err(|e| write_error(&e))?;
+        writer.write_all(b"\n").map_err(|e| write_error(&e))
+    })?;
+    writer.flush().map_err(|e| write_error(&e))?;
+    Ok(count)
+}
+
+/// Commits added since a previously seen HEAD.
+#[derive(Clone, Debug)]
+pub struct IncrementalCommits {
+    pub commits: Vec<CommitInfo>,
+    pub head_sha: String,   // Pass as `last_sha` on the next call
+    pub full_history: bool, // `last_sha` wasn't an ancestor of HEAD, so everything was returned
+}
+
+/// Extracts only the commits reachable from HEAD but not from `last_sha`.
+///
+/// If `last_sha` is unknown or no longer in HEAD's history (e.g. after a force-push),
+/// falls back to the full history and sets `full_history`.
+pub fn extract_commits_since(
+    repo_path: PathBuf,
+    repo_name: String,
+    last_sha: &str,
+) -> Result<IncrementalCommits, String> {
+    let (head, reachable) = {
+        let repo = Repository::open(&repo_path)
+            .map_err(|e| format!("Failed to open repository at {:?}: {}", repo_path, e))?;
+        let head = repo
+            .head()
+            .and_then(|h| h.peel_to_commit())
+            .map_err(|e| format!("Failed to resolve HEAD: {}", e))?
+            .id();
+        let reachable = Oid::from_str(last_sha)
+            .ok()
+            .filter(|last| repo.find_commit(*last).is_ok())
+            .is_some_and(|last| {
+                head == last || repo.graph_descendant_of(head, last).unwrap_or(false)
+            });
+        (head, reachable)
+    };
+
+    let options = CommitAnalysisOptions {
+        since_sha: reachable.then(|| last_sha.to_string()),
+        ..Default::default()
+    };
+    let commits = extract_commits_parallel(repo_path, repo_name, &options)?;
+    Ok(IncrementalCommits {
+        commits,
+        head_sha: head.to_string(),
+        full_history: !reachable,
+    })
+}
+
+/// Extracts commit information from multiple repositories in parallel.
+///
+/// Repositories are spread over Rayon's pool, which is sized to the number of CPUs.
+pub fn extract_commits_bulk(
+    repo_paths: Vec<(String, PathBuf)>,
+    options: &CommitAnalysisOptions,
+) -> HashMap<String, Result<Vec<CommitInfo>, String>> {
+    repo_paths
+        .into_par_iter()
+        .map(|(repo_url, path)| {
+            let result = extract_commits_parallel(path, String::new(), options);
+            (repo_url, result)
+        })
+        .collect()
+}
@@ -0,0 +1,183 @@
+use git2::{ObjectType, Repository, TreeWalkMode, TreeWalkResult};
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// Finds leftover merge conflict markers in the files committed at HEAD.
+///
+/// Returns a map of file path to the 1-based line numbers holding a marker;
+/// files without markers are omitted. When `paths` is given only those files
+/// are scanned. Binary files are skipped.
+pub fn find_conflict_markers(
+    repo_path: &Path,
+    paths: Option<Vec<String>>,
+) -> Result<HashMap<String, Vec<usize>>, String> {
+    let repo = Repository::open(repo_path)
+        .map_err(|e| format!("Failed to open repository at {:?}: {}", repo_path, e))?;
+    let tree = repo
+        .head()
+        .and_then(|h| h.peel_to_tree())
+        .map_err(|e| format!("Failed to resolve HEAD tree: {}", e))?;
+
+    let wanted: Option<HashSet<String>> = paths.map(|p| p.into_iter().collect());
+
+    // Collect blob ids first; the walk callback can't propagate errors
+    let mut blobs = Vec::new();
+    tree.walk(TreeWalkMode::PreOrder, |dir, entry| {
+        if entry.kind() == Some(ObjectType::Blob) {
+            if let Some(name) = entry.name() {
+                let path = format!("{}{}", dir, name);
+                if wanted.as_ref().is_none_or(|w| w.contains(&path)) {
+                    blobs.push((path, entry.id()));
+                }
+            }
+        }
+        TreeWalkResult::Ok
+    })
+    .map_err(|e| format!("Failed to walk tree: {}", e))?;
+
+    let mut results = HashMap::new();
+    for (path, oid) in blobs {
+        let blob = repo
+            .find_blob(oid)
+            .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+        if blob.is_binary() {
+            continue;
+        }
+
+        let content = String::from_utf8_lossy(blob.content());
+        let hits: Vec<usize> = content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| is_conflict_marker(line))
+            .map(|(idx, _)| idx + 1)
+            .collect();
+        if !hits.is_empty() {
+            results.insert(path, hits);
+        }
+    }
+
+    Ok(results)
+}
+
+/// Checks whether a line is one of git's conflict markers
+fn is_conflict_marker(line: &str) -> bool {
+    let line = line.trim_end_matches('\r');
+    line.starts_with("<<<<<<<") || line.starts_with(">>>>>>>") || line == "======="
+}
+
+/// Lists the files tracked in the repository's index, like `git ls-files`.
+///
+/// Paths are relative to the repository root and sorted. When `glob` is given
+/// only matching paths are returned: `*` and `?` don't cross `/`, while `**`
+/// matches any number of directories (e.g. `src/**/*.py`).
+pub fn list_files(repo_path: &Path, glob: Option<&str>) -> Result<Vec<String>, String> {
+    let repo = Repository::open(repo_path)
+        .map_err(|e| format!("Failed to open repository at {:?}: {}", repo_path, e))?;
+    let index = repo
+        .index()
+        .map_err(|e| format!("Failed to read index: {}", e))?;
+    let pattern = glob.map(glob_to_regex).transpose()?;
+
+    let mut paths: Vec<String> = index
+        .iter()
+        .map(|entry| String::from_utf8_lossy(&entry.path).into_owned())
+        .filter(|path| pattern.as_ref().is_none_or(|re| re.is_match(path)))
+        .collect();
+    // Conflicted files have one entry per stage
+    paths.dedup();
+    Ok(paths)
+}
+
+/// Translates a shell-style glob into an anchored regex
+fn glob_to_regex(glob: &str) -> Result<Regex, String> {
+    let mut pattern = String::from("^");
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    // "**/" also matches no directory at all
+                    chars.next();
+                    pattern.push_str("(?:.*/)?");
+                } else {
+                    pattern.push_str(".*");
+                }
+            }
+            '*' => pattern.push_str("[^/]*"),
+            '?' => pattern.push_str("[^/]"),
+            '[' => {
+                let mut class = String::new();
+                let mut closed = false;
+                for c in chars.by_ref() {
+                    if c == ']' && !class.is_empty() && class != "!" {
+                        closed = true;
+                        break;
+                    }
+                    class.push(c);
+                }
+                if !closed {
+                    return Err(format!("Invalid glob pattern {:?}: unclosed '['", glob));
+                }
+                let class = match class.strip_prefix('!') {
+                    Some(rest) => format!("^{}", rest),
+                    None => class,
+                };
+                pattern.push('[');
+                pattern.push_str(&class.replace('\\', "\\\\"));
+                pattern.push(']');
+            }
+            _ => pattern.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    pattern.push('$');
+    Regex::new(&pattern).map_err(|e| format!("Invalid glob pattern {:?}: {}", glob, e))
+}
+
+/// Byte-order marks of UTF-8, UTF-16 BE and UTF-16 LE
+const BOMS: [&[u8]; 3] = [b"\xEF\xBB\xBF", b"\xFE\xFF", b"\xFF\xFE"];
+
+/// Line-ending and encoding facts about a committed file.
+#[derive(Debug, Clone)]
+pub struct FileEncoding {
+    pub has_crlf: bool, // At least one "\r\n" line ending
+    pub is_utf8: bool,  // The whole content decodes as UTF-8 (a UTF-8 BOM included)
+    pub has_bom: bool,  // Starts with a UTF-8 or UTF-16 byte-order mark
+    pub byte_count: usize,
+}
+
+/// Inspects the raw bytes of the given files as committed at HEAD, e.g. to flag
+/// submissions with Windows line endings or non-UTF-8 text.
+///
+/// Errors if any path isn't a file at HEAD.
+pub fn file_encoding_report(
+    repo_path: &Path,
+    file_paths: &[String],
+) -> Result<HashMap<String, FileEncoding>, String> {
+    let repo = Repository::open(repo_path)
+        .map_err(|e| format!("Failed to open repository at {:?}: {}", repo_path, e))?;
+    let tree = repo
+        .head()
+        .and_then(|h| h.peel_to_tree())
+        .map_err(|e| format!("Failed to resolve HEAD tree: {}", e))?;
+
+    let mut results = HashMap::new();
+    for path in file_paths {
+        let blob = tree
+            .get_path(Path::new(path))
+            .and_then(|entry| entry.to_object(&repo))
+            .and_then(|object| object.peel_to_blob())
+            .map_err(|e| format!("File '{}' not found at HEAD: {}", path, e))?;
+        let content = blob.content();
+        let encoding = FileEncoding {
+            has_crlf: content.windows(2).any(|pair| pair == b"\r\n"),
+            is_utf8: std::str::from_utf8(content).is_ok(),
+            has_bom: BOMS.iter().any(|bom| content.starts_with(bom)),
+            byte_count: content.len(),
+        };
+        results.insert(path.clone(), encoding);
+    }
+
+    Ok(results)
+}
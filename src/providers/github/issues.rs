@@ -1,9 +1,19 @@
-use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, USER_AGENT};
+use lazy_static::lazy_static;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tokio::task;
 
-use crate::repo::parse_slug_from_url;
+use crate::api_client::{
+    api_error, create_github_client, next_page_url, request_error, ApiClientConfig,
+};
+use crate::repo::parse_repo_parts;
+
+lazy_static! {
+    // ISO 8601 timestamps as accepted by GitHub, e.g. 2024-01-31T12:00:00Z or with a +01:00 offset
+    static ref RE_ISO_TIMESTAMP: Regex =
+        Regex::new(r"^\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(\.\d+)?(Z|[+-]\d{2}:\d{2})$").unwrap();
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IssueInfo {
@@ -21,9 +31,18 @@ pub struct IssueInfo {
     pub is_pull_request: bool,
     pub labels: Vec<String>,
     pub assignees: Vec<String>,
-    pub milestone: Option<String>,
+    pub milestone: Option<MilestoneInfo>,
+    pub milestone_title: Option<String>, // Same as milestone.title, kept for older callers
     pub locked: bool,
     pub html_url: String,
+    // Author's relation to the repository: "OWNER", "MEMBER", "COLLABORATOR", "CONTRIBUTOR",
+    // "FIRST_TIME_CONTRIBUTOR", "FIRST_TIMER" or "NONE"
+    pub author_association: String,
+}
+
+/// `author_association` for responses that leave it out
+pub(crate) fn default_author_association() -> String {
+    "NONE".to_string()
 }
 
 #[derive(Deserialize)]
@@ -37,9 +56,69 @@ struct User {
     id: i64,
 }
 
+/// The milestone an issue is assigned to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MilestoneInfo {
+    pub title: String,
+    pub number: i32,
+    pub state: String,          // "open" or "closed"
+    pub due_on: Option<String>, // ISO 8601 deadline, if one is set
+    pub closed_at: Option<String>,
+}
+
+/// Issue as returned by the REST API
 #[derive(Deserialize)]
-struct Milestone {
+struct IssueResponse {
+    id: i64,
+    number: i32,
     title: String,
+    state: String,
+    created_at: String,
+    updated_at: String,
+    closed_at: Option<String>,
+    user: User,
+    body: Option<String>,
+    comments: i32,
+    pull_request: Option<PullRequest>,
+    labels: Vec<Label>,
+    assignees: Vec<User>,
+    milestone: Option<MilestoneInfo>,
+    locked: bool,
+    html_url: String,
+    #[serde(default = "default_author_association")]
+    author_association: String,
+}
+
+#[derive(Deserialize)]
+struct PullRequest {
+    #[allow(dead_code)]
+    url: String,
+}
+
+impl IssueResponse {
+    fn into_info(self) -> IssueInfo {
+        IssueInfo {
+            id: self.id,
+            number: self.number,
+            title: self.title,
+            state: self.state,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            closed_at: self.closed_at,
+            user_login: self.user.login,
+            user_id: self.user.id,
+            body: self.body,
+            comments_count: self.comments,
+            is_pull_request: self.pull_request.is_some(),
+            labels: self.labels.into_iter().map(|l| l.name).collect(),
+            assignees: self.assignees.into_iter().map(|a| a.login).collect(),
+            milestone_title: self.milestone.as_ref().map(|m| m.title.clone()),
+            milestone: self.milestone,
+            locked: self.locked,
+            html_url: self.html_url,
+            author_association: self.author_association,
+        }
+    }
 }
 
 /// Fetches issue information for multiple repositories concurrently
@@ -50,11 +129,22 @@ pub async fn fetch_issues(
     repo_urls: Vec<String>,
     github_username: &str,
     github_token: &str,
+    api_config: &ApiClientConfig,
     state: Option<&str>, // "open", "closed", "all"
     max_pages: Option<usize>,
+    since: Option<&str>, // Only issues updated at or after this ISO 8601 timestamp
 ) -> Result<HashMap<String, Result<Vec<IssueInfo>, String>>, String> {
+    if let Some(since) = since {
+        if !RE_ISO_TIMESTAMP.is_match(since) {
+            return Err(format!(
+                "Invalid 'since' timestamp '{}': expected ISO 8601, e.g. 2024-01-31T12:00:00Z",
+                since
+            ));
+        }
+    }
+
     // Create a GitHub client
-    let client = match create_github_client(github_token) {
+    let client = match create_github_client(github_token, api_config) {
         Ok(c) => c,
         Err(e) => {
             let err_msg = format!("Failed to create GitHub client: {}", e);
@@ -75,7 +165,7 @@ pub async fn fetch_issues(
         let username = github_username.to_string();
         let url = repo_url.clone();
         let state_param = state.map(|s| s.to_string());
-        let max_pages = max_pages.clone();
+        let since_param = since.map(|s| s.to_string());
         let task = task::spawn(async move {
             let result = fetch_repo_issues(
                 &client,
@@ -84,6 +174,7 @@ pub async fn fetch_issues(
                 &token,
                 state_param.as_deref(),
                 max_pages,
+                since_param.as_deref(),
             )
             .await;
             (url, result)
@@ -108,39 +199,6 @@ pub async fn fetch_issues(
     Ok(results)
 }
 
-/// Creates a GitHub API client with proper authentication
-fn create_github_client(token: &str) -> Result<reqwest::Client, reqwest::Error> {
-    let mut headers = HeaderMap::new();
-    // Standard GitHub API headers
-    headers.insert(
-        ACCEPT,
-        HeaderValue::from_static("application/vnd.github.v3+json"),
-    );
-    headers.insert(
-        AUTHORIZATION,
-        HeaderValue::from_str(&format!("token {}", token)).unwrap(),
-    );
-    headers.insert(
-        USER_AGENT,
-        HeaderValue::from_static("gradelib-github-client/0.1.0"),
-    );
-
-    reqwest::Client::builder().default_headers(headers).build()
-}
-
-/// Parses owner and repo name from GitHub URL
-fn parse_repo_parts(repo_url: &str) -> Result<(String, String), String> {
-    let slug = parse_slug_from_url(repo_url)
-        .ok_or_else(|| format!("Invalid repository URL format: {}", repo_url))?;
-
-    let parts: Vec<&str> = slug.split('/').collect();
-    if parts.len() != 2 {
-        return Err(format!("Invalid repository slug format: {}", slug));
-    }
-
-    Ok((parts[0].to_string(), parts[1].to_string()))
-}
-
 /// Fetches issues for a single repository
 async fn fetch_repo_issues(
     client: &reqwest::Client,
@@ -149,95 +207,272 @@ async fn fetch_repo_issues(
     _github_token: &str,    // Prefixed with underscore to indicate intentional non-use
     state: Option<&str>,    // "open", "closed", "all"
     max_pages: Option<usize>,
+    since: Option<&str>,
 ) -> Result<Vec<IssueInfo>, String> {
     let (owner, repo) = parse_repo_parts(repo_url)?;
     let mut issues = Vec::new();
     let mut page = 1;
+    let mut issues_url = format!("https://api.github.com/repos/{}/{}/issues", owner, repo);
+    let mut query_params = Vec::new();
+    if let Some(state_val) = state {
+        query_params.push(format!("state={}", state_val));
+    } else {
+        query_params.push("state=all".to_string());
+    }
+    query_params.push("direction=desc".to_string());
+    query_params.push("sort=updated".to_string());
+    if let Some(since_val) = since {
+        // '+' in a timezone offset must be escaped in the query string
+        query_params.push(format!("since={}", since_val.replace('+', "%2B")));
+    }
+    query_params.push("per_page=100".to_string());
+    if !query_params.is_empty() {
+        issues_url = format!("{}?{}", issues_url, query_params.join("&"));
+    }
     loop {
-        let mut issues_url = format!("https://api.github.com/repos/{}/{}/issues", owner, repo);
-        let mut query_params = Vec::new();
-        if let Some(state_val) = state {
-            query_params.push(format!("state={}", state_val));
-        } else {
-            query_params.push("state=all".to_string());
-        }
-        query_params.push("direction=desc".to_string());
-        query_params.push("sort=updated".to_string());
-        query_params.push("per_page=100".to_string());
-        query_params.push(format!("page={}", page));
-        if !query_params.is_empty() {
-            issues_url = format!("{}?{}", issues_url, query_params.join("&"));
-        }
-        #[derive(Deserialize)]
-        struct IssueResponse {
-            id: i64,
-            number: i32,
-            title: String,
-            state: String,
-            created_at: String,
-            updated_at: String,
-            closed_at: Option<String>,
-            user: User,
-            body: Option<String>,
-            comments: i32,
-            pull_request: Option<PullRequest>,
-            labels: Vec<Label>,
-            assignees: Vec<User>,
-            milestone: Option<Milestone>,
-            locked: bool,
-            html_url: String,
-        }
-        #[derive(Deserialize)]
-        struct PullRequest {
-            #[allow(dead_code)]
-            url: String,
-        }
         let issues_response = client
             .get(&issues_url)
             .send()
             .await
-            .map_err(|e| format!("Failed to fetch issues: {}", e))?;
+            .map_err(|e| request_error("Failed to fetch issues", e))?;
         if !issues_response.status().is_success() {
-            return Err(format!("GitHub API error: {}", issues_response.status()));
+            return Err(api_error("GitHub API error", issues_response).await);
         }
+        let next_url = next_page_url(issues_response.headers());
         let issue_responses: Vec<IssueResponse> = issues_response
             .json()
             .await
             .map_err(|e| format!("Failed to parse issues response: {}", e))?;
-        if issue_responses.is_empty() {
-            break;
-        }
         for issue in issue_responses {
-            let label_names = issue.labels.iter().map(|l| l.name.clone()).collect();
-            let assignee_logins = issue.assignees.iter().map(|a| a.login.clone()).collect();
-            let milestone_title = issue.milestone.map(|m| m.title);
-            let issue_info = IssueInfo {
-                id: issue.id,
-                number: issue.number,
-                title: issue.title,
-                state: issue.state,
-                created_at: issue.created_at,
-                updated_at: issue.updated_at,
-                closed_at: issue.closed_at,
-                user_login: issue.user.login,
-                user_id: issue.user.id,
-                body: issue.body,
-                comments_count: issue.comments,
-                is_pull_request: issue.pull_request.is_some(),
-                labels: label_names,
-                assignees: assignee_logins,
-                milestone: milestone_title,
-                locked: issue.locked,
-                html_url: issue.html_url,
-            };
-            issues.push(issue_info);
+            issues.push(issue.into_info());
         }
-        page += 1;
-        if let Some(max) = max_pages {
-            if page > max {
-                break;
-            }
+        // The Link header names the next page; the last page has none
+        match next_url {
+            Some(url) if max_pages.is_none_or(|max| page < max) => issues_url = url,
+            _ => break,
         }
+        page += 1;
     }
     Ok(issues)
 }
+
+/// Fetches a single issue by number with one request
+///
+/// Returns `Ok(None)` when GitHub answers 404, i.e. there is no such issue.
+pub async fn fetch_issue(
+    repo_url: &str,
+    github_token: &str,
+    api_config: &ApiClientConfig,
+    number: i32,
+) -> Result<Option<IssueInfo>, String> {
+    let client = create_github_client(github_token, api_config)
+        .map_err(|e| format!("Failed to create GitHub client: {}", e))?;
+    let (owner, repo) = parse_repo_parts(repo_url)?;
+
+    let issue_url = format!(
+        "https://api.github.com/repos/{}/{}/issues/{}",
+        owner, repo, number
+    );
+    let response = client
+        .get(&issue_url)
+        .send()
+        .await
+        .map_err(|e| request_error("Failed to fetch issue", e))?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !response.status().is_success() {
+        return Err(api_error("GitHub API error", response).await);
+    }
+    let issue: IssueResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse issue response: {}", e))?;
+    Ok(Some(issue.into_info()))
+}
+
+/// One entry of an issue or pull request timeline, e.g. who labeled or closed it and when
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventInfo {
+    // Raw GitHub event type ("labeled", "closed", "assigned", ...); unknown types are kept as-is
+    pub event: String,
+    // None when the actor account has been deleted
+    pub actor_login: Option<String>,
+    pub created_at: String,
+    // Set for labeled/unlabeled events
+    pub label: Option<String>,
+    // Set for assigned/unassigned events
+    pub assignee: Option<String>,
+}
+
+/// Issue event as returned by the REST API
+#[derive(Deserialize)]
+struct EventResponse {
+    event: String,
+    actor: Option<User>,
+    created_at: String,
+    label: Option<Label>,
+    assignee: Option<User>,
+}
+
+impl EventResponse {
+    fn into_info(self) -> EventInfo {
+        EventInfo {
+            event: self.event,
+            actor_login: self.actor.map(|a| a.login),
+            created_at: self.created_at,
+            label: self.label.map(|l| l.name),
+            assignee: self.assignee.map(|a| a.login),
+        }
+    }
+}
+
+/// Fetches every event of one issue or pull request with `GET /issues/{number}/events`,
+/// following pagination
+pub async fn fetch_issue_events(
+    repo_url: &str,
+    github_token: &str,
+    api_config: &ApiClientConfig,
+    number: i32,
+) -> Result<Vec<EventInfo>, String> {
+    let client = create_github_client(github_token, api_config)
+        .map_err(|e| format!("Failed to create GitHub client: {}", e))?;
+    let (owner, repo) = parse_repo_parts(repo_url)?;
+
+    let per_page = 100;
+    let mut events = Vec::new();
+    let mut page = 1;
+    loop {
+        let events_url = format!(
+            "https://api.github.com/repos/{}/{}/issues/{}/events?per_page={}&page={}",
+            owner, repo, number, per_page, page
+        );
+        let response = client
+            .get(&events_url)
+            .send()
+            .await
+            .map_err(|e| request_error("Failed to fetch issue events", e))?;
+        if !response.status().is_success() {
+            return Err(api_error("GitHub API error", response).await);
+        }
+        let event_responses: Vec<EventResponse> = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse issue events response: {}", e))?;
+        let count = event_responses.len();
+        events.extend(event_responses.into_iter().map(EventResponse::into_info));
+        // A short page is the last one
+        if count < per_page {
+            break;
+        }
+        page += 1;
+    }
+    Ok(events)
+}
+
+/// The search API never returns more than this many results for one query
+pub const SEARCH_RESULTS_LIMIT: usize = 1000;
+
+/// Issues and pull requests matching a search query
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueSearchResults {
+    pub items: Vec<IssueInfo>,
+    pub total_count: u64,
+    // Set when not every match was returned (the 1000-result cap, max_pages or a timed-out search)
+    pub truncated: bool,
+}
+
+/// Searches issues and pull requests with `GET /search/issues`, following pagination
+///
+/// `query` uses GitHub's search syntax, e.g. `is:pr is:open TODO repo:owner/a repo:owner/b`.
+/// Search has its own rate limit (30 requests per minute); when it is exhausted between
+/// pages, this waits for the window to reset.
+pub async fn search_issues(
+    query: &str,
+    github_token: &str,
+    api_config: &ApiClientConfig,
+    max_pages: Option<usize>,
+) -> Result<IssueSearchResults, String> {
+    let client = create_github_client(github_token, api_config)
+        .map_err(|e| format!("Failed to create GitHub client: {}", e))?;
+
+    #[derive(Deserialize)]
+    struct SearchResponse {
+        total_count: u64,
+        incomplete_results: bool,
+        items: Vec<IssueResponse>,
+    }
+
+    let per_page = 100;
+    let mut items = Vec::new();
+    let mut total_count;
+    let mut incomplete = false;
+    let mut page = 1;
+
+    loop {
+        let response = client
+            .get("https://api.github.com/search/issues")
+            .query(&[
+                ("q", query),
+                ("per_page", &per_page.to_string()),
+                ("page", &page.to_string()),
+            ])
+            .send()
+            .await
+            .map_err(|e| request_error("Failed to search issues", e))?;
+        if !response.status().is_success() {
+            return Err(api_error("GitHub API error", response).await);
+        }
+        let rate_limit_wait = search_rate_limit_wait(response.headers());
+
+        let search: SearchResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse search response: {}", e))?;
+        total_count = search.total_count;
+        incomplete |= search.incomplete_results;
+        let count = search.items.len();
+        items.extend(search.items.into_iter().map(IssueResponse::into_info));
+
+        // A short page is the last one
+        if count < per_page || items.len() >= SEARCH_RESULTS_LIMIT {
+            break;
+        }
+        if max_pages.is_some_and(|max| page >= max) {
+            break;
+        }
+        if let Some(wait) = rate_limit_wait {
+            tokio::time::sleep(wait).await;
+        }
+        page += 1;
+    }
+
+    let truncated = incomplete || (items.len() as u64) < total_count;
+    Ok(IssueSearchResults {
+        items,
+        total_count,
+        truncated,
+    })
+}
+
+/// How long to wait before the next search request, if the search rate limit is used up
+fn search_rate_limit_wait(headers: &reqwest::header::HeaderMap) -> Option<std::time::Duration> {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<u64>().ok())
+    };
+    if header("x-ratelimit-remaining")? > 0 {
+        return None;
+    }
+    let reset = header("x-ratelimit-reset")?;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()?
+        .as_secs();
+    // The search window is one minute, so never wait longer than that
+    Some(std::time::Duration::from_secs(
+        reset.saturating_sub(now).clamp(1, 60),
+    ))
+}
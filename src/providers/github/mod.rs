@@ -1,4 +1,6 @@
 // GitHub provider modules
+pub(crate) mod access;
+pub(crate) mod api_client;
 pub(crate) mod blame;
 pub(crate) mod branch;
 pub(crate) mod clone;
@@ -6,7 +8,12 @@ pub(crate) mod code_review;
 pub(crate) mod collaborators;
 pub(crate) mod comments;
 pub(crate) mod commits;
+pub(crate) mod files;
 pub(crate) mod issues;
 pub mod oauth;
+pub(crate) mod org_repos;
 pub(crate) mod pull_requests;
+pub(crate) mod rate_limit;
+pub(crate) mod reflog;
 pub(crate) mod repo;
+pub(crate) mod summary;
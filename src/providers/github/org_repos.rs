@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+
+use crate::api_client::{api_error, create_github_client, request_error, ApiClientConfig};
+
+/// A repository owned by an organization or user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OwnerRepoInfo {
+    pub name: String,
+    pub full_name: String, // "owner/name"
+    pub clone_url: String, // HTTPS URL, accepted by `clone` and the fetch_* methods
+    pub private: bool,
+}
+
+/// Lists the repositories of an organization, or of a user when no such organization exists,
+/// following pagination
+///
+/// With `name_prefix`, only repositories whose name starts with it (ignoring case) are kept,
+/// e.g. `assignment1-` to find every student repository of one assignment.
+pub async fn list_owner_repos(
+    owner: &str,
+    name_prefix: Option<&str>,
+    github_token: &str,
+    api_config: &ApiClientConfig,
+) -> Result<Vec<OwnerRepoInfo>, String> {
+    let client = create_github_client(github_token, api_config)
+        .map_err(|e| format!("Failed to create GitHub client: {}", e))?;
+
+    let org_url = format!("https://api.github.com/orgs/{}/repos?type=all", owner);
+    let repos = match fetch_repo_pages(&client, &org_url).await? {
+        Some(repos) => repos,
+        None => {
+            // Not an organization; GitHub answers 404 for user accounts here
+            let user_url = format!("https://api.github.com/users/{}/repos?type=owner", owner);
+            fetch_repo_pages(&client, &user_url)
+                .await?
+                .ok_or_else(|| format!("No organization or user named '{}'", owner))?
+        }
+    };
+
+    let prefix = name_prefix.map(str::to_lowercase);
+    Ok(repos
+        .into_iter()
+        .filter(|repo| {
+            prefix
+                .as_deref()
+                .is_none_or(|p| repo.name.to_lowercase().starts_with(p))
+        })
+        .collect())
+}
+
+/// Fetches every page of a repository listing; `Ok(None)` when the first page is a 404
+async fn fetch_repo_pages(
+    client: &reqwest::Client,
+    base_url: &str,
+) -> Result<Option<Vec<OwnerRepoInfo>>, String> {
+    let per_page = 100;
+    let mut repos = Vec::new();
+    let mut page = 1;
+    loop {
+        let url = format!("{}&per_page={}&page={}", base_url, per_page, page);
+        let response = client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| request_error("Failed to list repositories", e))?;
+        let status = response.status();
+        if status == reqwest::StatusCode::NOT_FOUND && page == 1 {
+            return Ok(None);
+        }
+        if !status.is_success() {
+            return Err(match rate_limit_reset(response.headers()) {
+                Some(reset) => format!(
+                    "GitHub API rate limit exceeded (resets at {} epoch seconds)",
+                    reset
+                ),
+                None => api_error("GitHub API error", response).await,
+            });
+        }
+        let page_repos: Vec<OwnerRepoInfo> = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse repositories response: {}", e))?;
+        let count = page_repos.len();
+        repos.extend(page_repos);
+        // A short page is the last one
+        if count < per_page {
+            break;
+        }
+        page += 1;
+    }
+    Ok(Some(repos))
+}
+
+/// The reset time of an exhausted rate limit, if that is why a request was refused
+fn rate_limit_reset(headers: &reqwest::header::HeaderMap) -> Option<u64> {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<u64>().ok())
+    };
+    if header("x-ratelimit-remaining")? > 0 {
+        return None;
+    }
+    header("x-ratelimit-reset")
+}
@@ -1,8 +1,16 @@
-use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, USER_AGENT};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
 use tokio::task;
 
+use crate::api_client::{
+    api_error, create_github_client, next_page_url, request_error, ApiClientConfig,
+};
+use crate::code_review::{fetch_pr_reviews, ReviewInfo};
+use crate::issues::default_author_association;
+use crate::repo::parse_repo_parts;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PullRequestInfo {
     pub id: i64,
@@ -26,6 +34,38 @@ pub struct PullRequestInfo {
     pub draft: bool,
     pub merged: bool,
     pub merged_by: Option<String>,
+    pub base_ref: String, // Branch the pull request merges into
+    pub base_sha: String,
+    pub head_ref: String, // Branch the changes come from
+    pub head_sha: String,
+    pub head_repo_full_name: Option<String>, // None once the head repository is deleted
+    pub is_cross_fork: bool,                 // Head branch lives in another repository
+    pub author_association: String,          // As for issues, e.g. "MEMBER" or "CONTRIBUTOR"
+}
+
+/// Default cap on pull request detail requests in flight during one `fetch_pull_requests` call
+pub const DEFAULT_MAX_CONCURRENT_DETAILS: usize = 8;
+
+/// Options controlling how `fetch_pull_requests` queries the API.
+#[derive(Clone, Debug)]
+pub struct PullRequestFetchOptions {
+    /// One GraphQL query per page instead of a REST call per pull request.
+    pub use_graphql: bool,
+    /// REST only: one extra request per pull request for the detail-only fields.
+    pub fetch_details: bool,
+    /// Detail requests in flight at once, shared by all repositories of the call, so a
+    /// large detail fan-out can't crowd out everything else. Must be at least 1.
+    pub max_concurrent_details: usize,
+}
+
+impl Default for PullRequestFetchOptions {
+    fn default() -> Self {
+        Self {
+            use_graphql: false,
+            fetch_details: true,
+            max_concurrent_details: DEFAULT_MAX_CONCURRENT_DETAILS,
+        }
+    }
 }
 
 /// Fetches pull request information for multiple repositories concurrently
@@ -36,11 +76,13 @@ pub async fn fetch_pull_requests(
     repo_urls: Vec<String>,
     _github_username: &str, // Prefix with underscore to indicate intentional non-use
     github_token: &str,
+    api_config: &ApiClientConfig,
     state: Option<&str>, // "open", "closed", "all"
     max_pages: Option<usize>,
+    options: &PullRequestFetchOptions,
 ) -> Result<HashMap<String, Result<Vec<PullRequestInfo>, String>>, String> {
     // Create a GitHub client
-    let client = match create_github_client(github_token) {
+    let client = match create_github_client(github_token, api_config) {
         Ok(c) => c,
         Err(e) => {
             let err_msg = format!("Failed to create GitHub client: {}", e);
@@ -54,17 +96,32 @@ pub async fn fetch_pull_requests(
 
     // Fetch pull requests for all repositories concurrently
     let mut tasks = Vec::new();
+    let use_graphql = options.use_graphql;
+    let fetch_details = options.fetch_details;
+    let detail_slots = Arc::new(Semaphore::new(options.max_concurrent_details.max(1)));
 
     for repo_url in repo_urls {
         let client = client.clone();
         let token = github_token.to_string();
         let url = repo_url.clone();
         let state_param = state.map(|s| s.to_string());
-        let max_pages = max_pages.clone();
+        let detail_slots = Arc::clone(&detail_slots);
         let task = task::spawn(async move {
-            let result =
-                fetch_repo_pull_requests(&client, &url, &token, state_param.as_deref(), max_pages)
-                    .await;
+            let result = if use_graphql {
+                fetch_repo_pull_requests_graphql(&client, &url, state_param.as_deref(), max_pages)
+                    .await
+            } else {
+                fetch_repo_pull_requests(
+                    &client,
+                    &url,
+                    &token,
+                    state_param.as_deref(),
+                    max_pages,
+                    fetch_details,
+                    &detail_slots,
+                )
+                .await
+            };
             (url, result)
         });
         tasks.push(task);
@@ -87,160 +144,442 @@ pub async fn fetch_pull_requests(
     Ok(results)
 }
 
-/// Creates a GitHub API client with proper authentication
-fn create_github_client(token: &str) -> Result<reqwest::Client, reqwest::Error> {
-    let mut headers = HeaderMap::new();
-    // Standard GitHub API headers
-    headers.insert(
-        ACCEPT,
-        HeaderValue::from_static("application/vnd.github.v3+json"),
-    );
-    headers.insert(
-        AUTHORIZATION,
-        HeaderValue::from_str(&format!("token {}", token)).unwrap(),
-    );
-    headers.insert(
-        USER_AGENT,
-        HeaderValue::from_static("gradelib-github-client/0.1.0"),
-    );
+/// Pull request as returned by the list endpoint
+#[derive(Deserialize)]
+struct PullRequestBasic {
+    id: i64,
+    number: i32,
+    title: String,
+    state: String,
+    created_at: String,
+    updated_at: String,
+    closed_at: Option<String>,
+    merged_at: Option<String>,
+    user: User,
+    body: Option<String>,
+    draft: bool,
+    labels: Vec<Label>,
+    base: BranchRef,
+    head: BranchRef,
+    #[serde(default = "default_author_association")]
+    author_association: String,
+}
+
+/// A pull request's `base` or `head` branch
+#[derive(Deserialize)]
+struct BranchRef {
+    #[serde(rename = "ref")]
+    ref_name: String,
+    sha: String,
+    repo: Option<RepoName>, // None for the head of a pull request whose fork was deleted
+}
+
+#[derive(Deserialize)]
+struct RepoName {
+    full_name: String,
+}
 
-    reqwest::Client::builder().default_headers(headers).build()
+/// Whether a pull request comes from another repository than it targets. Only forks get
+/// deleted from under an open pull request, so a missing head repository counts as one.
+fn is_cross_fork(base_repo: Option<&str>, head_repo: Option<&str>) -> bool {
+    head_repo.is_none_or(|head| base_repo.is_none_or(|base| !head.eq_ignore_ascii_case(base)))
 }
 
-/// Parses owner and repo name from GitHub URL
-fn parse_repo_parts(repo_url: &str) -> Result<(String, String), String> {
-    use crate::repo::parse_slug_from_url;
+#[derive(Deserialize)]
+struct User {
+    login: String,
+    id: i64,
+}
 
-    let slug = parse_slug_from_url(repo_url)
-        .ok_or_else(|| format!("Invalid repository URL format: {}", repo_url))?;
+#[derive(Deserialize)]
+struct Label {
+    name: String,
+}
 
-    let parts: Vec<&str> = slug.split('/').collect();
-    if parts.len() != 2 {
-        return Err(format!("Invalid repository slug format: {}", slug));
+impl PullRequestBasic {
+    /// Converts list-level data into a PullRequestInfo with the detail-only fields left empty
+    fn into_info(self) -> PullRequestInfo {
+        let labels = self.labels.into_iter().map(|l| l.name).collect();
+        let is_merged = self.merged_at.is_some();
+        let base_repo = self.base.repo.map(|r| r.full_name);
+        let head_repo_full_name = self.head.repo.map(|r| r.full_name);
+        let is_cross_fork = is_cross_fork(base_repo.as_deref(), head_repo_full_name.as_deref());
+        PullRequestInfo {
+            id: self.id,
+            number: self.number,
+            title: self.title,
+            state: self.state,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            closed_at: self.closed_at,
+            merged_at: self.merged_at,
+            user_login: self.user.login,
+            user_id: self.user.id,
+            body: self.body,
+            comments: 0,
+            commits: 0,
+            additions: 0,
+            deletions: 0,
+            changed_files: 0,
+            mergeable: None,
+            labels,
+            draft: self.draft,
+            merged: is_merged,
+            merged_by: None,
+            base_ref: self.base.ref_name,
+            base_sha: self.base.sha,
+            head_ref: self.head.ref_name,
+            head_sha: self.head.sha,
+            head_repo_full_name,
+            is_cross_fork,
+            author_association: self.author_association,
+        }
     }
+}
+
+/// Detail-only fields from the single pull request endpoint
+#[derive(Deserialize)]
+struct PullRequestDetail {
+    mergeable: Option<bool>,
+    merged: bool,
+    merged_by: Option<User>,
+    comments: i32,
+    commits: i32,
+    additions: i32,
+    deletions: i32,
+    changed_files: i32,
+}
 
-    Ok((parts[0].to_string(), parts[1].to_string()))
+impl PullRequestDetail {
+    /// Fills the detail-only fields of a PullRequestInfo built from list-level data
+    fn apply(self, pr_info: &mut PullRequestInfo) {
+        pr_info.comments = self.comments;
+        pr_info.commits = self.commits;
+        pr_info.additions = self.additions;
+        pr_info.deletions = self.deletions;
+        pr_info.changed_files = self.changed_files;
+        pr_info.mergeable = self.mergeable;
+        pr_info.merged = self.merged;
+        pr_info.merged_by = self.merged_by.map(|user| user.login);
+    }
 }
 
 /// Fetches pull requests for a single repository
+///
+/// Without `fetch_details`, only the list endpoint is used, so comments, commits, additions,
+/// deletions and changed_files are 0, mergeable and merged_by are None, and merged is
+/// derived from merged_at. Detail requests of a page run concurrently, each holding one of
+/// `detail_slots` while in flight.
 async fn fetch_repo_pull_requests(
     client: &reqwest::Client,
     repo_url: &str,
     _token: &str,        // Prefixed with underscore to indicate intentional non-use
     state: Option<&str>, // "open", "closed", "all"
     max_pages: Option<usize>,
+    fetch_details: bool,
+    detail_slots: &Arc<Semaphore>,
 ) -> Result<Vec<PullRequestInfo>, String> {
     let (owner, repo) = parse_repo_parts(repo_url)?;
     let mut detailed_prs = Vec::new();
     let mut page = 1;
+    let mut pr_url = format!("https://api.github.com/repos/{}/{}/pulls", owner, repo);
+    let mut query_params = Vec::new();
+    if let Some(state_val) = state {
+        query_params.push(format!("state={}", state_val));
+    } else {
+        query_params.push("state=all".to_string());
+    }
+    query_params.push("per_page=100".to_string());
+    if !query_params.is_empty() {
+        pr_url = format!("{}?{}", pr_url, query_params.join("&"));
+    }
     loop {
-        let mut pr_url = format!("https://api.github.com/repos/{}/{}/pulls", owner, repo);
-        let mut query_params = Vec::new();
-        if let Some(state_val) = state {
-            query_params.push(format!("state={}", state_val));
-        } else {
-            query_params.push("state=all".to_string());
-        }
-        query_params.push(format!("per_page=100"));
-        query_params.push(format!("page={}", page));
-        if !query_params.is_empty() {
-            pr_url = format!("{}?{}", pr_url, query_params.join("&"));
-        }
-        #[derive(Deserialize)]
-        struct PullRequestBasic {
-            id: i64,
-            number: i32,
-            title: String,
-            state: String,
-            created_at: String,
-            updated_at: String,
-            closed_at: Option<String>,
-            merged_at: Option<String>,
-            user: User,
-            body: Option<String>,
-            draft: bool,
-            labels: Vec<Label>,
-        }
-        #[derive(Deserialize)]
-        struct User {
-            login: String,
-            id: i64,
-        }
-        #[derive(Deserialize)]
-        struct Label {
-            name: String,
-        }
         let prs_response = client
             .get(&pr_url)
             .send()
             .await
-            .map_err(|e| format!("Failed to fetch pull requests: {}", e))?;
+            .map_err(|e| request_error("Failed to fetch pull requests", e))?;
         if !prs_response.status().is_success() {
-            return Err(format!("GitHub API error: {}", prs_response.status()));
+            return Err(api_error("GitHub API error", prs_response).await);
         }
+        let next_url = next_page_url(prs_response.headers());
         let basic_prs: Vec<PullRequestBasic> = prs_response
             .json()
             .await
             .map_err(|e| format!("Failed to parse pull requests response: {}", e))?;
-        if basic_prs.is_empty() {
-            break;
+        if !fetch_details {
+            detailed_prs.extend(basic_prs.into_iter().map(PullRequestBasic::into_info));
+        } else {
+            let mut detail_tasks = Vec::new();
+            for basic_pr in basic_prs {
+                let mut pr_info = basic_pr.into_info();
+                let client = client.clone();
+                let (owner, repo) = (owner.clone(), repo.clone());
+                let detail_slots = Arc::clone(detail_slots);
+                detail_tasks.push(task::spawn(async move {
+                    let _permit = detail_slots
+                        .acquire_owned()
+                        .await
+                        .expect("detail semaphore is never closed");
+                    if let Err(e) = fetch_pr_details(&client, &owner, &repo, &mut pr_info).await {
+                        eprintln!(
+                            "Warning: Failed to fetch details for PR #{}: {}",
+                            pr_info.number, e
+                        );
+                    }
+                    pr_info
+                }));
+            }
+            // Awaited in order so pull requests keep the list order
+            for detail_task in detail_tasks {
+                let pr_info = detail_task
+                    .await
+                    .map_err(|e| format!("Pull request detail task failed: {}", e))?;
+                detailed_prs.push(pr_info);
+            }
+        }
+        // The Link header names the next page; the last page has none
+        match next_url {
+            Some(url) if max_pages.is_none_or(|max| page < max) => pr_url = url,
+            _ => break,
         }
-        for basic_pr in basic_prs {
-            let label_names: Vec<String> = basic_pr.labels.iter().map(|l| l.name.clone()).collect();
-            match fetch_pr_details(
-                client,
-                &owner,
-                &repo,
-                basic_pr.number,
-                basic_pr.id,
-                &basic_pr.title,
-                &basic_pr.state,
-                &basic_pr.created_at,
-                &basic_pr.updated_at,
-                &basic_pr.closed_at,
-                &basic_pr.merged_at,
-                &basic_pr.user.login,
-                basic_pr.user.id,
-                &basic_pr.body,
-                basic_pr.draft,
-                &label_names,
-            )
+        page += 1;
+    }
+    Ok(detailed_prs)
+}
+
+const GRAPHQL_URL: &str = "https://api.github.com/graphql";
+
+/// Pull request fields matching what the REST list and detail endpoints provide together
+const PULL_REQUESTS_QUERY: &str = r#"
+query($owner: String!, $name: String!, $states: [PullRequestState!], $cursor: String) {
+  repository(owner: $owner, name: $name) {
+    pullRequests(first: 100, after: $cursor, states: $states, orderBy: {field: CREATED_AT, direction: DESC}) {
+      pageInfo { hasNextPage endCursor }
+      nodes {
+        databaseId number title state createdAt updatedAt closedAt mergedAt body
+        isDraft merged mergeable additions deletions changedFiles
+        baseRefName baseRefOid headRefName headRefOid isCrossRepository authorAssociation
+        headRepository { nameWithOwner }
+        author { login ... on User { databaseId } ... on Bot { databaseId } }
+        mergedBy { login }
+        comments { totalCount }
+        commits { totalCount }
+        labels(first: 100) { nodes { name } }
+      }
+    }
+  }
+}
+"#;
+
+#[derive(Deserialize)]
+struct GraphQlResponse {
+    data: Option<GraphQlData>,
+    errors: Option<Vec<GraphQlError>>,
+}
+
+#[derive(Deserialize)]
+struct GraphQlError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct GraphQlData {
+    repository: Option<GraphQlRepository>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlRepository {
+    pull_requests: GraphQlConnection<GraphQlPullRequest>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlConnection<T> {
+    page_info: GraphQlPageInfo,
+    nodes: Vec<T>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlPageInfo {
+    has_next_page: bool,
+    end_cursor: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GraphQlCount {
+    #[serde(rename = "totalCount")]
+    total_count: i32,
+}
+
+#[derive(Deserialize)]
+struct GraphQlRepositoryName {
+    #[serde(rename = "nameWithOwner")]
+    name_with_owner: String,
+}
+
+#[derive(Deserialize)]
+struct GraphQlActor {
+    login: String,
+    #[serde(rename = "databaseId")]
+    database_id: Option<i64>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlPullRequest {
+    database_id: Option<i64>,
+    number: i32,
+    title: String,
+    state: String, // OPEN, CLOSED or MERGED
+    created_at: String,
+    updated_at: String,
+    closed_at: Option<String>,
+    merged_at: Option<String>,
+    body: Option<String>,
+    is_draft: bool,
+    merged: bool,
+    mergeable: String, // MERGEABLE, CONFLICTING or UNKNOWN
+    additions: i32,
+    deletions: i32,
+    changed_files: i32,
+    author: Option<GraphQlActor>, // None for deleted accounts
+    merged_by: Option<GraphQlActor>,
+    comments: GraphQlCount,
+    commits: GraphQlCount,
+    labels: Option<GraphQlLabels>,
+    base_ref_name: String,
+    base_ref_oid: String,
+    head_ref_name: String,
+    head_ref_oid: String,
+    head_repository: Option<GraphQlRepositoryName>,
+    is_cross_repository: bool,
+    author_association: String, // Same values as REST
+}
+
+#[derive(Deserialize)]
+struct GraphQlLabels {
+    nodes: Vec<Label>,
+}
+
+impl GraphQlPullRequest {
+    /// Converts to the REST-shaped PullRequestInfo
+    fn into_info(self) -> PullRequestInfo {
+        let (user_login, user_id) = match self.author {
+            Some(author) => (author.login, author.database_id.unwrap_or(0)),
+            None => ("ghost".to_string(), 0),
+        };
+        PullRequestInfo {
+            id: self.database_id.unwrap_or(0),
+            number: self.number,
+            title: self.title,
+            // REST reports merged pull requests as closed
+            state: match self.state.as_str() {
+                "OPEN" => "open".to_string(),
+                _ => "closed".to_string(),
+            },
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            closed_at: self.closed_at,
+            merged_at: self.merged_at,
+            user_login,
+            user_id,
+            body: self.body,
+            comments: self.comments.total_count,
+            commits: self.commits.total_count,
+            additions: self.additions,
+            deletions: self.deletions,
+            changed_files: self.changed_files,
+            mergeable: match self.mergeable.as_str() {
+                "MERGEABLE" => Some(true),
+                "CONFLICTING" => Some(false),
+                _ => None,
+            },
+            labels: self
+                .labels
+                .map(|l| l.nodes.into_iter().map(|l| l.name).collect())
+                .unwrap_or_default(),
+            draft: self.is_draft,
+            merged: self.merged,
+            merged_by: self.merged_by.map(|user| user.login),
+            base_ref: self.base_ref_name,
+            base_sha: self.base_ref_oid,
+            head_ref: self.head_ref_name,
+            head_sha: self.head_ref_oid,
+            // Same rule as REST: a deleted head repository was a fork
+            is_cross_fork: self.is_cross_repository || self.head_repository.is_none(),
+            head_repo_full_name: self.head_repository.map(|r| r.name_with_owner),
+            author_association: self.author_association,
+        }
+    }
+}
+
+/// Fetches pull requests for a single repository through the GraphQL API,
+/// 100 pull requests with all their details per request
+async fn fetch_repo_pull_requests_graphql(
+    client: &reqwest::Client,
+    repo_url: &str,
+    state: Option<&str>, // "open", "closed", "all"
+    max_pages: Option<usize>,
+) -> Result<Vec<PullRequestInfo>, String> {
+    let (owner, repo) = parse_repo_parts(repo_url)?;
+    let states = match state {
+        Some("open") => serde_json::json!(["OPEN"]),
+        Some("closed") => serde_json::json!(["CLOSED", "MERGED"]),
+        _ => serde_json::Value::Null,
+    };
+
+    let mut all_prs = Vec::new();
+    let mut cursor: Option<String> = None;
+    let mut page = 1;
+    loop {
+        let request_body = serde_json::json!({
+            "query": PULL_REQUESTS_QUERY,
+            "variables": {
+                "owner": owner,
+                "name": repo,
+                "states": states,
+                "cursor": cursor,
+            },
+        });
+        let response = client
+            .post(GRAPHQL_URL)
+            .json(&request_body)
+            .send()
             .await
-            {
-                Ok(pr_info) => detailed_prs.push(pr_info),
-                Err(e) => {
-                    eprintln!(
-                        "Warning: Failed to fetch details for PR #{}: {}",
-                        basic_pr.number, e
-                    );
-                    let labels = basic_pr.labels.iter().map(|l| l.name.clone()).collect();
-                    let is_merged = basic_pr.merged_at.is_some();
-                    detailed_prs.push(PullRequestInfo {
-                        id: basic_pr.id,
-                        number: basic_pr.number,
-                        title: basic_pr.title,
-                        state: basic_pr.state,
-                        created_at: basic_pr.created_at,
-                        updated_at: basic_pr.updated_at,
-                        closed_at: basic_pr.closed_at,
-                        merged_at: basic_pr.merged_at,
-                        user_login: basic_pr.user.login,
-                        user_id: basic_pr.user.id,
-                        body: basic_pr.body,
-                        comments: 0,
-                        commits: 0,
-                        additions: 0,
-                        deletions: 0,
-                        changed_files: 0,
-                        mergeable: None,
-                        labels,
-                        draft: basic_pr.draft,
-                        merged: is_merged,
-                        merged_by: None,
-                    });
-                }
-            }
+            .map_err(|e| request_error("Failed to fetch pull requests", e))?;
+        if !response.status().is_success() {
+            return Err(api_error("GitHub API error", response).await);
+        }
+        let body: GraphQlResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse pull requests response: {}", e))?;
+
+        // GraphQL reports failures in the body alongside a 200 status
+        if let Some(errors) = body.errors.filter(|errors| !errors.is_empty()) {
+            let messages: Vec<String> = errors.into_iter().map(|e| e.message).collect();
+            return Err(format!("GitHub GraphQL error: {}", messages.join("; ")));
+        }
+        let connection = body
+            .data
+            .and_then(|data| data.repository)
+            .ok_or_else(|| format!("Repository {}/{} not found", owner, repo))?
+            .pull_requests;
+
+        all_prs.extend(
+            connection
+                .nodes
+                .into_iter()
+                .map(GraphQlPullRequest::into_info),
+        );
+
+        if !connection.page_info.has_next_page {
+            break;
         }
+        cursor = connection.page_info.end_cursor;
         page += 1;
         if let Some(max) = max_pages {
             if page > max {
@@ -248,60 +587,31 @@ async fn fetch_repo_pull_requests(
             }
         }
     }
-    Ok(detailed_prs)
+    Ok(all_prs)
 }
 
-/// Fetches detailed information for a single pull request
+/// Fetches detailed information for a single pull request and fills in the detail-only fields
 async fn fetch_pr_details(
     client: &reqwest::Client,
     owner: &str,
     repo: &str,
-    pr_number: i32,
-    pr_id: i64,
-    title: &str,
-    state: &str,
-    created_at: &str,
-    updated_at: &str,
-    closed_at: &Option<String>,
-    merged_at: &Option<String>,
-    user_login: &str,
-    user_id: i64,
-    body: &Option<String>,
-    draft: bool,
-    labels: &Vec<String>, // Update parameter type to Vec<String>
-) -> Result<PullRequestInfo, String> {
+    pr_info: &mut PullRequestInfo,
+) -> Result<(), String> {
     // API URL for detailed PR information
     let pr_detail_url = format!(
         "https://api.github.com/repos/{}/{}/pulls/{}",
-        owner, repo, pr_number
+        owner, repo, pr_info.number
     );
 
-    #[derive(Deserialize)]
-    struct PullRequestDetail {
-        mergeable: Option<bool>,
-        merged: bool,
-        merged_by: Option<User>,
-        comments: i32,
-        commits: i32,
-        additions: i32,
-        deletions: i32,
-        changed_files: i32,
-    }
-
-    #[derive(Deserialize)]
-    struct User {
-        login: String,
-    }
-
     // Fetch PR details
     let pr_response = client
         .get(&pr_detail_url)
         .send()
         .await
-        .map_err(|e| format!("Failed to fetch PR details: {}", e))?;
+        .map_err(|e| request_error("Failed to fetch PR details", e))?;
 
     if !pr_response.status().is_success() {
-        return Err(format!("GitHub API error: {}", pr_response.status()));
+        return Err(api_error("GitHub API error", pr_response).await);
     }
 
     let pr_detail: PullRequestDetail = pr_response
@@ -309,31 +619,360 @@ async fn fetch_pr_details(
         .await
         .map_err(|e| format!("Failed to parse PR detail response: {}", e))?;
 
-    // No need to convert labels since they're already strings
-    let label_names = labels.clone();
-
-    // Build the final PR info object
-    Ok(PullRequestInfo {
-        id: pr_id,
-        number: pr_number,
-        title: title.to_string(),
-        state: state.to_string(),
-        created_at: created_at.to_string(),
-        updated_at: updated_at.to_string(),
-        closed_at: closed_at.clone(),
-        merged_at: merged_at.clone(),
-        user_login: user_login.to_string(),
-        user_id,
-        body: body.clone(),
-        comments: pr_detail.comments,
-        commits: pr_detail.commits,
-        additions: pr_detail.additions,
-        deletions: pr_detail.deletions,
-        changed_files: pr_detail.changed_files,
-        mergeable: pr_detail.mergeable,
-        labels: label_names,
-        draft,
-        merged: pr_detail.merged,
-        merged_by: pr_detail.merged_by.map(|user| user.login),
+    pr_detail.apply(pr_info);
+
+    Ok(())
+}
+
+/// Fetches a single pull request by number with one request
+///
+/// The detail endpoint carries both the list-level and the detail-only fields.
+/// Returns `Ok(None)` when GitHub answers 404, i.e. there is no such pull request.
+pub async fn fetch_pull_request(
+    repo_url: &str,
+    github_token: &str,
+    api_config: &ApiClientConfig,
+    number: i32,
+) -> Result<Option<PullRequestInfo>, String> {
+    let client = create_github_client(github_token, api_config)
+        .map_err(|e| format!("Failed to create GitHub client: {}", e))?;
+    let (owner, repo) = parse_repo_parts(repo_url)?;
+
+    #[derive(Deserialize)]
+    struct PullRequestFull {
+        #[serde(flatten)]
+        basic: PullRequestBasic,
+        #[serde(flatten)]
+        detail: PullRequestDetail,
+    }
+
+    let pr_url = format!(
+        "https://api.github.com/repos/{}/{}/pulls/{}",
+        owner, repo, number
+    );
+    let response = client
+        .get(&pr_url)
+        .send()
+        .await
+        .map_err(|e| request_error("Failed to fetch pull request", e))?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !response.status().is_success() {
+        return Err(api_error("GitHub API error", response).await);
+    }
+    let pr: PullRequestFull = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse pull request response: {}", e))?;
+    let mut pr_info = pr.basic.into_info();
+    pr.detail.apply(&mut pr_info);
+    Ok(Some(pr_info))
+}
+
+/// Reviews and outstanding review requests for a single pull request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PullRequestReviews {
+    pub reviews: Vec<ReviewInfo>,
+    pub requested_reviewers: Vec<String>,
+    pub requested_teams: Vec<String>,
+}
+
+/// Fetches the submitted reviews and pending review requests for one pull request
+///
+/// A pull request without reviews yields empty lists rather than an error.
+pub async fn fetch_pull_request_reviews(
+    repo_url: &str,
+    github_token: &str,
+    api_config: &ApiClientConfig,
+    pr_number: i32,
+) -> Result<PullRequestReviews, String> {
+    let client = create_github_client(github_token, api_config)
+        .map_err(|e| format!("Failed to create GitHub client: {}", e))?;
+    let (owner, repo) = parse_repo_parts(repo_url)?;
+
+    let reviews = fetch_pr_reviews(&client, &owner, &repo, pr_number, "").await?;
+
+    #[derive(Deserialize)]
+    struct RequestedReviewersResponse {
+        users: Vec<User>,
+        teams: Vec<Team>,
+    }
+
+    #[derive(Deserialize)]
+    struct Team {
+        slug: String,
+    }
+
+    let requested_url = format!(
+        "https://api.github.com/repos/{}/{}/pulls/{}/requested_reviewers",
+        owner, repo, pr_number
+    );
+    let response = client
+        .get(&requested_url)
+        .send()
+        .await
+        .map_err(|e| request_error("Failed to fetch requested reviewers", e))?;
+    if !response.status().is_success() {
+        return Err(api_error("GitHub API error", response).await);
+    }
+    let requested: RequestedReviewersResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse requested reviewers response: {}", e))?;
+
+    Ok(PullRequestReviews {
+        reviews,
+        requested_reviewers: requested.users.into_iter().map(|u| u.login).collect(),
+        requested_teams: requested.teams.into_iter().map(|t| t.slug).collect(),
+    })
+}
+
+/// A file changed by a pull request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrFileInfo {
+    pub filename: String,
+    pub status: String,
+    pub additions: i32,
+    pub deletions: i32,
+    pub changes: i32,
+    // GitHub omits the patch for binary files and very large diffs
+    pub patch: Option<String>,
+}
+
+/// Fetches every file changed by a single pull request, following pagination
+pub async fn fetch_pr_files(
+    repo_url: &str,
+    github_token: &str,
+    api_config: &ApiClientConfig,
+    pr_number: i32,
+) -> Result<Vec<PrFileInfo>, String> {
+    let client = create_github_client(github_token, api_config)
+        .map_err(|e| format!("Failed to create GitHub client: {}", e))?;
+    let (owner, repo) = parse_repo_parts(repo_url)?;
+
+    let per_page = 100;
+    let mut all_files = Vec::new();
+    let mut page = 1;
+
+    loop {
+        let api_url = format!(
+            "https://api.github.com/repos/{}/{}/pulls/{}/files?per_page={}&page={}",
+            owner, repo, pr_number, per_page, page
+        );
+
+        let response = client
+            .get(&api_url)
+            .send()
+            .await
+            .map_err(|e| request_error("Failed to fetch pull request files", e))?;
+        if !response.status().is_success() {
+            return Err(api_error("GitHub API error", response).await);
+        }
+
+        let files: Vec<PrFileInfo> = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse pull request files response: {}", e))?;
+
+        let count = files.len();
+        all_files.extend(files);
+
+        // A short page is the last one
+        if count < per_page {
+            break;
+        }
+        page += 1;
+    }
+
+    Ok(all_files)
+}
+
+/// An inline review comment, anchored to a line of a pull request's diff
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewCommentInfo {
+    pub id: i64,
+    pub user_login: Option<String>, // None when the author's account was deleted
+    pub body: String,
+    pub path: String,
+    pub line: Option<i32>, // None when the comment is outdated: its line is gone from the diff
+    pub original_line: Option<i32>, // Line in the diff the comment was made on
+    pub commit_id: String,
+    pub created_at: String,
+    pub in_reply_to_id: Option<i64>, // Comment this one replies to; None starts a thread
+}
+
+/// Review comment as returned by the REST API
+#[derive(Deserialize)]
+struct ReviewCommentResponse {
+    id: i64,
+    user: Option<User>,
+    body: String,
+    path: String,
+    line: Option<i32>,
+    original_line: Option<i32>,
+    commit_id: String,
+    created_at: String,
+    in_reply_to_id: Option<i64>,
+}
+
+/// Fetches the inline review comments on a single pull request, following pagination
+///
+/// These are the comments left on lines of the diff, distinct from the conversation
+/// comments returned by the issue comments endpoint.
+pub async fn fetch_pr_review_comments(
+    repo_url: &str,
+    github_token: &str,
+    api_config: &ApiClientConfig,
+    pr_number: i32,
+) -> Result<Vec<ReviewCommentInfo>, String> {
+    let client = create_github_client(github_token, api_config)
+        .map_err(|e| format!("Failed to create GitHub client: {}", e))?;
+    let (owner, repo) = parse_repo_parts(repo_url)?;
+
+    let per_page = 100;
+    let mut all_comments = Vec::new();
+    let mut page = 1;
+
+    loop {
+        let api_url = format!(
+            "https://api.github.com/repos/{}/{}/pulls/{}/comments?per_page={}&page={}",
+            owner, repo, pr_number, per_page, page
+        );
+
+        let response = client
+            .get(&api_url)
+            .send()
+            .await
+            .map_err(|e| request_error("Failed to fetch review comments", e))?;
+        if !response.status().is_success() {
+            return Err(api_error("GitHub API error", response).await);
+        }
+
+        let comments: Vec<ReviewCommentResponse> = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse review comments response: {}", e))?;
+
+        let count = comments.len();
+        all_comments.extend(comments.into_iter().map(|c| ReviewCommentInfo {
+            id: c.id,
+            user_login: c.user.map(|u| u.login),
+            body: c.body,
+            path: c.path,
+            line: c.line,
+            original_line: c.original_line,
+            commit_id: c.commit_id,
+            created_at: c.created_at,
+            in_reply_to_id: c.in_reply_to_id,
+        }));
+
+        // A short page is the last one
+        if count < per_page {
+            break;
+        }
+        page += 1;
+    }
+
+    Ok(all_comments)
+}
+
+/// The most commits GitHub lists for a single pull request
+pub const PR_COMMITS_LIMIT: usize = 250;
+
+/// A commit on a pull request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrCommitInfo {
+    pub sha: String,
+    pub message: String,
+    pub author_login: Option<String>, // None when the author email isn't linked to an account
+    pub author_email: String,
+    pub authored_at: String,
+}
+
+/// Commits on a single pull request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PullRequestCommits {
+    pub commits: Vec<PrCommitInfo>,
+    // Set when the listing hit PR_COMMITS_LIMIT, so later commits may be missing
+    pub truncated: bool,
+}
+
+/// Fetches the commits on a single pull request, following pagination
+///
+/// GitHub lists at most 250 commits per pull request; `truncated` is set when
+/// that many were returned.
+pub async fn fetch_pr_commits(
+    repo_url: &str,
+    github_token: &str,
+    api_config: &ApiClientConfig,
+    pr_number: i32,
+) -> Result<PullRequestCommits, String> {
+    let client = create_github_client(github_token, api_config)
+        .map_err(|e| format!("Failed to create GitHub client: {}", e))?;
+    let (owner, repo) = parse_repo_parts(repo_url)?;
+
+    #[derive(Deserialize)]
+    struct CommitResponse {
+        sha: String,
+        commit: CommitDetail,
+        author: Option<User>,
+    }
+
+    #[derive(Deserialize)]
+    struct CommitDetail {
+        message: String,
+        author: GitAuthor,
+    }
+
+    #[derive(Deserialize)]
+    struct GitAuthor {
+        email: String,
+        date: String,
+    }
+
+    let per_page = 100;
+    let mut all_commits = Vec::new();
+    let mut page = 1;
+
+    loop {
+        let api_url = format!(
+            "https://api.github.com/repos/{}/{}/pulls/{}/commits?per_page={}&page={}",
+            owner, repo, pr_number, per_page, page
+        );
+
+        let response = client
+            .get(&api_url)
+            .send()
+            .await
+            .map_err(|e| request_error("Failed to fetch pull request commits", e))?;
+        if !response.status().is_success() {
+            return Err(api_error("GitHub API error", response).await);
+        }
+
+        let commits: Vec<CommitResponse> = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse pull request commits response: {}", e))?;
+
+        let count = commits.len();
+        all_commits.extend(commits.into_iter().map(|c| PrCommitInfo {
+            sha: c.sha,
+            message: c.commit.message,
+            author_login: c.author.map(|user| user.login),
+            author_email: c.commit.author.email,
+            authored_at: c.commit.author.date,
+        }));
+
+        // A short page is the last one
+        if count < per_page || all_commits.len() >= PR_COMMITS_LIMIT {
+            break;
+        }
+        page += 1;
+    }
+
+    Ok(PullRequestCommits {
+        truncated: all_commits.len() >= PR_COMMITS_LIMIT,
+        commits: all_commits,
     })
 }
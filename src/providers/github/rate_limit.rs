@@ -0,0 +1,45 @@
+use serde::Deserialize;
+
+use crate::api_client::{api_error, create_github_client, request_error, ApiClientConfig};
+
+/// Rate limit status for one GitHub API resource
+#[derive(Debug, Clone, Deserialize)]
+pub struct RateLimitInfo {
+    pub limit: u32,
+    pub remaining: u32,
+    pub reset: i64, // Seconds since epoch
+    pub resource: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RateLimitResponse {
+    rate: RateLimitInfo,
+}
+
+/// Fetches the core rate limit status for the given token
+///
+/// Querying `/rate_limit` does not count against the limit itself.
+pub async fn fetch_rate_limit(
+    github_token: &str,
+    api_config: &ApiClientConfig,
+) -> Result<RateLimitInfo, String> {
+    let client = create_github_client(github_token, api_config)
+        .map_err(|e| format!("Failed to create GitHub client: {}", e))?;
+
+    let response = client
+        .get("https://api.github.com/rate_limit")
+        .send()
+        .await
+        .map_err(|e| request_error("Failed to fetch rate limit", e))?;
+
+    if !response.status().is_success() {
+        return Err(api_error("GitHub API error", response).await);
+    }
+
+    let rate_limit: RateLimitResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse rate limit response: {}", e))?;
+
+    Ok(rate_limit.rate)
+}
@@ -0,0 +1,94 @@
+use git2::{Oid, Repository};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::Path;
+
+/// A reflog entry where the default branch moved to a commit that doesn't contain the
+/// previous tip, i.e. history was rewritten (typically by a force-push).
+#[derive(Debug, Clone, Serialize)]
+pub struct ForcePushInfo {
+    pub ref_name: String, // Reflog the entry came from, e.g. "refs/remotes/origin/main"
+    pub old_sha: String,
+    pub new_sha: String,
+    pub timestamp: i64, // Seconds since epoch, when the ref was updated locally
+    pub offset: i32,    // Timezone offset in minutes
+    pub message: String,
+}
+
+/// Finds force-updates of the default branch in a clone's reflog.
+///
+/// Both the checked-out branch and its remote-tracking ref are scanned. An entry counts as
+/// forced when git recorded it as a `forced-update`, or when the old tip is not an ancestor of
+/// the new one. Only updates seen by this clone are visible: a force-push that happened and
+/// was overwritten between two fetches can't be detected.
+pub fn detect_force_pushes(repo_path: &Path) -> Result<Vec<ForcePushInfo>, String> {
+    let repo = Repository::open(repo_path)
+        .map_err(|e| format!("Failed to open repository at {:?}: {}", repo_path, e))?;
+    if repo.is_bare() {
+        return Err("Force-push detection needs a non-bare clone".to_string());
+    }
+    if repo.is_shallow() {
+        return Err("Force-push detection needs a full (non-shallow) clone".to_string());
+    }
+
+    let head = repo
+        .head()
+        .map_err(|e| format!("Failed to resolve HEAD: {}", e))?;
+    if !head.is_branch() {
+        return Err("HEAD is detached; the default branch can't be determined".to_string());
+    }
+    let branch_ref = head
+        .name()
+        .ok_or_else(|| "HEAD has a non-UTF-8 name".to_string())?
+        .to_string();
+    let mut ref_names = vec![branch_ref.clone()];
+    if let Ok(upstream) = repo.branch_upstream_name(&branch_ref) {
+        if let Some(upstream) = upstream.as_str() {
+            ref_names.push(upstream.to_string());
+        }
+    }
+
+    let mut found_reflog = false;
+    let mut seen = HashSet::new();
+    let mut force_pushes = Vec::new();
+    for ref_name in ref_names {
+        let reflog = repo
+            .reflog(&ref_name)
+            .map_err(|e| format!("Failed to read reflog of {}: {}", ref_name, e))?;
+        found_reflog |= !reflog.is_empty();
+        for entry in reflog.iter() {
+            let (old, new) = (entry.id_old(), entry.id_new());
+            let message = entry.message().unwrap_or("").to_string();
+            if !is_forced_update(&repo, old, new, &message) || !seen.insert((old, new)) {
+                continue;
+            }
+            let when = entry.committer().when();
+            force_pushes.push(ForcePushInfo {
+                ref_name: ref_name.clone(),
+                old_sha: old.to_string(),
+                new_sha: new.to_string(),
+                timestamp: when.seconds(),
+                offset: when.offset_minutes(),
+                message,
+            });
+        }
+    }
+    if !found_reflog {
+        return Err(format!("No reflog found for {}", branch_ref));
+    }
+
+    force_pushes.sort_by_key(|f| f.timestamp);
+    Ok(force_pushes)
+}
+
+fn is_forced_update(repo: &Repository, old: Oid, new: Oid, message: &str) -> bool {
+    if message.contains("forced-update") {
+        return true;
+    }
+    // Branch creation and no-op updates can't rewrite anything
+    if old.is_zero() || new.is_zero() || old == new {
+        return false;
+    }
+    // An old tip that is gone or not contained in the new one was discarded
+    !repo.graph_descendant_of(new, old).unwrap_or(false)
+}
@@ -1,19 +1,42 @@
 use futures::future::join_all;
-use git2::{Cred, FetchOptions, Progress, RemoteCallbacks};
+use git2::{AutotagOption, Cred, FetchOptions, Progress, ProxyOptions, RemoteCallbacks};
 use lazy_static::lazy_static;
 use regex::Regex;
+use serde::Serialize;
 use std::{
     collections::HashMap,
-    path::PathBuf,
-    sync::{Arc, Mutex},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
 };
 use tempfile::TempDir;
+use tokio::sync::{broadcast, Semaphore};
 use tokio::task::JoinHandle; // For spawn_blocking handle type // Keep regex crate
 
 // --- Import from new modules ---
-use crate::blame::{get_blame_for_file, BlameLineInfo};
-use crate::clone::{InternalCloneStatus, InternalRepoCloneTask};
-use crate::commits::{extract_commits_parallel, CommitInfo}; // Use the new parallel function
+use crate::api_client::ApiClientConfig;
+use crate::blame::{
+    blame_files, resolve_blame_commit, summarize_authorship, BlameAuthorship, BlameCache,
+    BlameCacheKey, BlameLineInfo, BlameOptions,
+};
+use crate::branch::{extract_branches, BranchInfo};
+use crate::clone::{
+    checkout_ref, delete_tags, is_transient_clone_error, reset_to_fetched, CloneOptions,
+    CloneProgressEvent, InternalCloneStatus, InternalRepoCloneTask,
+};
+use crate::collaborators::{fetch_collaborators, CollaboratorInfo};
+use crate::commits::{
+    coauthorship_graph, commit_activity, diff_revisions, extract_commits_bulk,
+    extract_commits_parallel, extract_commits_since, stream_commits_to_file, ActivityBin,
+    ActivityBucket, CoauthorshipGraph, CommitAnalysisOptions, CommitInfo, DiffStats,
+    IncrementalCommits,
+}; // Use the new parallel function
+use crate::issues::{fetch_issues, IssueInfo};
+use crate::pull_requests::{fetch_pull_requests, PullRequestFetchOptions, PullRequestInfo};
+use crate::reflog::{detect_force_pushes, ForcePushInfo};
 
 // --- Internal Data Structures ---
 
@@ -22,23 +45,102 @@ use crate::commits::{extract_commits_parallel, CommitInfo}; // Use the new paral
 pub struct InternalRepoManagerLogic {
     // Stores clone tasks, keyed by repository URL
     pub tasks: Arc<Mutex<HashMap<String, InternalRepoCloneTask>>>,
-    // GitHub credentials used for cloning
+    // GitHub credentials used for cloning and API calls; the token can be rotated
     pub github_username: String,
-    pub github_token: String,
+    github_token: Arc<Mutex<String>>,
+    // Persistent clone location; None clones into temporary directories
+    pub target_dir: Option<PathBuf>,
+    // Directory layout of persistent clones under target_dir
+    pub clone_dir_scheme: CloneDirScheme,
+    // Upper bound on a single clone; None never times out
+    pub clone_timeout: Option<Duration>,
+    // Timeouts and pooling for GitHub API clients
+    pub api_config: ApiClientConfig,
+    // Cancellation flags for in-flight clones, keyed by canonical URL
+    cancel_flags: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
+    // Most recent results per (data kind, canonical repo URL or path), kept for export
+    last_results: Arc<Mutex<HashMap<(String, String), serde_json::Value>>>,
+    // Publishes every clone status transition to progress watchers
+    progress_tx: broadcast::Sender<CloneProgressEvent>,
+    // Blame results keyed by (commit, path, options), reused until HEAD moves
+    pub blame_cache: Arc<Mutex<BlameCache>>,
+    // Caps how many clones run at once; the rest wait in Queued
+    clone_slots: Arc<Semaphore>,
+    // Extra attempts after a transient network failure (see `is_transient_clone_error`)
+    pub clone_retries: u32,
+}
+
+/// How a batch of clones stands, tallied by `clone_summary`.
+#[derive(Debug, Default)]
+pub struct CloneSummary {
+    pub queued: usize,
+    pub cloning: usize,
+    pub completed: usize,
+    pub failed: usize,
+    // (URL as given, error message) of each failed task, sorted by URL
+    pub failures: Vec<(String, String)>,
 }
 
+/// Everything known about one repository, gathered by `fetch_repo_report`.
+/// Each section succeeds or fails on its own.
+pub struct RepoReport {
+    pub commits: Result<Vec<CommitInfo>, String>,
+    pub branches: Result<Vec<BranchInfo>, String>,
+    pub collaborators: Result<Vec<CollaboratorInfo>, String>,
+    pub issues: Result<Vec<IssueInfo>, String>,
+    pub pull_requests: Result<Vec<PullRequestInfo>, String>,
+}
+
+/// Default cap on simultaneous clones
+pub const DEFAULT_MAX_CONCURRENT_CLONES: usize = 8;
+
+/// Default number of retries after a transient clone failure
+pub const DEFAULT_CLONE_RETRIES: u32 = 2;
+
+/// Wait before the first retry; each further retry waits this much longer
+const CLONE_RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Buffered progress events per watcher; slower watchers skip the oldest
+const PROGRESS_CHANNEL_CAPACITY: usize = 1024;
+
+/// Result kinds that can be exported with `export_json`
+pub const EXPORT_KINDS: &[&str] = &[
+    "commits",
+    "blame",
+    "branches",
+    "collaborators",
+    "issues",
+    "pull_requests",
+];
+
 // --- Helper Functions ---
 
 lazy_static! {
-    // Regex for HTTPS: captures 'owner/repo' from https://github.com/owner/repo.git or https://host.com/owner/repo
-    static ref RE_HTTPS: Regex = Regex::new(r"https?://[^/]+/(?P<slug>[^/]+/[^/.]+?)(\.git)?/?$").unwrap();
-    // Regex for SSH: captures 'owner/repo' from git@github.com:owner/repo.git or user@host:owner/repo
-    static ref RE_SSH: Regex = Regex::new(r"^(?:ssh://)?git@.*?:(?P<slug>[^/]+/[^/.]+?)(\.git)?$").unwrap();
+    // Regex for HTTPS: captures 'owner/repo' from https://github.com/owner/repo.git or https://host.com/owner/repo/
+    // Repository names may contain dots (e.g. user.github.io); only a final '.git' is dropped
+    static ref RE_HTTPS: Regex = Regex::new(r"^https?://[^/]+/(?P<slug>[^/]+/[^/?#]+?)(\.git)?/*$").unwrap();
+    // Regex for SSH: captures 'owner/repo' from git@github.com:owner/repo.git or ssh://git@github.com[:port]/owner/repo
+    static ref RE_SSH: Regex = Regex::new(r"^(?:git@[^/:]+:|ssh://git@[^/:]+(?::\d+)?/)(?P<slug>[^/]+/[^/?#]+?)(\.git)?/*$").unwrap();
+    // Regex for the host part of HTTPS (https://host/...) and SSH (git@host:... or ssh://git@host/...) URLs
+    static ref RE_HOST: Regex = Regex::new(r"^(?:https?://(?:[^@/]+@)?|(?:ssh://)?git@)(?P<host>[^/:]+)").unwrap();
+    // Regex for GitLab URLs, whose paths may nest groups: captures 'group/subgroup/repo'
+    static ref RE_GITLAB: Regex = Regex::new(r"^(?:https?://(?:[^@/]+@)?[^/]*gitlab[^/]*/|(?:ssh://)?git@[^:/]*gitlab[^:/]*[:/])(?P<slug>[^/]+(?:/[^/]+)*?/[^/?#]+?)(\.git)?/*$").unwrap();
+}
+
+/// Picks one repository's entry out of a multi-repository fetch result.
+fn single_repo_result<T>(
+    results: Result<HashMap<String, Result<Vec<T>, String>>, String>,
+    repo_url: &str,
+) -> Result<Vec<T>, String> {
+    results?
+        .remove(repo_url)
+        .unwrap_or_else(|| Err(format!("No result for {}", repo_url)))
 }
 
 /// Parses a repository slug (e.g., "owner/repo") from common Git URLs.
 /// Moved outside the impl block.
 pub fn parse_slug_from_url(url: &str) -> Option<String> {
+    let url = url.trim();
     if let Some(caps) = RE_HTTPS.captures(url) {
         caps.name("slug").map(|m| m.as_str().to_string())
     } else if let Some(caps) = RE_SSH.captures(url) {
@@ -48,62 +150,308 @@ pub fn parse_slug_from_url(url: &str) -> Option<String> {
     }
 }
 
+/// Parses a GitLab project path (e.g., "group/subgroup/repo") from HTTPS or SSH URLs.
+/// Unlike GitHub slugs, GitLab paths can contain nested groups.
+pub fn parse_gitlab_slug(url: &str) -> Option<String> {
+    RE_GITLAB
+        .captures(url.trim())
+        .and_then(|caps| caps.name("slug"))
+        .map(|m| m.as_str().to_string())
+}
+
+/// Parses the git host (e.g., "github.com") from HTTPS or SSH URLs.
+pub fn parse_host_from_url(url: &str) -> Option<String> {
+    RE_HOST
+        .captures(url.trim())
+        .and_then(|caps| caps.name("host"))
+        .map(|m| m.as_str().to_lowercase())
+}
+
+/// Parses the owner and repository name from a GitHub repository URL.
+///
+/// This is the one parser used by every GitHub API call. HTTPS and SSH URLs are
+/// accepted, with or without a `.git` suffix or trailing slash; URLs for other
+/// hosts, or without both an owner and a repository name, are rejected.
+pub fn parse_repo_parts(repo_url: &str) -> Result<(String, String), String> {
+    let trimmed = repo_url.trim();
+    let host = parse_host_from_url(trimmed)
+        .ok_or_else(|| format!("Invalid repository URL format: {}", repo_url))?;
+    if host != "github.com" && host != "www.github.com" {
+        return Err(format!(
+            "Unsupported host {} in {}: only github.com repositories are supported",
+            host, repo_url
+        ));
+    }
+    let slug = parse_slug_from_url(trimmed)
+        .ok_or_else(|| format!("Invalid repository URL format: {}", repo_url))?;
+
+    match slug.split_once('/') {
+        Some((owner, repo)) if !owner.is_empty() && !repo.is_empty() => {
+            Ok((owner.to_string(), repo.to_string()))
+        }
+        _ => Err(format!("Invalid repository slug format: {}", slug)),
+    }
+}
+
+/// Returns the canonical form of a repository URL, used as the key for clone tasks.
+/// SSH and HTTPS URLs pointing at the same repository (e.g. `git@github.com:owner/repo.git`
+/// and `https://github.com/owner/repo`) both map to `https://github.com/owner/repo`.
+/// URLs that are not recognized (such as local paths) are returned unchanged.
+pub fn canonicalize_url(url: &str) -> String {
+    let trimmed = url.trim();
+    let slug = parse_gitlab_slug(trimmed).or_else(|| parse_slug_from_url(trimmed));
+    match (parse_host_from_url(trimmed), slug) {
+        (Some(host), Some(slug)) => format!("https://{}/{}", host, slug),
+        _ => trimmed.to_string(),
+    }
+}
+
 // --- Core Logic Implementation for InternalRepoManagerLogic ---
 
+/// How persistent clones are laid out under `target_dir`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum CloneDirScheme {
+    // target_dir/owner__repo
+    #[default]
+    OwnerRepo,
+    // target_dir/owner/repo
+    FullSlug,
+    // A template such as "{owner}-{repo}" or "students/{owner}/{repo}"; '/' nests directories
+    Template(String),
+}
+
+impl CloneDirScheme {
+    /// Parses the `clone_dir_scheme` accepted by `RepoManager`: "owner__repo", "full-slug"
+    /// or a template containing `{repo}` and optionally `{owner}`.
+    pub fn parse(scheme: &str) -> Result<Self, String> {
+        match scheme {
+            "owner__repo" => Ok(CloneDirScheme::OwnerRepo),
+            "full-slug" => Ok(CloneDirScheme::FullSlug),
+            template if template.contains("{repo}") => {
+                // The template's own text must not climb out of target_dir
+                let sample = template
+                    .replace("{owner}", "owner")
+                    .replace("{repo}", "repo");
+                let escapes = sample.starts_with('/')
+                    || sample.split('/').any(|part| {
+                        part.is_empty()
+                            || part == "."
+                            || part == ".."
+                            || part.contains(['\\', ':', '\0'])
+                    });
+                if escapes {
+                    return Err(format!(
+                        "Invalid clone_dir_scheme template '{}': it must be a relative path \
+                         without empty, '.' or '..' components",
+                        template
+                    ));
+                }
+                Ok(CloneDirScheme::Template(template.to_string()))
+            }
+            _ => Err(format!(
+                "Invalid clone_dir_scheme '{}': expected 'owner__repo', 'full-slug' or a \
+                 template containing {{repo}}",
+                scheme
+            )),
+        }
+    }
+
+    /// The clone's path relative to `target_dir`, e.g. `owner__repo`.
+    /// Owner and repository names are sanitized so a crafted URL can't escape `target_dir`.
+    fn relative_path(&self, url: &str) -> PathBuf {
+        let (owners, repo) = match parse_gitlab_slug(url).or_else(|| parse_slug_from_url(url)) {
+            Some(slug) => {
+                let mut parts: Vec<String> = slug.split('/').map(safe_path_component).collect();
+                let repo = parts.pop().unwrap_or_default();
+                (parts, repo)
+            }
+            None => {
+                // Local paths and other URLs fall back to their last path component
+                let name = url
+                    .trim_end_matches('/')
+                    .rsplit(['/', '\\', ':'])
+                    .next()
+                    .unwrap_or(url);
+                (
+                    Vec::new(),
+                    safe_path_component(name.trim_end_matches(".git")),
+                )
+            }
+        };
+        match self {
+            CloneDirScheme::OwnerRepo => {
+                let mut parts = owners;
+                parts.push(repo);
+                PathBuf::from(parts.join("__"))
+            }
+            CloneDirScheme::FullSlug => owners.iter().chain(std::iter::once(&repo)).collect(),
+            CloneDirScheme::Template(template) => {
+                let owner = if owners.is_empty() {
+                    "_".to_string()
+                } else {
+                    owners.join("__")
+                };
+                template
+                    .replace("{owner}", &owner)
+                    .replace("{repo}", &repo)
+                    .split('/')
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Makes a URL segment safe to use as a single directory name
+fn safe_path_component(segment: &str) -> String {
+    let cleaned: String = segment
+        .chars()
+        .map(|c| {
+            if matches!(c, '/' | '\\' | ':') || c.is_control() {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+    // "", "." and ".." would refer to the parent directories
+    if cleaned.chars().all(|c| c == '.') {
+        format!("_{}", cleaned)
+    } else {
+        cleaned
+    }
+}
+
 impl InternalRepoManagerLogic {
     /// Creates a new instance of the internal manager logic.
-    pub fn new(urls: &[&str], github_username: &str, github_token: &str) -> Self {
+    #[allow(clippy::too_many_arguments)] // Mirrors RepoManager's constructor arguments
+    pub fn new(
+        urls: &[&str],
+        github_username: &str,
+        github_token: &str,
+        target_dir: Option<PathBuf>,
+        clone_dir_scheme: CloneDirScheme,
+        clone_timeout: Option<Duration>,
+        api_config: ApiClientConfig,
+        max_concurrent_clones: usize,
+        clone_retries: u32,
+    ) -> Self {
         // Initialize lazy_static regexes here if not already done
         lazy_static::initialize(&RE_HTTPS);
         lazy_static::initialize(&RE_SSH);
+        lazy_static::initialize(&RE_HOST);
+        lazy_static::initialize(&RE_GITLAB);
 
-        let tasks = urls
-            .iter()
-            .map(|&url| {
-                (
-                    url.to_string(),
-                    InternalRepoCloneTask {
-                        url: url.to_string(),
-                        status: InternalCloneStatus::Queued,
-                        temp_dir: None,
-                    },
-                )
-            })
-            .collect();
+        // Key tasks by canonical URL so equivalent SSH/HTTPS URLs are only cloned once;
+        // the first URL given for a repository is the one used for cloning.
+        let mut tasks = HashMap::new();
+        for &url in urls {
+            tasks
+                .entry(canonicalize_url(url))
+                .or_insert_with(|| InternalRepoCloneTask {
+                    url: url.to_string(),
+                    status: InternalCloneStatus::Queued,
+                    temp_dir: None,
+                    attempts: 0,
+                });
+        }
 
         Self {
             tasks: Arc::new(Mutex::new(tasks)),
             github_username: github_username.to_string(),
-            github_token: github_token.to_string(),
+            github_token: Arc::new(Mutex::new(github_token.to_string())),
+            target_dir,
+            clone_dir_scheme,
+            clone_timeout,
+            api_config,
+            cancel_flags: Arc::new(Mutex::new(HashMap::new())),
+            last_results: Arc::new(Mutex::new(HashMap::new())),
+            progress_tx: broadcast::channel(PROGRESS_CHANNEL_CAPACITY).0,
+            blame_cache: Arc::new(Mutex::new(BlameCache::default())),
+            clone_slots: Arc::new(Semaphore::new(max_concurrent_clones)),
+            clone_retries,
         }
     }
 
+    /// The current GitHub token. Clones and API calls copy it when they start, so a
+    /// rotation never changes the token of a request already in flight.
+    pub fn github_token(&self) -> String {
+        self.github_token.lock().unwrap().clone()
+    }
+
+    /// Replaces the GitHub token used by clones and API calls started from now on.
+    pub fn set_github_token(&self, github_token: &str) {
+        *self.github_token.lock().unwrap() = github_token.to_string();
+    }
+
     /// Initiates cloning for all repositories managed by this instance.
     pub async fn clone_all(&self) -> HashMap<String, Result<PathBuf, String>> {
         let task_urls = {
             let tasks_guard = self.tasks.lock().unwrap();
-            tasks_guard.keys().cloned().collect::<Vec<_>>()
+            tasks_guard
+                .values()
+                .map(|task| task.url.clone())
+                .collect::<Vec<_>>()
         };
-        let results = join_all(task_urls.iter().cloned().map(|url| self.clone(url))).await;
+        let results = join_all(
+            task_urls
+                .into_iter()
+                .map(|url| self.clone(url, CloneOptions::default())),
+        )
+        .await;
         let mut map = HashMap::new();
-        for ((result, _url), original_url) in results.into_iter().zip(task_urls.into_iter()) {
-            map.insert(original_url, result);
+        for (result, url) in results {
+            map.insert(canonicalize_url(&url), result);
         }
         map
     }
 
     /// Clones a single repository specified by URL.
-    pub async fn clone(&self, url: String) -> (Result<PathBuf, String>, String) {
-        self.update_status(&url, InternalCloneStatus::Cloning(0))
+    /// `options` selects the branch to check out and the depth/tag settings of the fetch.
+    pub async fn clone(
+        &self,
+        url: String,
+        options: CloneOptions,
+    ) -> (Result<PathBuf, String>, String) {
+        let key = canonicalize_url(&url);
+        let cancelled = Arc::new(AtomicBool::new(false));
+        self.cancel_flags
+            .lock()
+            .unwrap()
+            .insert(key.clone(), Arc::clone(&cancelled));
+        // Wait in Queued for a clone slot. The slot frees once this task has published the
+        // final status and the blocking clone's thread has ended, even after a timeout
+        self.set_status(&key, InternalCloneStatus::Queued);
+        let permit = Arc::new(
+            Arc::clone(&self.clone_slots)
+                .acquire_owned()
+                .await
+                .expect("clone semaphore is never closed"),
+        );
+        let permit_task = Arc::clone(&permit);
+        self.update_status(&key, InternalCloneStatus::Cloning(0))
             .await;
+        let cancelled_task = Arc::clone(&cancelled);
         let manager_logic = Clone::clone(self);
         let username = self.github_username.clone();
-        let token = self.github_token.clone();
+        let token = self.github_token();
         let url_clone = url.clone();
-        let result: Result<Result<PathBuf, String>, tokio::task::JoinError> =
-            tokio::task::spawn_blocking(move || {
-                let temp_dir = TempDir::new().map_err(|e| e.to_string())?;
-                let temp_path = temp_dir.path().to_path_buf();
+        let key_cb = key.clone();
+        let target_dir = self.target_dir.clone();
+        let proxy_url = self.api_config.proxy_url.clone();
+        let clone_retries = self.clone_retries;
+        let clone_dir_scheme = self.clone_dir_scheme.clone();
+        let handle: JoinHandle<Result<PathBuf, String>> = tokio::task::spawn_blocking(move || {
+            let _permit = permit_task;
+            // Persistent clones live at target_dir/<owner>__<repo>; otherwise use a temp dir
+            let (clone_path, temp_dir) = match &target_dir {
+                Some(dir) => (dir.join(clone_dir_scheme.relative_path(&url)), None),
+                None => {
+                    let temp_dir = TempDir::new().map_err(|e| e.to_string())?;
+                    (temp_dir.path().to_path_buf(), Some(temp_dir))
+                }
+            };
+            // Built afresh for every attempt, since a clone consumes its fetch options
+            let build_fetch_options = || {
                 let mut callbacks = RemoteCallbacks::new();
                 let username_cb = username.clone();
                 let token_cb = token.clone();
@@ -119,6 +467,9 @@ impl InternalRepoManagerLogic {
                         // Use "git" as fallback username for GitHub URLs
                         if url.contains("github.com") {
                             "git"
+                        } else if url.contains("gitlab") {
+                            // GitLab accepts personal/OAuth tokens with the "oauth2" username
+                            "oauth2"
                         } else {
                             // For non-GitHub URLs, try with the URL-provided username if available
                             username_from_url.unwrap_or("")
@@ -130,68 +481,318 @@ impl InternalRepoManagerLogic {
 
                     Cred::userpass_plaintext(effective_username, &token_cb)
                 });
-                let tasks = Arc::clone(&manager_logic.tasks);
-                let url_str = url.clone();
+                let cancelled_cb = Arc::clone(&cancelled_task);
+                let manager_cb = Clone::clone(&manager_logic);
+                let key_progress = key_cb.clone();
                 callbacks.transfer_progress(move |stats: Progress| {
+                    // Returning false aborts the transfer. A cancelled or timed-out clone has
+                    // already been failed, so it must not publish progress over that
+                    if cancelled_cb.load(Ordering::Relaxed) {
+                        return false;
+                    }
                     let percent = ((stats.received_objects() as f32
                         / stats.total_objects().max(1) as f32)
                         * 100.0) as u8;
-                    if let Ok(mut tasks_guard) = tasks.lock() {
-                        if let Some(task) = tasks_guard.get_mut(&url_str) {
-                            task.status = InternalCloneStatus::Cloning(percent);
-                        }
-                    }
+                    manager_cb.set_status(&key_progress, InternalCloneStatus::Cloning(percent));
                     true
                 });
                 let mut fetch_options = FetchOptions::new();
                 fetch_options.remote_callbacks(callbacks);
+                if let Some(proxy_url) = &proxy_url {
+                    let mut proxy_options = ProxyOptions::new();
+                    proxy_options.url(proxy_url);
+                    fetch_options.proxy_options(proxy_options);
+                }
+                if let Some(depth) = options.depth {
+                    fetch_options.depth(depth);
+                }
+                if options.no_tags {
+                    fetch_options.download_tags(AutotagOption::None);
+                }
+                fetch_options
+            };
+            let branch = options.branch;
+            let checkout = options.checkout;
+
+            if cancelled_task.load(Ordering::Relaxed) {
+                return Err("cancelled".to_string());
+            }
+
+            // Reuse an existing persistent clone: fetch with the same depth and tag settings,
+            // then move the branch and working tree to what was fetched
+            if temp_dir.is_none() && clone_path.join(".git").is_dir() {
+                if let Ok(repo) = git2::Repository::open(&clone_path) {
+                    manager_logic.set_attempts(&key_cb, 1);
+                    let mut remote = repo.find_remote("origin").map_err(|e| e.to_string())?;
+                    // Different repositories can map to the same directory (see
+                    // CloneDirScheme), so never update a clone of something else. The
+                    // configured URL is compared, before any `insteadOf` rewriting.
+                    let origin_url = repo
+                        .config()
+                        .and_then(|config| config.get_string("remote.origin.url"))
+                        .map_err(|e| e.to_string())?;
+                    if canonicalize_url(&origin_url) != canonicalize_url(&url) {
+                        return Err(format!(
+                            "{} already holds a clone of {}, not {}",
+                            clone_path.display(),
+                            origin_url,
+                            url
+                        ));
+                    }
+                    remote
+                        .fetch(&[] as &[&str], Some(&mut build_fetch_options()), None)
+                        .map_err(|e| e.to_string())?;
+                    if options.no_tags {
+                        delete_tags(&repo)?;
+                    }
+                    reset_to_fetched(&repo, branch.as_deref())?;
+                    if let Some(rev) = &checkout {
+                        checkout_ref(&repo, rev)?;
+                    }
+                    return Ok(clone_path);
+                }
+            }
+
+            // Nested clone directory schemes need their parent directories too
+            if let (Some(_), Some(parent)) = (&target_dir, clone_path.parent()) {
+                std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            }
+            let mut attempt = 0;
+            let cloned = loop {
+                attempt += 1;
+                manager_logic.set_attempts(&key_cb, attempt);
                 let mut builder = git2::build::RepoBuilder::new();
-                builder.fetch_options(fetch_options);
-                match builder.clone(&url, &temp_path) {
-                    Ok(_repo) => Ok(temp_dir.into_path()),
-                    Err(e) => Err(e.to_string()),
+                builder.fetch_options(build_fetch_options());
+                if let Some(branch) = &branch {
+                    builder.branch(branch);
                 }
-            })
-            .await;
+                match builder.clone(&url, &clone_path) {
+                    Err(e)
+                        if attempt <= clone_retries
+                            && is_transient_clone_error(&e)
+                            && !cancelled_task.load(Ordering::Relaxed) =>
+                    {
+                        eprintln!(
+                            "Clone attempt {} of {} failed, retrying: {}",
+                            attempt, url, e
+                        );
+                        std::thread::sleep(CLONE_RETRY_BACKOFF * attempt);
+                    }
+                    result => break result,
+                }
+            };
+            match cloned {
+                Ok(repo) => {
+                    if options.no_tags {
+                        delete_tags(&repo)?;
+                    }
+                    if let Some(rev) = &checkout {
+                        checkout_ref(&repo, rev)?;
+                    }
+                    Ok(temp_dir.map_or(clone_path, |t| t.keep()))
+                }
+                Err(e) => match &branch {
+                    Some(branch) if e.code() == git2::ErrorCode::NotFound => {
+                        Err(format!("Branch '{}' not found: {}", branch, e))
+                    }
+                    _ => Err(e.to_string()),
+                },
+            }
+        });
+        let mut timed_out = false;
+        let result = match self.clone_timeout {
+            None => handle.await,
+            Some(limit) => match tokio::time::timeout(limit, handle).await {
+                Ok(result) => result,
+                Err(_) => {
+                    // The blocking clone can't be killed; the cancel flag makes its next
+                    // transfer callback abort it, and the task is failed right away
+                    cancelled.store(true, Ordering::Relaxed);
+                    timed_out = true;
+                    Ok(Err(format!("timed out after {}s", limit.as_secs_f64())))
+                }
+            },
+        };
+        {
+            let mut flags_guard = self.cancel_flags.lock().unwrap();
+            if flags_guard
+                .get(&key)
+                .is_some_and(|flag| Arc::ptr_eq(flag, &cancelled))
+            {
+                flags_guard.remove(&key);
+            }
+        }
+        // An aborted transfer surfaces as a git error; report it as a cancellation
+        let result = match result {
+            Ok(Err(_)) if cancelled.load(Ordering::Relaxed) && !timed_out => {
+                Ok(Err("cancelled".to_string()))
+            }
+            other => other,
+        };
         let ret = match result {
             Ok(Ok(path)) => {
-                self.update_status(&url_clone, InternalCloneStatus::Cloning(100))
+                self.update_status(&key, InternalCloneStatus::Cloning(100))
                     .await;
-                self.finalize_success(&url_clone, path.clone()).await;
+                self.finalize_success(&key, path.clone()).await;
                 Ok(path)
             }
             Ok(Err(err_string)) => {
-                self.update_status(&url_clone, InternalCloneStatus::Failed(err_string.clone()))
+                self.update_status(&key, InternalCloneStatus::Failed(err_string.clone()))
                     .await;
                 Err(err_string)
             }
             Err(join_err) => {
                 self.update_status(
-                    &url_clone,
+                    &key,
                     InternalCloneStatus::Failed(format!("Cloning task failed: {}", join_err)),
                 )
                 .await;
                 Err(format!("Cloning task failed: {}", join_err))
             }
         };
+        drop(permit);
         (ret, url_clone)
     }
 
+    /// Requests cancellation of an in-flight clone.
+    /// Returns false (and does nothing) if no clone is running for the URL.
+    pub fn cancel_clone(&self, url: &str) -> bool {
+        match self
+            .cancel_flags
+            .lock()
+            .unwrap()
+            .get(&canonicalize_url(url))
+        {
+            Some(flag) => {
+                flag.store(true, Ordering::Relaxed);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Requests cancellation of all in-flight clones, returning how many were signalled.
+    pub fn cancel_all(&self) -> usize {
+        let flags_guard = self.cancel_flags.lock().unwrap();
+        for flag in flags_guard.values() {
+            flag.store(true, Ordering::Relaxed);
+        }
+        flags_guard.len()
+    }
+
+    /// Remembers the latest results of one kind for a repository so they can be exported.
+    pub fn record_result<T: Serialize + ?Sized>(&self, kind: &str, repo: &str, data: &T) {
+        match serde_json::to_value(data) {
+            Ok(value) => {
+                self.last_results
+                    .lock()
+                    .unwrap()
+                    .insert((kind.to_string(), canonicalize_url(repo)), value);
+            }
+            Err(e) => eprintln!("Warning: Failed to record {} for {}: {}", kind, repo, e),
+        }
+    }
+
+    /// Records the successful entries of a per-repository results map.
+    pub fn record_results<T: Serialize>(
+        &self,
+        kind: &str,
+        results: &HashMap<String, Result<Vec<T>, String>>,
+    ) {
+        for (repo, result) in results {
+            if let Ok(data) = result {
+                self.record_result(kind, repo, data);
+            }
+        }
+    }
+
+    /// Writes the latest recorded results of one kind for a repository to a JSON file.
+    pub fn export_json(&self, path: &Path, kind: &str, repo: &str) -> Result<(), String> {
+        if !EXPORT_KINDS.contains(&kind) {
+            return Err(format!(
+                "Unknown data kind '{}'. Valid kinds are: {}",
+                kind,
+                EXPORT_KINDS.join(", ")
+            ));
+        }
+        let results_guard = self.last_results.lock().unwrap();
+        let value = results_guard
+            .get(&(kind.to_string(), canonicalize_url(repo)))
+            .ok_or_else(|| {
+                format!(
+                    "No {} results for {}; fetch or analyze them before exporting",
+                    kind, repo
+                )
+            })?;
+        let json = serde_json::to_string_pretty(value)
+            .map_err(|e| format!("Failed to serialize {}: {}", kind, e))?;
+        std::fs::write(path, json).map_err(|e| format!("Failed to write {:?}: {}", path, e))
+    }
+
     /// Updates the status of a specific clone task. Internal helper.
     async fn update_status(&self, url: &str, status: InternalCloneStatus) {
-        let mut tasks_guard = self.tasks.lock().unwrap();
-        if let Some(task) = tasks_guard.get_mut(url) {
-            task.status = status;
-        }
+        self.set_status(url, status);
     }
 
     /// Marks a task as completed and stores its temporary directory path. Internal helper.
     async fn finalize_success(&self, url: &str, path: PathBuf) {
-        let mut tasks_guard = self.tasks.lock().unwrap();
-        if let Some(task) = tasks_guard.get_mut(url) {
-            task.status = InternalCloneStatus::Completed;
+        if let Some(task) = self.tasks.lock().unwrap().get_mut(url) {
             task.temp_dir = Some(path);
         }
+        self.set_status(url, InternalCloneStatus::Completed);
+    }
+
+    /// Records how many clone attempts a task has made. Internal helper.
+    fn set_attempts(&self, url: &str, attempts: u32) {
+        if let Some(task) = self.tasks.lock().unwrap().get_mut(url) {
+            task.attempts = attempts;
+        }
+    }
+
+    /// Sets a task's status and, if it changed, publishes the transition to watchers.
+    /// A completed or failed task only leaves that state when a new clone queues it again.
+    fn set_status(&self, url: &str, status: InternalCloneStatus) {
+        let mut tasks_guard = self.tasks.lock().unwrap();
+        let Some(task) = tasks_guard.get_mut(url) else {
+            return;
+        };
+        // Transfer callbacks fire far more often than the percentage moves
+        match (&task.status, &status) {
+            (InternalCloneStatus::Cloning(old), InternalCloneStatus::Cloning(new))
+                if old == new =>
+            {
+                return
+            }
+            (InternalCloneStatus::Queued, InternalCloneStatus::Queued) => return,
+            // Late updates, e.g. from a timed-out clone's thread, can't reopen a finished task
+            (
+                InternalCloneStatus::Completed | InternalCloneStatus::Failed(_),
+                InternalCloneStatus::Cloning(_)
+                | InternalCloneStatus::Completed
+                | InternalCloneStatus::Failed(_),
+            ) => return,
+            _ => {}
+        }
+        task.status = status.clone();
+        // Sending only fails when nobody is watching
+        let _ = self.progress_tx.send(CloneProgressEvent {
+            url: task.url.clone(),
+            status,
+        });
+    }
+
+    /// Subscribes to clone status transitions from this point on.
+    pub fn subscribe_progress(&self) -> broadcast::Receiver<CloneProgressEvent> {
+        self.progress_tx.subscribe()
+    }
+
+    /// Whether every managed clone task has completed or failed.
+    pub fn all_clones_finished(&self) -> bool {
+        self.tasks
+            .lock()
+            .unwrap()
+            .values()
+            .all(|task| task.status.is_terminal())
     }
 
     /// Retrieves the current state of all managed clone tasks.
@@ -200,44 +801,380 @@ impl InternalRepoManagerLogic {
         self.tasks.lock().unwrap().clone()
     }
 
+    /// Tallies the clone tasks by status from a single snapshot of the task map, so the
+    /// counts always add up to the number of tasks even while clones are running.
+    pub fn clone_summary(&self) -> CloneSummary {
+        let mut summary = CloneSummary::default();
+        for task in self.tasks.lock().unwrap().values() {
+            match &task.status {
+                InternalCloneStatus::Queued => summary.queued += 1,
+                InternalCloneStatus::Cloning(_) => summary.cloning += 1,
+                InternalCloneStatus::Completed => summary.completed += 1,
+                InternalCloneStatus::Failed(err) => {
+                    summary.failed += 1;
+                    summary.failures.push((task.url.clone(), err.clone()));
+                }
+            }
+        }
+        summary.failures.sort();
+        summary
+    }
+
+    /// Checks every managed URL with `parse_repo_parts`.
+    /// Returns each URL as given mapped to None if valid, or to the parse error.
+    pub fn validate_urls(&self) -> HashMap<String, Option<String>> {
+        self.tasks
+            .lock()
+            .unwrap()
+            .values()
+            .map(|task| (task.url.clone(), parse_repo_parts(&task.url).err()))
+            .collect()
+    }
+
+    /// Registers a repository that is already on disk as a completed task, so it can be
+    /// analyzed under `identifier` without cloning. Replaces any task with the same identifier.
+    pub fn register_local_repo(&self, identifier: &str, path: &Path) -> Result<(), String> {
+        let repo = git2::Repository::open(path)
+            .map_err(|e| format!("{:?} is not a git repository: {}", path, e))?;
+        // Store the working directory even if the path pointed at the .git directory
+        let path = match repo.workdir() {
+            Some(workdir) if path.ends_with(".git") => workdir.to_path_buf(),
+            _ => path.to_path_buf(),
+        };
+        self.tasks.lock().unwrap().insert(
+            canonicalize_url(identifier),
+            InternalRepoCloneTask {
+                url: identifier.to_string(),
+                status: InternalCloneStatus::Completed,
+                temp_dir: Some(path),
+                attempts: 0,
+            },
+        );
+        Ok(())
+    }
+
+    /// Whether `url` names one of this manager's repositories.
+    pub fn manages(&self, url: &str) -> bool {
+        self.tasks
+            .lock()
+            .unwrap()
+            .contains_key(&canonicalize_url(url))
+    }
+
+    /// Returns the local path of a repository whose clone has completed.
+    pub fn get_repo_path(&self, url: &str) -> Result<PathBuf, String> {
+        let tasks_guard = self.tasks.lock().unwrap();
+        let task = tasks_guard
+            .get(&canonicalize_url(url))
+            .ok_or_else(|| format!("Repository {} is not managed", url))?;
+        match (&task.status, &task.temp_dir) {
+            (InternalCloneStatus::Completed, Some(path)) => Ok(path.clone()),
+            _ => Err(format!("Repository {} has not been cloned yet", url)),
+        }
+    }
+
     /// Performs git blame concurrently on multiple files within a specified repository.
+    /// At most `max_parallel` blames run at once (default: number of CPUs), each on a worker
+    /// that opens the repository once and takes files from a shared queue.
+    /// Successful results are cached per commit and reused until the repository's HEAD moves.
     pub async fn bulk_blame(
         &self,
-        repo_path: &PathBuf,
+        repo_path: &Path,
         file_paths: Vec<String>,
+        options: BlameOptions,
+        max_parallel: Option<usize>,
     ) -> Result<HashMap<String, Result<Vec<BlameLineInfo>, String>>, String> {
-        // 2. Create futures for each file's blame operation run via spawn_blocking
-        let mut blame_futures = Vec::new();
+        let limit = max_parallel
+            .unwrap_or_else(|| {
+                std::thread::available_parallelism()
+                    .map(|n| n.get())
+                    .unwrap_or(4)
+            })
+            .max(1);
+        let mut final_results: HashMap<String, Result<Vec<BlameLineInfo>, String>> = HashMap::new();
+
+        // 1. Serve what we can from the cache; without a resolvable commit nothing is cached
+        let commit_sha = match resolve_blame_commit(repo_path, options.rev.as_deref()) {
+            Ok((head_sha, commit_sha)) => {
+                self.blame_cache
+                    .lock()
+                    .unwrap()
+                    .sync_head(repo_path, &head_sha);
+                Some(commit_sha)
+            }
+            Err(_) => None,
+        };
+        let mut uncached = Vec::new();
         for file_path in file_paths {
-            let repo_path_clone = repo_path.clone();
-            let file_path_clone = file_path.clone();
-            let handle: JoinHandle<Result<Vec<BlameLineInfo>, String>> =
-                tokio::task::spawn_blocking(move || {
-                    get_blame_for_file(&repo_path_clone, &file_path_clone)
-                });
-            blame_futures.push(async move { (file_path, handle.await) });
+            let cached = commit_sha.as_ref().and_then(|sha| {
+                let key = BlameCacheKey::new(sha, &file_path, &options);
+                self.blame_cache.lock().unwrap().get(repo_path, &key)
+            });
+            match cached {
+                Some(lines) => {
+                    final_results.insert(file_path, Ok(lines));
+                }
+                None => uncached.push(file_path),
+            }
         }
-        let joined_results = join_all(blame_futures).await;
-        let mut final_results: HashMap<String, Result<Vec<BlameLineInfo>, String>> = HashMap::new();
-        for (file_path, join_result) in joined_results {
+
+        // 2. Blame the rest on up to `limit` blocking workers sharing one queue of files
+        let uncached = Arc::new(uncached);
+        let next_index = Arc::new(AtomicUsize::new(0));
+        let workers = (0..limit.min(uncached.len())).map(|_| {
+            let repo_path = repo_path.to_path_buf();
+            let files = Arc::clone(&uncached);
+            let next_index = Arc::clone(&next_index);
+            let options = options.clone();
+            tokio::task::spawn_blocking(move || {
+                let next_file = || {
+                    files
+                        .get(next_index.fetch_add(1, Ordering::Relaxed))
+                        .cloned()
+                };
+                blame_files(&repo_path, next_file, &options)
+            })
+        });
+        let mut worker_error = None;
+        for join_result in join_all(workers).await {
             match join_result {
-                Ok(blame_result) => {
-                    final_results.insert(file_path, blame_result);
-                }
-                Err(join_error) => {
-                    final_results.insert(
-                        file_path,
-                        Err(format!("Blame task execution failed: {}", join_error)),
-                    );
+                Ok(blame_results) => {
+                    for (file_path, blame_result) in blame_results {
+                        if let (Some(sha), Ok(lines)) = (&commit_sha, &blame_result) {
+                            let key = BlameCacheKey::new(sha, &file_path, &options);
+                            self.blame_cache
+                                .lock()
+                                .unwrap()
+                                .insert(repo_path, key, lines.clone());
+                        }
+                        final_results.insert(file_path, blame_result);
+                    }
                 }
+                Err(join_error) => worker_error = Some(join_error.to_string()),
+            }
+        }
+        // Files taken by a worker that panicked have no result of their own
+        if let Some(join_error) = worker_error {
+            for file_path in uncached.iter() {
+                final_results
+                    .entry(file_path.clone())
+                    .or_insert_with(|| Err(format!("Blame task execution failed: {}", join_error)));
             }
         }
         Ok(final_results)
     }
 
+    /// Blames `file_paths` in a cloned repository and counts the surviving lines per author.
+    /// Files that fail to blame are reported under `errors`.
+    pub async fn blame_authorship(
+        &self,
+        repo_url: &str,
+        file_paths: Vec<String>,
+        identity_map: HashMap<String, String>,
+    ) -> Result<BlameAuthorship, String> {
+        let repo_path = self.get_repo_path(repo_url)?;
+        let blame_results = self
+            .bulk_blame(&repo_path, file_paths, BlameOptions::default(), None)
+            .await?;
+        Ok(summarize_authorship(blame_results, &identity_map))
+    }
+
     /// Analyzes the commit history of a cloned repository using parallel processing.
-    /// This method is synchronous internally but designed to be called from an async context.
-    pub fn get_commit_analysis(&self, repo_path: &PathBuf) -> Result<Vec<CommitInfo>, String> {
-        extract_commits_parallel(repo_path.clone(), String::new())
+    /// The revwalk runs on the blocking thread pool so it doesn't stall the async executor.
+    pub async fn get_commit_analysis(
+        &self,
+        repo_path: &Path,
+        options: CommitAnalysisOptions,
+    ) -> Result<Vec<CommitInfo>, String> {
+        let repo_path = repo_path.to_path_buf();
+        tokio::task::spawn_blocking(move || {
+            extract_commits_parallel(repo_path, String::new(), &options)
+        })
+        .await
+        .unwrap_or_else(|join_error| Err(format!("Commit analysis task failed: {}", join_error)))
+    }
+
+    /// Builds the co-authorship graph of a cloned repository's history
+    /// (see `coauthorship_graph`).
+    pub async fn coauthorship_graph(
+        &self,
+        repo_url: &str,
+        identity_map: HashMap<String, String>,
+    ) -> Result<CoauthorshipGraph, String> {
+        let repo_path = self.get_repo_path(repo_url)?;
+        let options = CommitAnalysisOptions {
+            parse_trailers: true,
+            ..Default::default()
+        };
+        let commits = self.get_commit_analysis(&repo_path, options).await?;
+        Ok(coauthorship_graph(&commits, &identity_map))
+    }
+
+    /// Bins a cloned repository's commits by author date (see `commit_activity`).
+    pub async fn commit_activity(
+        &self,
+        repo_url: &str,
+        bucket: ActivityBucket,
+        utc_offset: Option<i32>,
+    ) -> Result<Vec<ActivityBin>, String> {
+        let repo_path = self.get_repo_path(repo_url)?;
+        let commits = self
+            .get_commit_analysis(&repo_path, CommitAnalysisOptions::default())
+            .await?;
+        Ok(commit_activity(&commits, bucket, utc_offset))
+    }
+
+    /// Streams the commit history of a cloned repository to `output` as JSON lines.
+    /// Returns the number of commits written.
+    pub async fn stream_commits_to_file(
+        &self,
+        repo_url: &str,
+        output: PathBuf,
+        options: CommitAnalysisOptions,
+    ) -> Result<usize, String> {
+        let repo_path = self.get_repo_path(repo_url)?;
+        tokio::task::spawn_blocking(move || stream_commits_to_file(&repo_path, &output, &options))
+            .await
+            .unwrap_or_else(|join_error| {
+                Err(format!("Commit streaming task failed: {}", join_error))
+            })
+    }
+
+    /// Finds force-pushes of the default branch in a cloned repository's reflog.
+    pub async fn detect_force_pushes(&self, repo_url: &str) -> Result<Vec<ForcePushInfo>, String> {
+        let repo_path = self.get_repo_path(repo_url)?;
+        tokio::task::spawn_blocking(move || detect_force_pushes(&repo_path))
+            .await
+            .unwrap_or_else(|join_error| {
+                Err(format!("Force-push detection task failed: {}", join_error))
+            })
+    }
+
+    /// Analyzes a cloned repository and fetches its GitHub data concurrently, with the same
+    /// defaults as the individual methods. The clone is used as is, never re-cloned.
+    pub async fn fetch_repo_report(&self, repo_url: &str) -> Result<RepoReport, String> {
+        let repo_path = self.get_repo_path(repo_url)?;
+        let urls = vec![repo_url.to_string()];
+        let branch_path = repo_path.clone();
+        let github_token = self.github_token();
+        let pr_options = PullRequestFetchOptions::default();
+
+        let (commits, branches, collaborators, issues, pull_requests) = tokio::join!(
+            self.get_commit_analysis(&repo_path, CommitAnalysisOptions::default()),
+            async move {
+                tokio::task::spawn_blocking(move || extract_branches(&branch_path))
+                    .await
+                    .unwrap_or_else(|e| Err(format!("Branch analysis task failed: {}", e)))
+            },
+            fetch_collaborators(
+                urls.clone(),
+                &self.github_username,
+                &github_token,
+                &self.api_config,
+                None,
+                None,
+            ),
+            fetch_issues(
+                urls.clone(),
+                &self.github_username,
+                &github_token,
+                &self.api_config,
+                None,
+                None,
+                None,
+            ),
+            fetch_pull_requests(
+                urls,
+                &self.github_username,
+                &github_token,
+                &self.api_config,
+                None,
+                None,
+                &pr_options,
+            ),
+        );
+
+        let report = RepoReport {
+            commits,
+            branches,
+            collaborators: single_repo_result(collaborators, repo_url),
+            issues: single_repo_result(issues, repo_url),
+            pull_requests: single_repo_result(pull_requests, repo_url),
+        };
+        if let Ok(commits) = &report.commits {
+            self.record_result("commits", repo_url, commits);
+        }
+        if let Ok(branches) = &report.branches {
+            self.record_result("branches", repo_url, branches);
+        }
+        if let Ok(collaborators) = &report.collaborators {
+            self.record_result("collaborators", repo_url, collaborators);
+        }
+        if let Ok(issues) = &report.issues {
+            self.record_result("issues", repo_url, issues);
+        }
+        if let Ok(pull_requests) = &report.pull_requests {
+            self.record_result("pull_requests", repo_url, pull_requests);
+        }
+        Ok(report)
+    }
+
+    /// Diffs two revisions of a cloned repository (see `diff_revisions`).
+    pub async fn diff_stats(
+        &self,
+        repo_url: &str,
+        base: String,
+        head: String,
+    ) -> Result<DiffStats, String> {
+        let repo_path = self.get_repo_path(repo_url)?;
+        tokio::task::spawn_blocking(move || diff_revisions(&repo_path, &base, &head))
+            .await
+            .unwrap_or_else(|join_error| Err(format!("Diff task failed: {}", join_error)))
+    }
+
+    /// Analyzes the commit history of several cloned repositories in parallel.
+    /// Repositories that are unmanaged or not completed get an error entry.
+    pub async fn get_commit_analysis_bulk(
+        &self,
+        repo_urls: Vec<String>,
+        options: CommitAnalysisOptions,
+    ) -> HashMap<String, Result<Vec<CommitInfo>, String>> {
+        let mut results = HashMap::new();
+        let mut repo_paths = Vec::new();
+        for url in repo_urls {
+            match self.get_repo_path(&url) {
+                Ok(path) => repo_paths.push((url, path)),
+                Err(e) => {
+                    results.insert(url, Err(e));
+                }
+            }
+        }
+
+        let urls: Vec<String> = repo_paths.iter().map(|(url, _)| url.clone()).collect();
+        match tokio::task::spawn_blocking(move || extract_commits_bulk(repo_paths, &options)).await
+        {
+            Ok(analyzed) => results.extend(analyzed),
+            Err(join_error) => {
+                for url in urls {
+                    let error = format!("Commit analysis task failed: {}", join_error);
+                    results.insert(url, Err(error));
+                }
+            }
+        }
+        results
+    }
+
+    /// Performs commit analysis for the commits added since `last_sha`.
+    pub async fn get_commit_analysis_since(
+        &self,
+        repo_path: &Path,
+        last_sha: String,
+    ) -> Result<IncrementalCommits, String> {
+        let repo_path = repo_path.to_path_buf();
+        tokio::task::spawn_blocking(move || {
+            extract_commits_since(repo_path, String::new(), &last_sha)
+        })
+        .await
+        .unwrap_or_else(|join_error| Err(format!("Commit analysis task failed: {}", join_error)))
     }
 }
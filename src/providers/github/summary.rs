@@ -0,0 +1,213 @@
+use git2::{BranchType, Repository, Sort};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::api_client::{api_error, create_github_client, request_error, ApiClientConfig};
+use crate::repo::parse_repo_parts;
+
+/// Headline numbers for a single repository.
+///
+/// Git-derived fields always come from the local clone. API-derived fields are
+/// `None` when the GitHub API can't be reached (e.g. local or non-GitHub URLs).
+#[derive(Debug, Clone)]
+pub struct RepoSummary {
+    pub commit_count: usize,
+    pub contributor_count: usize,
+    pub branch_count: usize,
+    pub open_issues: Option<u64>,
+    pub open_prs: Option<u64>,
+    pub default_branch: Option<String>,
+    pub last_commit_date: Option<i64>, // Seconds since epoch
+    pub primary_language: Option<String>,
+}
+
+/// Statistics computed from the local clone
+struct LocalStats {
+    commit_count: usize,
+    contributor_count: usize,
+    branch_count: usize,
+    head_branch: Option<String>,
+    last_commit_date: Option<i64>,
+}
+
+/// Statistics fetched from the GitHub API
+struct RemoteStats {
+    open_issues: u64,
+    open_prs: u64,
+    default_branch: String,
+    primary_language: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RepoResponse {
+    default_branch: String,
+    language: Option<String>,
+    // GitHub counts open pull requests as issues here
+    open_issues_count: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    total_count: u64,
+}
+
+/// Builds the summary for a cloned repository.
+pub async fn build_summary(
+    repo_url: &str,
+    repo_path: PathBuf,
+    github_token: &str,
+    api_config: &ApiClientConfig,
+) -> Result<RepoSummary, String> {
+    let local = tokio::task::spawn_blocking(move || collect_local_stats(&repo_path))
+        .await
+        .unwrap_or_else(|join_error| Err(format!("Summary task failed: {}", join_error)))?;
+
+    let remote = match fetch_remote_stats(repo_url, github_token, api_config).await {
+        Ok(stats) => Some(stats),
+        Err(e) => {
+            eprintln!(
+                "Warning: Failed to fetch repository details for {}: {}",
+                repo_url, e
+            );
+            None
+        }
+    };
+
+    Ok(RepoSummary {
+        commit_count: local.commit_count,
+        contributor_count: local.contributor_count,
+        branch_count: local.branch_count,
+        open_issues: remote.as_ref().map(|r| r.open_issues),
+        open_prs: remote.as_ref().map(|r| r.open_prs),
+        last_commit_date: local.last_commit_date,
+        default_branch: remote
+            .as_ref()
+            .map(|r| r.default_branch.clone())
+            .or(local.head_branch),
+        primary_language: remote.and_then(|r| r.primary_language),
+    })
+}
+
+/// Walks the local clone for commit, contributor and branch counts.
+fn collect_local_stats(repo_path: &Path) -> Result<LocalStats, String> {
+    let repo = Repository::open(repo_path)
+        .map_err(|e| format!("Failed to open repository at {:?}: {}", repo_path, e))?;
+
+    let head = repo.head().ok();
+    let head_branch = head
+        .as_ref()
+        .filter(|h| h.is_branch())
+        .and_then(|h| h.shorthand().map(|s| s.to_string()));
+
+    let mut commit_count = 0;
+    let mut authors = HashSet::new();
+    let mut last_commit_date = None;
+
+    // An empty repository has no HEAD to walk from
+    if head.is_some() {
+        let mut revwalk = repo
+            .revwalk()
+            .map_err(|e| format!("Failed to create revwalk: {}", e))?;
+        revwalk
+            .push_head()
+            .map_err(|e| format!("Failed to push HEAD: {}", e))?;
+        revwalk
+            .set_sorting(Sort::TIME)
+            .map_err(|e| format!("Failed to set revwalk sorting: {}", e))?;
+
+        for oid in revwalk {
+            let oid = oid.map_err(|e| format!("Failed to walk commits: {}", e))?;
+            let commit = repo
+                .find_commit(oid)
+                .map_err(|e| format!("Failed to find commit {}: {}", oid, e))?;
+            let author = commit.author();
+            authors.insert(author.email().unwrap_or("").to_lowercase());
+            commit_count += 1;
+        }
+
+        last_commit_date = repo
+            .head()
+            .ok()
+            .and_then(|h| h.peel_to_commit().ok())
+            .map(|c| c.time().seconds());
+    }
+
+    // A local branch and its remote-tracking counterpart count once
+    let mut branch_names = HashSet::new();
+    let branches = repo
+        .branches(None)
+        .map_err(|e| format!("Failed to list branches: {}", e))?;
+    for (branch, branch_type) in branches.flatten() {
+        let Ok(Some(name)) = branch.name() else {
+            continue;
+        };
+        let name = match branch_type {
+            BranchType::Local => name,
+            BranchType::Remote => match name.split_once('/') {
+                Some((_, "HEAD")) => continue,
+                Some((_, short)) => short,
+                None => name,
+            },
+        };
+        branch_names.insert(name.to_string());
+    }
+
+    Ok(LocalStats {
+        commit_count,
+        contributor_count: authors.len(),
+        branch_count: branch_names.len(),
+        head_branch,
+        last_commit_date,
+    })
+}
+
+/// Fetches repository metadata and the open pull request count from the GitHub API.
+async fn fetch_remote_stats(
+    repo_url: &str,
+    github_token: &str,
+    api_config: &ApiClientConfig,
+) -> Result<RemoteStats, String> {
+    let (owner, repo) = parse_repo_parts(repo_url)?;
+    let client = create_github_client(github_token, api_config)
+        .map_err(|e| format!("Failed to create GitHub client: {}", e))?;
+
+    let repo_api_url = format!("https://api.github.com/repos/{}/{}", owner, repo);
+    let response = client
+        .get(&repo_api_url)
+        .send()
+        .await
+        .map_err(|e| request_error("API request failed", e))?;
+    if !response.status().is_success() {
+        return Err(api_error("GitHub API error", response).await);
+    }
+    let repo_info = response
+        .json::<RepoResponse>()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+    let search_url = format!(
+        "https://api.github.com/search/issues?q=repo:{}/{}+type:pr+state:open&per_page=1",
+        owner, repo
+    );
+    let response = client
+        .get(&search_url)
+        .send()
+        .await
+        .map_err(|e| request_error("API request failed", e))?;
+    if !response.status().is_success() {
+        return Err(api_error("GitHub API error", response).await);
+    }
+    let open_prs = response
+        .json::<SearchResponse>()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))?
+        .total_count;
+
+    Ok(RemoteStats {
+        open_issues: repo_info.open_issues_count.saturating_sub(open_prs),
+        open_prs,
+        default_branch: repo_info.default_branch,
+        primary_language: repo_info.language,
+    })
+}
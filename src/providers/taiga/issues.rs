@@ -32,10 +32,7 @@ pub async fn fetch_issues_for_project(
     project_id: i64,
 ) -> Result<Vec<IssueResponse>, TaigaError> {
     let endpoint = format!("issues?project={}", project_id);
-    let response = client
-        .get(&endpoint)
-        .await
-        .map_err(|e| TaigaError::ApiError(e))?;
+    let response = client.get(&endpoint).await.map_err(TaigaError::ApiError)?;
 
     serde_json::from_str(&response).map_err(|e| TaigaError::ParseError(e.to_string()))
 }
@@ -46,10 +43,7 @@ pub async fn fetch_issues_for_sprint(
     sprint_id: i64,
 ) -> Result<Vec<IssueResponse>, TaigaError> {
     let endpoint = format!("issues?milestone={}", sprint_id);
-    let response = client
-        .get(&endpoint)
-        .await
-        .map_err(|e| TaigaError::ApiError(e))?;
+    let response = client.get(&endpoint).await.map_err(TaigaError::ApiError)?;
 
     serde_json::from_str(&response).map_err(|e| TaigaError::ParseError(e.to_string()))
 }
@@ -57,10 +51,7 @@ pub async fn fetch_issues_for_sprint(
 /// Fetch a single issue by ID
 pub async fn fetch_issue(client: &TaigaClient, issue_id: i64) -> Result<IssueResponse, TaigaError> {
     let endpoint = format!("issues/{}", issue_id);
-    let response = client
-        .get(&endpoint)
-        .await
-        .map_err(|e| TaigaError::ApiError(e))?;
+    let response = client.get(&endpoint).await.map_err(TaigaError::ApiError)?;
 
     serde_json::from_str(&response).map_err(|e| TaigaError::ParseError(e.to_string()))
 }
@@ -61,7 +61,7 @@ pub async fn fetch_complete_project_data(
 
     // Extract all task IDs for fetching task histories
     let mut task_ids = Vec::new();
-    for (_, sprint_tasks) in &tasks {
+    for sprint_tasks in tasks.values() {
         for task in sprint_tasks {
             task_ids.push(task.id);
         }
@@ -25,10 +25,7 @@ pub async fn fetch_project_by_slug(
     slug: &str,
 ) -> Result<TaigaProject, TaigaError> {
     let endpoint = format!("projects/by_slug?slug={}", slug);
-    let response = client
-        .get(&endpoint)
-        .await
-        .map_err(|e| TaigaError::ApiError(e))?;
+    let response = client.get(&endpoint).await.map_err(TaigaError::ApiError)?;
 
     serde_json::from_str(&response).map_err(|e| TaigaError::ParseError(e.to_string()))
 }
@@ -39,10 +36,7 @@ pub async fn fetch_project_members(
     project_id: i64,
 ) -> Result<Vec<MemberResponse>, TaigaError> {
     let endpoint = format!("memberships?project={}", project_id);
-    let response = client
-        .get(&endpoint)
-        .await
-        .map_err(|e| TaigaError::ApiError(e))?;
+    let response = client.get(&endpoint).await.map_err(TaigaError::ApiError)?;
 
     serde_json::from_str(&response).map_err(|e| TaigaError::ParseError(e.to_string()))
 }
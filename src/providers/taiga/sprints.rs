@@ -31,10 +31,7 @@ pub async fn fetch_sprints(
     closed: bool,
 ) -> Result<Vec<SprintResponse>, TaigaError> {
     let endpoint = format!("milestones?project={}&closed={}", project_id, closed);
-    let response = client
-        .get(&endpoint)
-        .await
-        .map_err(|e| TaigaError::ApiError(e))?;
+    let response = client.get(&endpoint).await.map_err(TaigaError::ApiError)?;
 
     serde_json::from_str(&response).map_err(|e| TaigaError::ParseError(e.to_string()))
 }
@@ -64,10 +61,7 @@ pub async fn fetch_sprint(
     sprint_id: i64,
 ) -> Result<SprintResponse, TaigaError> {
     let endpoint = format!("milestones/{}", sprint_id);
-    let response = client
-        .get(&endpoint)
-        .await
-        .map_err(|e| TaigaError::ApiError(e))?;
+    let response = client.get(&endpoint).await.map_err(TaigaError::ApiError)?;
 
     serde_json::from_str(&response).map_err(|e| TaigaError::ParseError(e.to_string()))
 }
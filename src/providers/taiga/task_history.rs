@@ -67,10 +67,7 @@ pub async fn fetch_task_history(
     task_id: i64,
 ) -> Result<Vec<TaskHistoryEvent>, TaigaError> {
     let endpoint = format!("history/task/{}", task_id);
-    let response = client
-        .get(&endpoint)
-        .await
-        .map_err(|e| TaigaError::ApiError(e))?;
+    let response = client.get(&endpoint).await.map_err(TaigaError::ApiError)?;
 
     serde_json::from_str(&response).map_err(|e| TaigaError::ParseError(e.to_string()))
 }
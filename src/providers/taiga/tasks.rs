@@ -39,10 +39,7 @@ pub async fn fetch_tasks_for_sprint(
     sprint_id: i64,
 ) -> Result<Vec<TaskResponse>, TaigaError> {
     let endpoint = format!("tasks?project={}&milestone={}", project_id, sprint_id);
-    let response = client
-        .get(&endpoint)
-        .await
-        .map_err(|e| TaigaError::ApiError(e))?;
+    let response = client.get(&endpoint).await.map_err(TaigaError::ApiError)?;
 
     serde_json::from_str(&response).map_err(|e| TaigaError::ParseError(e.to_string()))
 }
@@ -53,10 +50,7 @@ pub async fn fetch_user_story_description(
     user_story_id: i64,
 ) -> Result<HashMap<String, String>, TaigaError> {
     let endpoint = format!("userstories/{}", user_story_id);
-    let response = client
-        .get(&endpoint)
-        .await
-        .map_err(|e| TaigaError::ApiError(e))?;
+    let response = client.get(&endpoint).await.map_err(TaigaError::ApiError)?;
 
     serde_json::from_str(&response).map_err(|e| TaigaError::ParseError(e.to_string()))
 }
@@ -50,10 +50,7 @@ pub async fn fetch_user_story(
     user_story_id: i64,
 ) -> Result<UserStoryResponse, TaigaError> {
     let endpoint = format!("userstories/{}", user_story_id);
-    let response = client
-        .get(&endpoint)
-        .await
-        .map_err(|e| TaigaError::ApiError(e))?;
+    let response = client.get(&endpoint).await.map_err(TaigaError::ApiError)?;
 
     serde_json::from_str(&response).map_err(|e| TaigaError::ParseError(e.to_string()))
 }
@@ -64,10 +61,7 @@ pub async fn fetch_user_stories_for_sprint(
     sprint_id: i64,
 ) -> Result<Vec<UserStoryResponse>, TaigaError> {
     let endpoint = format!("userstories?milestone={}", sprint_id);
-    let response = client
-        .get(&endpoint)
-        .await
-        .map_err(|e| TaigaError::ApiError(e))?;
+    let response = client.get(&endpoint).await.map_err(TaigaError::ApiError)?;
 
     serde_json::from_str(&response).map_err(|e| TaigaError::ParseError(e.to_string()))
 }
@@ -78,10 +72,7 @@ pub async fn fetch_user_stories_for_project(
     project_id: i64,
 ) -> Result<Vec<UserStoryResponse>, TaigaError> {
     let endpoint = format!("userstories?project={}", project_id);
-    let response = client
-        .get(&endpoint)
-        .await
-        .map_err(|e| TaigaError::ApiError(e))?;
+    let response = client.get(&endpoint).await.map_err(TaigaError::ApiError)?;
 
     serde_json::from_str(&response).map_err(|e| TaigaError::ParseError(e.to_string()))
 }